@@ -294,7 +294,12 @@ fn bench_multi_font(c: &mut Criterion) {
 /// # Changes
 /// * v2: Add 4us gpu upload wait
 fn bench_multi_font_population(c: &mut Criterion) {
-    c.bench_function("multi_font_population_v2", |b| {
+    bench_multi_font_population_with(c, Packer::Rows, "multi_font_population_v2");
+    bench_multi_font_population_with(c, Packer::Skyline, "multi_font_population_skyline");
+}
+
+fn bench_multi_font_population_with(c: &mut Criterion, packer: Packer, name: &str) {
+    c.bench_function(name, |b| {
         // Use a much smaller amount of the test string, to offset the extra font-glyph
         // bench load & much slower performance of fresh population each run
         let up_to_index = TEST_STR.char_indices().nth(70).unwrap().0;
@@ -306,7 +311,10 @@ fn bench_multi_font_population(c: &mut Criterion) {
             .collect();
 
         b.iter(|| {
-            let mut cache = DrawCache::builder().dimensions(1024, 1024).build();
+            let mut cache = DrawCache::builder()
+                .dimensions(1024, 1024)
+                .packer(packer)
+                .build();
 
             for &(font_id, ref glyphs) in &font_glyphs {
                 for glyph in glyphs {
@@ -485,6 +493,11 @@ fn bench_resizing(c: &mut Criterion) {
 /// # Changes
 /// * v2: 4us gpu upload wait
 fn bench_moving_text_thrashing(c: &mut Criterion) {
+    bench_moving_text_thrashing_with(c, Packer::Rows, "moving_text_thrashing_v2");
+    bench_moving_text_thrashing_with(c, Packer::Skyline, "moving_text_thrashing_skyline");
+}
+
+fn bench_moving_text_thrashing_with(c: &mut Criterion, packer: Packer, name: &str) {
     let chars: Vec<_> = TEST_STR.chars().collect();
     let subsection_len = 60;
     let distinct_subsection: Vec<_> = chars.windows(subsection_len).collect();
@@ -516,9 +529,10 @@ fn bench_moving_text_thrashing(c: &mut Criterion) {
         .dimensions(320, 320)
         .scale_tolerance(0.1)
         .position_tolerance(0.1)
+        .packer(packer)
         .build();
 
-    c.bench_function("moving_text_thrashing_v2", |b| {
+    c.bench_function(name, |b| {
         b.iter(|| {
             // switch text variant each run to force cache to deal with moving text
             // requirements
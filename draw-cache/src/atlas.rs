@@ -0,0 +1,281 @@
+use crate::{draw_glyph, DrawCache, Rectangle};
+use ::ab_glyph::Font;
+use std::io;
+
+/// Debug overlays drawn onto an [`AtlasImage`](struct.AtlasImage.html).
+///
+/// # Example
+/// ```
+/// use glyph_brush_draw_cache::AtlasOverlay;
+///
+/// let overlay = AtlasOverlay {
+///     glyph_rects: true,
+///     ..AtlasOverlay::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct AtlasOverlay {
+    /// Outline texture rows with [`AtlasImage::ROW_SHADE`](struct.AtlasImage.html#associatedconstant.ROW_SHADE).
+    /// Caches using `Packer::Skyline` have no rows.
+    pub rows: bool,
+    /// Outline glyph texture rects, including any padding, with
+    /// [`AtlasImage::GLYPH_RECT_SHADE`](struct.AtlasImage.html#associatedconstant.GLYPH_RECT_SHADE).
+    pub glyph_rects: bool,
+}
+
+/// 8-bit greyscale image of the cache texture contents with pages stacked vertically,
+/// see [`DrawCache::atlas_image`](struct.DrawCache.html#method.atlas_image).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AtlasImage {
+    pub width: u32,
+    /// Height of all pages.
+    pub height: u32,
+    /// Row major pixels, `width * height` bytes.
+    pub pixels: Vec<u8>,
+}
+
+impl AtlasImage {
+    /// Shade of [`AtlasOverlay::rows`](struct.AtlasOverlay.html#structfield.rows) outlines.
+    pub const ROW_SHADE: u8 = 64;
+    /// Shade of [`AtlasOverlay::glyph_rects`](struct.AtlasOverlay.html#structfield.glyph_rects) outlines.
+    pub const GLYPH_RECT_SHADE: u8 = 192;
+
+    /// Returns the pixel at `(x, y)`.
+    #[inline]
+    pub fn pixel(&self, x: u32, y: u32) -> u8 {
+        self.pixels[y as usize * self.width as usize + x as usize]
+    }
+
+    /// Writes the image as a binary PGM (portable graymap).
+    pub fn write_pgm<W: io::Write>(&self, mut out: W) -> io::Result<()> {
+        write!(out, "P5\n{} {}\n255\n", self.width, self.height)?;
+        out.write_all(&self.pixels)
+    }
+
+    /// Writes the image as an 8-bit greyscale PNG.
+    #[cfg(feature = "png")]
+    pub fn write_png<W: io::Write>(&self, out: W) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(out, self.width, self.height);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&self.pixels))
+            .map_err(io::Error::from)
+    }
+
+    /// Draws a 1px outline just inside `rect`.
+    fn outline(&mut self, rect: Rectangle<u32>, shade: u8) {
+        if rect.width() == 0 || rect.height() == 0 {
+            return;
+        }
+        let width = self.width as usize;
+        let (right, bottom) = (rect.max[0] - 1, rect.max[1] - 1);
+        let mut set = |x: u32, y: u32| self.pixels[y as usize * width + x as usize] = shade;
+        for x in rect.min[0]..rect.max[0] {
+            set(x, rect.min[1]);
+            set(x, bottom);
+        }
+        for y in rect.min[1]..rect.max[1] {
+            set(rect.min[0], y);
+            set(right, y);
+        }
+    }
+}
+
+impl DrawCache {
+    /// Redraws the cached glyphs into an 8-bit greyscale image of the cache texture, to
+    /// check texture uploads & coordinates without a GPU.
+    ///
+    /// `fonts` must be those used to cache the glyphs. LCD subpixel coverage is averaged &
+    /// signed distance fields are drawn as distance values.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use glyph_brush_draw_cache::*;
+    /// # let cache = DrawCache::builder().build();
+    /// # let fonts: Vec<ab_glyph::FontArc> = vec![];
+    /// let overlay = AtlasOverlay {
+    ///     rows: true,
+    ///     glyph_rects: true,
+    /// };
+    /// let image = cache.atlas_image(&fonts, overlay);
+    /// image.write_pgm(std::fs::File::create("atlas.pgm")?)?;
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn atlas_image<F: Font>(&self, fonts: &[F], overlay: AtlasOverlay) -> AtlasImage {
+        let (width, height) = (self.width, self.height * self.pages);
+        let mut image = AtlasImage {
+            width,
+            height,
+            pixels: vec![0; width as usize * height as usize],
+        };
+        let bpp = self.texture_format.bytes_per_pixel();
+        let glyphs = || {
+            self.rows
+                .values()
+                .flat_map(|row| &row.glyphs)
+                .chain(self.skyline_glyphs.values())
+        };
+
+        for info in glyphs() {
+            let font = &fonts[info.glyph_info.font_id];
+            let outlined = match self.outline_glyph(font, &info.glyph, info.glyph_info.style) {
+                Some(outlined) => outlined,
+                None => continue,
+            };
+            let pixels = draw_glyph(
+                info.tex_coords,
+                &outlined,
+                self.pad_glyphs,
+                self.sdf,
+                self.texture_format,
+                self.coverage_lut.as_ref(),
+            );
+
+            let top = (info.page * self.height + info.tex_coords.min[1]) as usize;
+            let left = info.tex_coords.min[0] as usize;
+            for (y, line) in pixels.as_slice().chunks(pixels.col).enumerate() {
+                let image_left = (top + y) * width as usize + left;
+                for (x, texel) in line.chunks(bpp).enumerate() {
+                    let sum: usize = texel.iter().map(|v| *v as usize).sum();
+                    image.pixels[image_left + x] = (sum / bpp) as u8;
+                }
+            }
+        }
+
+        if overlay.rows {
+            for (top, row) in &self.rows {
+                let rect = Rectangle {
+                    min: [0, *top],
+                    max: [width, top + row.height],
+                };
+                image.outline(rect, AtlasImage::ROW_SHADE);
+            }
+        }
+        if overlay.glyph_rects {
+            for info in glyphs() {
+                let page_top = info.page * self.height;
+                let rect = Rectangle {
+                    min: [info.tex_coords.min[0], page_top + info.tex_coords.min[1]],
+                    max: [info.tex_coords.max[0], page_top + info.tex_coords.max[1]],
+                };
+                image.outline(rect, AtlasImage::GLYPH_RECT_SHADE);
+            }
+        }
+
+        image
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Packer;
+    use ::ab_glyph::*;
+    use glyph_brush_layout::*;
+
+    const FONT: &[u8] = include_bytes!("../../fonts/WenQuanYiMicroHei.ttf");
+
+    fn cached(packer: Packer) -> (DrawCache, Vec<u8>) {
+        let font = FontRef::try_from_slice(FONT).unwrap();
+        let mut cache = DrawCache::builder()
+            .dimensions(64, 32)
+            .pages(2)
+            .packer(packer)
+            .multithread(false)
+            .build();
+        let glyphs = Layout::default_single_line().calculate_glyphs(
+            &[&font],
+            &SectionGeometry::default(),
+            &[SectionText {
+                text: "ABCDEFGH",
+                scale: 16.0.into(),
+                ..<_>::default()
+            }],
+        );
+        for sg in glyphs {
+            cache.queue_glyph(0, sg.glyph);
+        }
+
+        // cpu texture of both pages
+        let mut texture = vec![0; 64 * 64];
+        cache
+            .cache_queued_paged(&[&font], |page, rect, data| {
+                let width = rect.width() as usize;
+                for (y, line) in data.chunks(width).enumerate() {
+                    let top = (page * 32 + rect.min[1]) as usize + y;
+                    let left = top * 64 + rect.min[0] as usize;
+                    texture[left..left + width].copy_from_slice(line);
+                }
+            })
+            .unwrap();
+        (cache, texture)
+    }
+
+    #[test]
+    fn atlas_image_matches_uploads() {
+        let font = FontRef::try_from_slice(FONT).unwrap();
+        for packer in [Packer::Rows, Packer::Skyline].iter().copied() {
+            let (cache, texture) = cached(packer);
+            let image = cache.atlas_image(&[&font], AtlasOverlay::default());
+            assert_eq!((image.width, image.height), (64, 64));
+            assert!(image.pixels.iter().any(|v| *v > 0));
+            assert_eq!(image.pixels, texture, "{:?}", packer);
+        }
+    }
+
+    #[test]
+    fn atlas_image_overlay() {
+        let font = FontRef::try_from_slice(FONT).unwrap();
+        let (cache, _) = cached(Packer::Rows);
+        let overlay = AtlasOverlay {
+            rows: true,
+            glyph_rects: true,
+        };
+        let image = cache.atlas_image(&[&font], overlay);
+
+        for (top, row) in &cache.rows {
+            // right edge of the row beyond its glyphs
+            assert_eq!(image.pixel(63, *top), AtlasImage::ROW_SHADE);
+            assert_eq!(image.pixel(63, top + row.height - 1), AtlasImage::ROW_SHADE);
+            for info in &row.glyphs {
+                let x = info.tex_coords.min[0];
+                assert_eq!(image.pixel(x, *top), AtlasImage::GLYPH_RECT_SHADE);
+            }
+        }
+    }
+
+    #[test]
+    fn write_pgm() {
+        let image = AtlasImage {
+            width: 3,
+            height: 2,
+            pixels: vec![0, 64, 128, 192, 255, 0],
+        };
+
+        let mut pgm = vec![];
+        image.write_pgm(&mut pgm).unwrap();
+        assert_eq!(pgm, b"P5\n3 2\n255\n\x00\x40\x80\xc0\xff\x00");
+    }
+
+    #[cfg(feature = "png")]
+    #[test]
+    fn write_png() {
+        let image = AtlasImage {
+            width: 3,
+            height: 2,
+            pixels: vec![0, 64, 128, 192, 255, 0],
+        };
+
+        let mut png = vec![];
+        image.write_png(&mut png).unwrap();
+
+        let mut reader = png::Decoder::new(png.as_slice()).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!((info.width, info.height), (3, 2));
+        assert_eq!(info.color_type, png::ColorType::Grayscale);
+        assert_eq!(&pixels[..info.buffer_size()], image.pixels.as_slice());
+    }
+}
@@ -0,0 +1,141 @@
+/// Corrections applied to rasterized glyph coverage before upload.
+///
+/// Linear coverage makes light text on dark backgrounds look thin & dark text on light
+/// backgrounds look bold. Use [`light_on_dark`](#method.light_on_dark) or
+/// [`dark_on_light`](#method.dark_on_light) to match native text rendering, or tune the
+/// fields directly.
+///
+/// Adjustments do not apply to signed distance field glyphs.
+///
+/// # Example
+/// ```
+/// use glyph_brush_draw_cache::{CoverageAdjustment, DrawCache};
+///
+/// let cache = DrawCache::builder()
+///     .coverage_adjustment(CoverageAdjustment::light_on_dark())
+///     .build();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CoverageAdjustment {
+    /// Gamma applied as `coverage^(1 / gamma)`. Values above `1.0` thicken glyphs &
+    /// values below `1.0` thin them. `1.0` is linear.
+    pub gamma: f32,
+    /// Contrast enhancement `[0, 1]`, blending coverage towards a smoothstep curve
+    /// to sharpen anti-aliased edges. `0.0` is linear.
+    pub contrast: f32,
+    /// Horizontal outline emboldening in pixels applied to small glyphs, fading out
+    /// from [`STEM_DARKENING_FULL_SCALE`](#associatedconstant.STEM_DARKENING_FULL_SCALE)
+    /// to [`STEM_DARKENING_NONE_SCALE`](#associatedconstant.STEM_DARKENING_NONE_SCALE).
+    /// Glyph advances are not affected. `0.0` disables.
+    pub stem_darkening: f32,
+}
+
+impl Default for CoverageAdjustment {
+    /// Linear coverage, no adjustment.
+    #[inline]
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+impl CoverageAdjustment {
+    /// Linear coverage, no adjustment.
+    pub const NONE: Self = Self {
+        gamma: 1.0,
+        contrast: 0.0,
+        stem_darkening: 0.0,
+    };
+
+    /// Pixel scale at & below which the full `stem_darkening` is applied.
+    pub const STEM_DARKENING_FULL_SCALE: f32 = 12.0;
+
+    /// Pixel scale at & above which no `stem_darkening` is applied.
+    pub const STEM_DARKENING_NONE_SCALE: f32 = 48.0;
+
+    /// Thickening correction for light text on dark backgrounds.
+    ///
+    /// `CoverageAdjustment { gamma: 1.45, contrast: 0.25, stem_darkening: 0.3 }`
+    #[inline]
+    pub fn light_on_dark() -> Self {
+        Self {
+            gamma: 1.45,
+            contrast: 0.25,
+            stem_darkening: 0.3,
+        }
+    }
+
+    /// Thinning correction for dark text on light backgrounds.
+    ///
+    /// `CoverageAdjustment { gamma: 0.8, contrast: 0.25, stem_darkening: 0.0 }`
+    #[inline]
+    pub fn dark_on_light() -> Self {
+        Self {
+            gamma: 0.8,
+            contrast: 0.25,
+            stem_darkening: 0.0,
+        }
+    }
+
+    /// Returns true if this does not alter coverage or outlines.
+    #[inline]
+    pub fn is_none(&self) -> bool {
+        *self == Self::NONE
+    }
+
+    /// Returns the adjusted `[0, 1]` coverage.
+    #[inline]
+    pub fn adjust(&self, coverage: f32) -> f32 {
+        let v = coverage.clamp(0.0, 1.0).powf(1.0 / self.gamma);
+        let smooth = v * v * (3.0 - 2.0 * v);
+        v + (smooth - v) * self.contrast
+    }
+
+    /// Returns the outline emboldening in pixels for glyphs of pixel height `scale`.
+    #[inline]
+    pub fn stem_darkening_at(&self, scale: f32) -> f32 {
+        let fade = (Self::STEM_DARKENING_NONE_SCALE - scale)
+            / (Self::STEM_DARKENING_NONE_SCALE - Self::STEM_DARKENING_FULL_SCALE);
+        self.stem_darkening * fade.clamp(0.0, 1.0)
+    }
+
+    /// Returns a lookup table of adjusted byte coverage for each byte coverage,
+    /// `None` if the adjustment does not alter coverage.
+    pub(crate) fn lut(&self) -> Option<[u8; 256]> {
+        if self.gamma == 1.0 && self.contrast == 0.0 {
+            return None;
+        }
+        let mut lut = [0; 256];
+        for (v, adjusted) in lut.iter_mut().enumerate() {
+            *adjusted = (self.adjust(v as f32 / 255.0) * 255.0).round() as u8;
+        }
+        Some(lut)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn adjust_curves() {
+        assert!(CoverageAdjustment::NONE.lut().is_none());
+        for v in [0.0, 0.3, 0.5, 1.0] {
+            assert_eq!(CoverageAdjustment::NONE.adjust(v), v);
+        }
+
+        let light = CoverageAdjustment::light_on_dark();
+        let dark = CoverageAdjustment::dark_on_light();
+        for adjustment in [light, dark] {
+            let lut = adjustment.lut().unwrap();
+            assert_eq!((lut[0], lut[255]), (0, 255));
+            // monotonic
+            assert!(lut.windows(2).all(|w| w[0] <= w[1]));
+        }
+        assert!(light.adjust(0.5) > 0.55);
+        assert!(dark.adjust(0.5) < 0.45);
+
+        assert_eq!(light.stem_darkening_at(10.0), 0.3);
+        assert!((light.stem_darkening_at(30.0) - 0.15).abs() < 1e-6);
+        assert_eq!(light.stem_darkening_at(64.0), 0.0);
+    }
+}
@@ -0,0 +1,131 @@
+use crate::ByteArray2d;
+use ::ab_glyph::*;
+
+/// Pixel format of the cache texture & the data passed to the `cache_queued` uploader.
+///
+/// # Example
+/// ```
+/// use glyph_brush_draw_cache::{DrawCache, TextureFormat};
+///
+/// let cache = DrawCache::builder()
+///     .texture_format(TextureFormat::LcdRgb8)
+///     .build();
+/// assert_eq!(cache.texture_format().bytes_per_pixel(), 3);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TextureFormat {
+    /// 1 byte per pixel of grayscale coverage.
+    A8,
+    /// 3 bytes per pixel of red, green & blue coverage for subpixel antialiasing
+    /// on LCD displays with horizontal RGB subpixels.
+    ///
+    /// Glyphs are rasterized at 3x horizontal resolution & filtered to reduce colour
+    /// fringes. Texels are aligned to whole pixels so glyphs should be drawn without
+    /// scaling or transforms.
+    ///
+    /// Drawing requires per-channel, "component alpha", blending where each colour channel
+    /// of the text is blended using the coverage of the same channel, i.e.
+    /// `out.c = text.c * cov.c * text.a + dst.c * (1 - cov.c * text.a)`.
+    /// Typically done with dual source blending or two draw passes.
+    LcdRgb8,
+}
+
+impl Default for TextureFormat {
+    /// `TextureFormat::A8`
+    #[inline]
+    fn default() -> Self {
+        Self::A8
+    }
+}
+
+impl TextureFormat {
+    /// Returns the number of bytes per texture pixel.
+    #[inline]
+    pub fn bytes_per_pixel(self) -> usize {
+        match self {
+            Self::A8 => 1,
+            Self::LcdRgb8 => 3,
+        }
+    }
+}
+
+/// FreeType's default 5-tap LCD filter, weights sum to 256.
+const FILTER: [u32; 5] = [8, 77, 86, 77, 8];
+
+/// Returns the glyph scaled 3x horizontally, so each outline pixel is a subpixel.
+#[inline]
+pub(crate) fn subpixel_glyph(glyph: &Glyph) -> Glyph {
+    Glyph {
+        id: glyph.id,
+        scale: PxScale {
+            x: glyph.scale.x * 3.0,
+            y: glyph.scale.y,
+        },
+        position: point(glyph.position.x * 3.0, glyph.position.y),
+    }
+}
+
+/// Returns subpixel glyph bounds widened to whole pixels, plus a pixel each side
+/// for the filter to spread into.
+#[inline]
+pub(crate) fn bounds(mut subpixel_bounds: Rect) -> Rect {
+    subpixel_bounds.min.x = ((subpixel_bounds.min.x / 3.0).floor() - 1.0) * 3.0;
+    subpixel_bounds.max.x = ((subpixel_bounds.max.x / 3.0).ceil() + 1.0) * 3.0;
+    subpixel_bounds
+}
+
+/// Draws the filtered subpixel glyph coverage into `width` x `height` RGB pixels,
+/// offset by 1 pixel if `padded`.
+pub(crate) fn draw(
+    glyph: &OutlinedGlyph,
+    width: usize,
+    height: usize,
+    padded: bool,
+    coverage_lut: Option<&[u8; 256]>,
+) -> ByteArray2d {
+    let pad = padded as usize;
+    let px_bounds = glyph.px_bounds();
+    let offset = (px_bounds.min.x - bounds(px_bounds).min.x) as usize;
+    let (w, h) = ((width - 2 * pad) * 3, height - 2 * pad);
+
+    let mut coverage = vec![0_u32; w * h];
+    glyph.draw(|x, y, v| {
+        let (x, y) = (x as usize + offset, y as usize);
+        if x < w && y < h {
+            coverage[y * w + x] = (v * 255.0).round() as u32;
+        }
+    });
+
+    let mut pixels = ByteArray2d::zeros(height, width * 3);
+    for y in 0..h {
+        let row = &coverage[y * w..(y + 1) * w];
+        for x in 0..w {
+            let sum: u32 = FILTER
+                .iter()
+                .enumerate()
+                .filter_map(|(k, weight)| Some(weight * row.get((x + k).checked_sub(2)?)?))
+                .sum();
+            let v = ((sum + 128) >> 8).min(255) as u8;
+            pixels[(y + pad, x + 3 * pad)] = match coverage_lut {
+                Some(lut) => lut[v as usize],
+                None => v,
+            };
+        }
+    }
+    pixels
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn whole_pixel_bounds() {
+        let bounds = bounds(Rect {
+            min: point(31.0, 2.0),
+            max: point(44.0, 20.0),
+        });
+        assert_eq!(bounds.min, point(27.0, 2.0));
+        assert_eq!(bounds.max, point(48.0, 20.0));
+    }
+}
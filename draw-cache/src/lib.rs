@@ -25,23 +25,38 @@
 //! # let font_id = 0;
 //! # let glyph: ab_glyph::Glyph = unimplemented!();
 //! match draw_cache.rect_for(font_id, &glyph) {
-//!     Some((tex_coords, px_coords)) => {}
+//!     Some((tex_coords, px_coords, _page)) => {}
 //!     None => {/* The glyph has no outline, or wasn't queued up to be cached */}
 //! }
 //! # Ok(()) }
 //! ```
 
+mod atlas;
+mod coverage;
 mod geometry;
+mod lcd;
+mod packer;
+mod sdf;
+mod stats;
+mod synthetic;
 
 /// Re-exported ab_glyph types.
 pub mod ab_glyph {
     pub use ab_glyph::*;
 }
 
+pub use atlas::{AtlasImage, AtlasOverlay};
+pub use coverage::CoverageAdjustment;
 pub use geometry::Rectangle;
+pub use lcd::TextureFormat;
+pub use packer::Packer;
+pub use sdf::Sdf;
+pub use stats::DrawCacheStats;
+pub use synthetic::SyntheticStyle;
 
 use ::ab_glyph::*;
 use linked_hash_map::LinkedHashMap;
+use packer::Skyline;
 use rustc_hash::{FxHashMap, FxHasher};
 use std::{
     collections::{HashMap, HashSet},
@@ -50,13 +65,15 @@ use std::{
     ops,
 };
 
-/// (Texture coordinates, pixel coordinates)
-pub type TextureCoords = (Rect, Rect);
+/// (Texture coordinates, pixel coordinates, texture page)
+pub type TextureCoords = (Rect, Rect, u32);
 
 type FxBuildHasher = BuildHasherDefault<FxHasher>;
 
 /// Indicates where a glyph texture is stored in the cache
 /// (row position, glyph index in row)
+///
+/// Row positions are offset by `page * height`, as if pages were stacked vertically.
 type TextureRowGlyphIndex = (u32, u32);
 
 /// Texture lookup key that uses scale & offset as integers attained
@@ -72,6 +89,7 @@ struct LossyGlyphInfo {
     /// `u16` is enough as subpixel position `[-0.5, 0.5]` converted to `[0, 1]`
     ///  divided by the min `position_tolerance` (`0.001`) is small.
     offset_over_tolerance: (u16, u16),
+    style: SyntheticStyle,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -142,6 +160,9 @@ struct Row {
 
 struct GlyphTexInfo {
     glyph_info: LossyGlyphInfo,
+    /// Queued glyph, used to redraw the texture.
+    glyph: Glyph,
+    page: u32,
     tex_coords: Rectangle<u32>,
     /// Used to calculate the bounds/texture pixel location for a similar glyph.
     ///
@@ -149,6 +170,35 @@ struct GlyphTexInfo {
     bounds_minus_position_over_scale: Rect,
 }
 
+impl GlyphTexInfo {
+    #[inline]
+    fn new(
+        glyph_info: LossyGlyphInfo,
+        glyph: Glyph,
+        page: u32,
+        tex_coords: Rectangle<u32>,
+        bounds: Rect,
+        g: &Glyph,
+    ) -> Self {
+        Self {
+            glyph_info,
+            glyph,
+            page,
+            tex_coords,
+            bounds_minus_position_over_scale: Rect {
+                min: point(
+                    (bounds.min.x - g.position.x) / g.scale.x,
+                    (bounds.min.y - g.position.y) / g.scale.y,
+                ),
+                max: point(
+                    (bounds.max.x - g.position.x) / g.scale.x,
+                    (bounds.max.y - g.position.y) / g.scale.y,
+                ),
+            },
+        }
+    }
+}
+
 trait PaddingAware {
     fn unpadded(self) -> Self;
 }
@@ -169,17 +219,22 @@ impl PaddingAware for Rectangle<u32> {
 /// # Example
 ///
 /// ```
-/// use glyph_brush_draw_cache::DrawCache;
+/// use glyph_brush_draw_cache::{CoverageAdjustment, DrawCache, Packer, TextureFormat};
 ///
 /// // Create a cache with all default values set explicitly
 /// // equivalent to `DrawCache::builder().build()`
 /// let default_cache = DrawCache::builder()
 ///     .dimensions(256, 256)
+///     .pages(1)
+///     .packer(Packer::Rows)
 ///     .scale_tolerance(0.1)
 ///     .position_tolerance(0.1)
 ///     .pad_glyphs(true)
 ///     .align_4x4(false)
 ///     .multithread(true)
+///     .sdf(None)
+///     .coverage_adjustment(CoverageAdjustment::NONE)
+///     .texture_format(TextureFormat::A8)
 ///     .build();
 ///
 /// // Create a cache with all default values, except with a dimension of 1024x1024
@@ -188,22 +243,32 @@ impl PaddingAware for Rectangle<u32> {
 #[derive(Debug, Clone)]
 pub struct DrawCacheBuilder {
     dimensions: (u32, u32),
+    pages: u32,
+    packer: Packer,
     scale_tolerance: f32,
     position_tolerance: f32,
     pad_glyphs: bool,
     align_4x4: bool,
     multithread: bool,
+    sdf: Option<Sdf>,
+    coverage_adjustment: CoverageAdjustment,
+    texture_format: TextureFormat,
 }
 
 impl Default for DrawCacheBuilder {
     fn default() -> Self {
         Self {
             dimensions: (256, 256),
+            pages: 1,
+            packer: Packer::Rows,
             scale_tolerance: 0.1,
             position_tolerance: 0.1,
             pad_glyphs: true,
             align_4x4: false,
             multithread: true,
+            sdf: None,
+            coverage_adjustment: CoverageAdjustment::NONE,
+            texture_format: TextureFormat::A8,
         }
     }
 }
@@ -227,6 +292,44 @@ impl DrawCacheBuilder {
         self
     }
 
+    /// Number of `width` x `height` texture pages, e.g. layers of a 2D texture array,
+    /// that will hold the cache contents on the GPU.
+    ///
+    /// Glyphs are added to further pages when earlier pages are full, before
+    /// glyphs are removed to make room. Useful when the queued glyphs cannot fit
+    /// into the maximum supported texture dimensions.
+    ///
+    /// This must match the number of pages of the actual texture used. Use
+    /// [`DrawCache::cache_queued_paged`](struct.DrawCache.html#method.cache_queued_paged)
+    /// to upload to pages other than the first.
+    ///
+    /// # Example (set to default value)
+    ///
+    /// ```
+    /// # use glyph_brush_draw_cache::DrawCache;
+    /// let cache = DrawCache::builder().pages(1).build();
+    /// ```
+    pub fn pages(mut self, pages: u32) -> Self {
+        self.pages = pages;
+        self
+    }
+
+    /// Texture packing strategy, see [`Packer`](enum.Packer.html).
+    ///
+    /// `Packer::Skyline` fits more mixed size glyphs into the same texture, but
+    /// reorders rather than removing unused glyphs when full.
+    ///
+    /// # Example (set to default value)
+    ///
+    /// ```
+    /// # use glyph_brush_draw_cache::{DrawCache, Packer};
+    /// let cache = DrawCache::builder().packer(Packer::Rows).build();
+    /// ```
+    pub fn packer(mut self, packer: Packer) -> Self {
+        self.packer = packer;
+        self
+    }
+
     /// Specifies the tolerances (maximum allowed difference) for judging
     /// whether an existing glyph in the cache is close enough to the
     /// requested glyph in scale to be used in its place. Due to floating
@@ -333,10 +436,69 @@ impl DrawCacheBuilder {
         self.multithread = multithread;
         self
     }
+    /// Rasterize glyphs as signed distance fields, see [`Sdf`](struct.Sdf.html).
+    ///
+    /// Each glyph is cached once at the reference scale & reused for all scales &
+    /// subpixel positions, ignoring `scale_tolerance` & `position_tolerance`. Useful
+    /// when drawing text at many scales, e.g. zoomable or 3D scenes.
+    ///
+    /// # Example (set to default value)
+    ///
+    /// ```
+    /// # use glyph_brush_draw_cache::DrawCache;
+    /// let cache = DrawCache::builder().sdf(None).build();
+    /// ```
+    pub fn sdf<S: Into<Option<Sdf>>>(mut self, sdf: S) -> Self {
+        self.sdf = sdf.into();
+        self
+    }
+
+    /// Gamma, contrast & stem darkening corrections applied to rasterized glyphs,
+    /// see [`CoverageAdjustment`](struct.CoverageAdjustment.html).
+    ///
+    /// # Example (set to default value)
+    ///
+    /// ```
+    /// # use glyph_brush_draw_cache::{CoverageAdjustment, DrawCache};
+    /// let cache = DrawCache::builder()
+    ///     .coverage_adjustment(CoverageAdjustment::NONE)
+    ///     .build();
+    /// ```
+    pub fn coverage_adjustment(mut self, coverage_adjustment: CoverageAdjustment) -> Self {
+        self.coverage_adjustment = coverage_adjustment;
+        self
+    }
+    /// Pixel format of the cache texture, see [`TextureFormat`](enum.TextureFormat.html).
+    ///
+    /// `TextureFormat::LcdRgb8` rasterizes glyphs with subpixel antialiasing, uploading
+    /// 3 bytes per pixel. Cannot be combined with `sdf`.
+    ///
+    /// # Example (set to default value)
+    ///
+    /// ```
+    /// # use glyph_brush_draw_cache::{DrawCache, TextureFormat};
+    /// let cache = DrawCache::builder()
+    ///     .texture_format(TextureFormat::A8)
+    ///     .build();
+    /// ```
+    pub fn texture_format(mut self, texture_format: TextureFormat) -> Self {
+        self.texture_format = texture_format;
+        self
+    }
 
     fn validated(self) -> Self {
+        assert!(self.pages >= 1);
         assert!(self.scale_tolerance >= 0.0);
         assert!(self.position_tolerance >= 0.0);
+        if let Some(sdf) = self.sdf {
+            assert!(sdf.reference_scale > 0.0);
+            assert!(sdf.spread > 0.0);
+            assert_eq!(self.texture_format, TextureFormat::A8);
+        }
+        let adjustment = self.coverage_adjustment;
+        assert!(adjustment.gamma > 0.0);
+        assert!((0.0..=1.0).contains(&adjustment.contrast));
+        assert!(adjustment.stem_darkening >= 0.0);
         let scale_tolerance = self.scale_tolerance.max(0.001);
         let position_tolerance = self.position_tolerance.max(0.001);
         #[cfg(not(target_arch = "wasm32"))]
@@ -355,8 +517,10 @@ impl DrawCacheBuilder {
     ///
     /// # Panics
     ///
-    /// `scale_tolerance` or `position_tolerance` are less than or equal to
-    /// zero.
+    /// `pages` is zero, `scale_tolerance` or `position_tolerance` are less than or
+    /// equal to zero, `sdf` `reference_scale` or `spread` are less than or equal to zero,
+    /// `coverage_adjustment` values are out of range, or `sdf` is used with a
+    /// `texture_format` other than `TextureFormat::A8`.
     ///
     /// # Example
     ///
@@ -367,35 +531,43 @@ impl DrawCacheBuilder {
     pub fn build(self) -> DrawCache {
         let DrawCacheBuilder {
             dimensions: (width, height),
+            pages,
+            packer,
             scale_tolerance,
             position_tolerance,
             pad_glyphs,
             align_4x4,
             multithread,
+            sdf,
+            coverage_adjustment,
+            texture_format,
         } = self.validated();
 
-        DrawCache {
+        let mut cache = DrawCache {
             scale_tolerance,
             position_tolerance,
             width,
             height,
+            pages,
+            packer,
             rows: LinkedHashMap::default(),
-            space_start_for_end: {
-                let mut m = HashMap::default();
-                m.insert(height, 0);
-                m
-            },
-            space_end_for_start: {
-                let mut m = HashMap::default();
-                m.insert(0, height);
-                m
-            },
+            space_start_for_end: HashMap::default(),
+            space_end_for_start: HashMap::default(),
+            skyline: Skyline::new(width, height, pages),
+            skyline_glyphs: HashMap::default(),
             queue: Vec::new(),
             all_glyphs: HashMap::default(),
             pad_glyphs,
             align_4x4,
             multithread,
-        }
+            sdf,
+            coverage_lut: coverage_adjustment.lut(),
+            coverage_adjustment,
+            texture_format,
+            stats: DrawCacheStats::default(),
+        };
+        cache.clear();
+        cache
     }
 
     /// Rebuilds a `DrawCache` with new attributes. All cached glyphs are cleared,
@@ -403,8 +575,10 @@ impl DrawCacheBuilder {
     ///
     /// # Panics
     ///
-    /// `scale_tolerance` or `position_tolerance` are less than or equal to
-    /// zero.
+    /// `pages` is zero, `scale_tolerance` or `position_tolerance` are less than or
+    /// equal to zero, `sdf` `reference_scale` or `spread` are less than or equal to zero,
+    /// `coverage_adjustment` values are out of range, or `sdf` is used with a
+    /// `texture_format` other than `TextureFormat::A8`.
     ///
     /// # Example
     ///
@@ -417,20 +591,31 @@ impl DrawCacheBuilder {
     pub fn rebuild(self, cache: &mut DrawCache) {
         let DrawCacheBuilder {
             dimensions: (width, height),
+            pages,
+            packer,
             scale_tolerance,
             position_tolerance,
             pad_glyphs,
             align_4x4,
             multithread,
+            sdf,
+            coverage_adjustment,
+            texture_format,
         } = self.validated();
 
         cache.width = width;
         cache.height = height;
+        cache.pages = pages;
+        cache.packer = packer;
         cache.scale_tolerance = scale_tolerance;
         cache.position_tolerance = position_tolerance;
         cache.pad_glyphs = pad_glyphs;
         cache.align_4x4 = align_4x4;
         cache.multithread = multithread;
+        cache.sdf = sdf;
+        cache.coverage_lut = coverage_adjustment.lut();
+        cache.coverage_adjustment = coverage_adjustment;
+        cache.texture_format = texture_format;
         cache.clear();
     }
 }
@@ -492,16 +677,27 @@ pub struct DrawCache {
     position_tolerance: f32,
     width: u32,
     height: u32,
+    pages: u32,
+    packer: Packer,
     rows: LinkedHashMap<u32, Row, FxBuildHasher>,
-    /// Mapping of row gaps bottom -> top
+    /// Mapping of row gaps bottom -> top, gaps never span pages
     space_start_for_end: FxHashMap<u32, u32>,
     /// Mapping of row gaps top -> bottom
     space_end_for_start: FxHashMap<u32, u32>,
-    queue: Vec<(usize, Glyph)>,
+    skyline: Skyline,
+    /// Glyphs packed with `Packer::Skyline`
+    skyline_glyphs: FxHashMap<LossyGlyphInfo, GlyphTexInfo>,
+    queue: Vec<(usize, Glyph, SyntheticStyle)>,
     all_glyphs: FxHashMap<LossyGlyphInfo, TextureRowGlyphIndex>,
     pad_glyphs: bool,
     align_4x4: bool,
     multithread: bool,
+    sdf: Option<Sdf>,
+    coverage_adjustment: CoverageAdjustment,
+    coverage_lut: Option<[u8; 256]>,
+    texture_format: TextureFormat,
+    /// Counters, gauges are calculated in `stats()`
+    stats: DrawCacheStats,
 }
 
 impl DrawCache {
@@ -527,21 +723,124 @@ impl DrawCache {
         (self.width, self.height)
     }
 
+    /// Returns the number of cache texture pages assumed by the cache. For proper
+    /// operation this should match the pages of the used GPU texture.
+    ///
+    /// See [`DrawCacheBuilder::pages`](struct.DrawCacheBuilder.html#method.pages).
+    pub fn pages(&self) -> u32 {
+        self.pages
+    }
+
+    /// Returns the texture packing strategy.
+    ///
+    /// See [`DrawCacheBuilder::packer`](struct.DrawCacheBuilder.html#method.packer).
+    pub fn packer(&self) -> Packer {
+        self.packer
+    }
+
+    /// Returns `true` if the cache spreads work across multiple CPU cores.
+    ///
+    /// See [`DrawCacheBuilder::multithread`](struct.DrawCacheBuilder.html#method.multithread).
+    pub fn multithread(&self) -> bool {
+        self.multithread && cfg!(not(target_arch = "wasm32"))
+    }
+
+    /// Returns the signed distance field settings, if glyphs are rasterized as
+    /// distance fields.
+    ///
+    /// See [`DrawCacheBuilder::sdf`](struct.DrawCacheBuilder.html#method.sdf).
+    pub fn sdf(&self) -> Option<Sdf> {
+        self.sdf
+    }
+
+    /// Returns the coverage corrections applied to rasterized glyphs.
+    ///
+    /// See [`DrawCacheBuilder::coverage_adjustment`](struct.DrawCacheBuilder.html#method.coverage_adjustment).
+    pub fn coverage_adjustment(&self) -> CoverageAdjustment {
+        self.coverage_adjustment
+    }
+
+    /// Returns the cache texture pixel format.
+    ///
+    /// See [`DrawCacheBuilder::texture_format`](struct.DrawCacheBuilder.html#method.texture_format).
+    pub fn texture_format(&self) -> TextureFormat {
+        self.texture_format
+    }
+
+    /// Returns cache usage statistics.
+    ///
+    /// See [`DrawCacheStats`](struct.DrawCacheStats.html).
+    pub fn stats(&self) -> DrawCacheStats {
+        let occupied_area: u64 = self
+            .rows
+            .values()
+            .flat_map(|row| &row.glyphs)
+            .chain(self.skyline_glyphs.values())
+            .map(|info| u64::from(info.tex_coords.width()) * u64::from(info.tex_coords.height()))
+            .sum();
+        let total_area = u64::from(self.width) * u64::from(self.height) * u64::from(self.pages);
+        let occupied_area_ratio = match total_area {
+            0 => 0.0,
+            total_area => occupied_area as f32 / total_area as f32,
+        };
+
+        DrawCacheStats {
+            glyphs: self.all_glyphs.len() + self.skyline_glyphs.len(),
+            rows: self.rows.len(),
+            occupied_area_ratio,
+            ..self.stats
+        }
+    }
+
     /// Queue a glyph for caching by the next call to `cache_queued`. `font_id`
     /// is used to disambiguate glyphs from different fonts. The user should
     /// ensure that `font_id` is unique to the font the glyph is from.
     pub fn queue_glyph(&mut self, font_id: usize, glyph: Glyph) {
-        self.queue.push((font_id, glyph));
+        self.queue_styled_glyph(font_id, glyph, SyntheticStyle::default());
+    }
+
+    /// Queue a glyph with a synthetic style for caching by the next call to `cache_queued`.
+    ///
+    /// Differently styled versions of the same glyph are cached separately.
+    pub fn queue_styled_glyph(&mut self, font_id: usize, glyph: Glyph, style: SyntheticStyle) {
+        self.queue.push((font_id, glyph, style));
     }
 
     /// Clears the cache. Does not affect the glyph queue.
     pub fn clear(&mut self) {
         self.rows.clear();
         self.space_end_for_start.clear();
-        self.space_end_for_start.insert(0, self.height);
         self.space_start_for_end.clear();
-        self.space_start_for_end.insert(self.height, 0);
+        for page in 0..self.pages {
+            let (top, bottom) = (page * self.height, (page + 1) * self.height);
+            self.space_end_for_start.insert(top, bottom);
+            self.space_start_for_end.insert(bottom, top);
+        }
         self.all_glyphs.clear();
+        self.skyline = Skyline::new(self.width, self.height, self.pages);
+        self.skyline_glyphs.clear();
+    }
+
+    /// Removes cached glyphs of fonts for which `keep` returns `false`, e.g. fonts that
+    /// will no longer be used, so their `font_id`s can be reused. Does not affect the
+    /// glyph queue.
+    ///
+    /// Texture space of removed glyphs is reclaimed when their row is evicted.
+    pub fn retain_fonts<P: FnMut(usize) -> bool>(&mut self, mut keep: P) {
+        let all_glyphs = &mut self.all_glyphs;
+        for (top, row) in self.rows.iter_mut() {
+            if row.glyphs.iter().all(|g| keep(g.glyph_info.font_id)) {
+                continue;
+            }
+            for g in &row.glyphs {
+                all_glyphs.remove(&g.glyph_info);
+            }
+            row.glyphs.retain(|g| keep(g.glyph_info.font_id));
+            for (index, g) in row.glyphs.iter().enumerate() {
+                all_glyphs.insert(g.glyph_info, (*top, index as u32));
+            }
+        }
+        self.skyline_glyphs.retain(|info, _| keep(info.font_id));
     }
 
     /// Clears the glyph queue.
@@ -553,16 +852,54 @@ impl DrawCache {
     pub fn to_builder(&self) -> DrawCacheBuilder {
         DrawCacheBuilder {
             dimensions: (self.width, self.height),
+            pages: self.pages,
+            packer: self.packer,
             position_tolerance: self.position_tolerance,
             scale_tolerance: self.scale_tolerance,
             pad_glyphs: self.pad_glyphs,
             align_4x4: self.align_4x4,
             multithread: self.multithread,
+            sdf: self.sdf,
+            coverage_adjustment: self.coverage_adjustment,
+            texture_format: self.texture_format,
         }
     }
 
+    /// Outlines a queued glyph as rasterized into the texture.
+    fn outline_glyph<F: Font>(
+        &self,
+        font: &F,
+        glyph: &Glyph,
+        style: SyntheticStyle,
+    ) -> Option<OutlinedGlyph> {
+        let stem_darkening = self.coverage_adjustment.stem_darkening_at(glyph.scale.y);
+        let (glyph, stem_darkening) = match (self.sdf, self.texture_format) {
+            (Some(sdf), _) => (sdf.reference_glyph(glyph), 0.0),
+            // subpixel outlines are 3x wider
+            (None, TextureFormat::LcdRgb8) => (lcd::subpixel_glyph(glyph), 3.0 * stem_darkening),
+            (None, TextureFormat::A8) => (glyph.clone(), stem_darkening),
+        };
+        synthetic::outline_glyph(font, glyph, style, stem_darkening)
+    }
+
     /// Returns glyph info with accuracy according to the set tolerances.
-    fn lossy_info_for(&self, font_id: usize, glyph: &Glyph) -> LossyGlyphInfo {
+    fn lossy_info_for(
+        &self,
+        font_id: usize,
+        glyph: &Glyph,
+        style: SyntheticStyle,
+    ) -> LossyGlyphInfo {
+        if self.sdf.is_some() {
+            // a single distance field is used for all scales & positions
+            return LossyGlyphInfo {
+                font_id,
+                glyph_id: glyph.id,
+                scale_over_tolerance: (0, 0),
+                offset_over_tolerance: (0, 0),
+                style,
+            };
+        }
+
         let scale = glyph.scale;
         let offset = normalised_offset_from_position(glyph.position);
 
@@ -578,6 +915,7 @@ impl DrawCache {
                 ((offset.x + 0.5) / self.position_tolerance + 0.5) as u16,
                 ((offset.y + 0.5) / self.position_tolerance + 0.5) as u16,
             ),
+            style,
         }
     }
 
@@ -591,10 +929,15 @@ impl DrawCache {
     /// uploads to the GPU. The information provided is the rectangular region
     /// to insert the pixel data into, and the pixel data itself. This data is
     /// provided in horizontal scanline format (row major), with stride equal to
-    /// the rectangle width.
+    /// the rectangle width multiplied by the
+    /// [`TextureFormat::bytes_per_pixel`](enum.TextureFormat.html#method.bytes_per_pixel).
     ///
     /// If successful returns a `CachedBy` that can indicate the validity of
     /// previously cached glyph textures.
+    ///
+    /// The texture page is not provided to `uploader`, caches with multiple
+    /// [`pages`](struct.DrawCacheBuilder.html#method.pages) should use
+    /// [`cache_queued_paged`](#method.cache_queued_paged).
     pub fn cache_queued<F, U>(
         &mut self,
         fonts: &[F],
@@ -603,12 +946,40 @@ impl DrawCache {
     where
         F: Font + Sync,
         U: FnMut(Rectangle<u32>, &[u8]),
+    {
+        self.cache_queued_paged(fonts, |_, rect, data| uploader(rect, data))
+    }
+
+    /// Caches the queued glyphs, see [`cache_queued`](#method.cache_queued).
+    ///
+    /// `uploader` is additionally provided the texture page to insert the pixel data into.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use glyph_brush_draw_cache::DrawCache;
+    /// # fn main() -> Result<(), glyph_brush_draw_cache::CacheWriteErr> {
+    /// # let fonts: Vec<ab_glyph::FontArc> = Vec::new();
+    /// # fn update_texture_layer(_: u32, _: glyph_brush_draw_cache::Rectangle<u32>, _: &[u8]) {}
+    /// let mut draw_cache = DrawCache::builder().dimensions(1024, 1024).pages(4).build();
+    /// draw_cache.cache_queued_paged(&fonts, |page, rect, tex_data| {
+    ///     update_texture_layer(page, rect, tex_data)
+    /// })?;
+    /// # Ok(()) }
+    /// ```
+    pub fn cache_queued_paged<F, U>(
+        &mut self,
+        fonts: &[F],
+        mut uploader: U,
+    ) -> Result<CachedBy, CacheWriteErr>
+    where
+        F: Font + Sync,
+        U: FnMut(u32, Rectangle<u32>, &[u8]),
     {
         let mut queue_success = true;
-        let from_empty = self.all_glyphs.is_empty();
+        let from_empty = self.all_glyphs.is_empty() && self.skyline_glyphs.is_empty();
 
         {
-            let (mut in_use_rows, uncached_glyphs) = {
+            let (mut in_use_rows, uncached_glyphs, hits) = {
                 let mut in_use_rows =
                     HashSet::with_capacity_and_hasher(self.rows.len(), FxBuildHasher::default());
                 let mut uncached_glyphs = HashMap::with_capacity_and_hasher(
@@ -616,19 +987,26 @@ impl DrawCache {
                     BuildHasherDefault::<FxHasher>::default(),
                 );
 
+                let mut hits = 0;
+
                 // divide glyphs into texture rows where a matching glyph texture
                 // already exists & glyphs where new textures must be cached
-                for (font_id, ref glyph) in &self.queue {
-                    let glyph_info = self.lossy_info_for(*font_id, glyph);
+                for (font_id, ref glyph, style) in &self.queue {
+                    let glyph_info = self.lossy_info_for(*font_id, glyph, *style);
                     if let Some((row, ..)) = self.all_glyphs.get(&glyph_info) {
                         in_use_rows.insert(*row);
+                        hits += 1;
+                    } else if self.skyline_glyphs.contains_key(&glyph_info) {
+                        hits += 1;
                     } else {
                         uncached_glyphs.insert(glyph_info, glyph);
                     }
                 }
 
-                (in_use_rows, uncached_glyphs)
+                (in_use_rows, uncached_glyphs, hits)
             };
+            self.stats.hits = hits;
+            self.stats.misses = uncached_glyphs.len();
 
             for row in &in_use_rows {
                 self.rows.get_refresh(row);
@@ -638,13 +1016,14 @@ impl DrawCache {
             let mut uncached_outlined: Vec<_> = uncached_glyphs
                 .into_iter()
                 .filter_map(|(info, glyph)| {
-                    Some((info, fonts[info.font_id].outline_glyph(glyph.clone())?))
+                    let outlined = self.outline_glyph(&fonts[info.font_id], glyph, info.style)?;
+                    Some((info, glyph.clone(), outlined))
                 })
                 .collect();
 
             // tallest first gives better packing
             // can use 'sort_unstable' as order of equal elements is unimportant
-            uncached_outlined.sort_unstable_by(|(_, ga), (_, gb)| {
+            uncached_outlined.sort_unstable_by(|(.., ga), (.., gb)| {
                 gb.px_bounds()
                     .height()
                     .partial_cmp(&ga.px_bounds().height())
@@ -654,14 +1033,27 @@ impl DrawCache {
             self.all_glyphs.reserve(uncached_outlined.len());
             let mut draw_and_upload = Vec::with_capacity(uncached_outlined.len());
 
-            'per_glyph: for (glyph_info, outlined) in uncached_outlined {
-                let bounds = outlined.px_bounds();
+            'per_glyph: for (glyph_info, glyph, outlined) in uncached_outlined {
+                let (bounds, texel_width) = match (self.sdf, self.texture_format) {
+                    (Some(sdf), _) => {
+                        let bounds = sdf.bordered(outlined.px_bounds());
+                        (bounds, bounds.width() as u32)
+                    }
+                    (None, TextureFormat::LcdRgb8) => {
+                        let bounds = lcd::bounds(outlined.px_bounds());
+                        (bounds, bounds.width() as u32 / 3)
+                    }
+                    (None, TextureFormat::A8) => {
+                        let bounds = outlined.px_bounds();
+                        (bounds, bounds.width() as u32)
+                    }
+                };
 
                 let (unaligned_width, unaligned_height) = {
                     if self.pad_glyphs {
-                        (bounds.width() as u32 + 2, bounds.height() as u32 + 2)
+                        (texel_width + 2, bounds.height() as u32 + 2)
                     } else {
-                        (bounds.width() as u32, bounds.height() as u32)
+                        (texel_width, bounds.height() as u32)
                     }
                 };
                 let (aligned_width, aligned_height) = if self.align_4x4 {
@@ -673,6 +1065,41 @@ impl DrawCache {
                 if aligned_width >= self.width || aligned_height >= self.height {
                     return Result::Err(CacheWriteErr::GlyphTooLarge);
                 }
+
+                if self.packer == Packer::Skyline {
+                    let (page, [x, y]) = match self.skyline.allocate(aligned_width, aligned_height)
+                    {
+                        Some(allocation) => allocation,
+                        // space is not reclaimed from a skyline, try a clean insert
+                        None if from_empty => return Err(CacheWriteErr::NoRoomForWholeQueue),
+                        None => {
+                            queue_success = false;
+                            break 'per_glyph;
+                        }
+                    };
+                    let unaligned_tex_coords = Rectangle {
+                        min: [x, y],
+                        max: [x + unaligned_width, y + unaligned_height],
+                    };
+                    self.skyline_glyphs.insert(
+                        glyph_info,
+                        GlyphTexInfo::new(
+                            glyph_info,
+                            glyph,
+                            page,
+                            unaligned_tex_coords,
+                            bounds,
+                            outlined.glyph(),
+                        ),
+                    );
+                    let aligned_tex_coords = Rectangle {
+                        min: [x, y],
+                        max: [x + aligned_width, y + aligned_height],
+                    };
+                    draw_and_upload.push((page, aligned_tex_coords, outlined));
+                    continue;
+                }
+
                 // find row to put the glyph in, most used rows first
                 let mut row_top = None;
                 for (top, row) in self.rows.iter().rev() {
@@ -684,14 +1111,13 @@ impl DrawCache {
                 }
 
                 if row_top.is_none() {
-                    let mut gap = None;
-                    // See if there is space for a new row
-                    for (start, end) in &self.space_end_for_start {
-                        if end - start >= aligned_height {
-                            gap = Some((*start, *end));
-                            break;
-                        }
-                    }
+                    // See if there is space for a new row, earlier pages first
+                    let mut gap = self
+                        .space_end_for_start
+                        .iter()
+                        .filter(|(start, end)| *end - *start >= aligned_height)
+                        .map(|(start, end)| (*start, *end))
+                        .min();
                     if gap.is_none() {
                         // Remove old rows until room is available
                         while !self.rows.is_empty() {
@@ -700,18 +1126,24 @@ impl DrawCache {
                                 // Remove row
                                 let (top, row) = self.rows.pop_front().unwrap();
 
+                                self.stats.evictions += row.glyphs.len() as u64;
                                 for g in row.glyphs {
                                     self.all_glyphs.remove(&g.glyph_info);
                                 }
 
                                 let (mut new_start, mut new_end) = (top, top + row.height);
                                 // Update the free space maps
-                                // Combine with neighbouring free space if possible
-                                if let Some(end) = self.space_end_for_start.remove(&new_end) {
-                                    new_end = end;
+                                // Combine with neighbouring free space on the same page
+                                if new_end % self.height != 0 {
+                                    if let Some(end) = self.space_end_for_start.remove(&new_end) {
+                                        new_end = end;
+                                    }
                                 }
-                                if let Some(start) = self.space_start_for_end.remove(&new_start) {
-                                    new_start = start;
+                                if new_start % self.height != 0 {
+                                    if let Some(start) = self.space_start_for_end.remove(&new_start)
+                                    {
+                                        new_start = start;
+                                    }
                                 }
                                 self.space_start_for_end.insert(new_end, new_start);
                                 self.space_end_for_start.insert(new_start, new_end);
@@ -756,38 +1188,31 @@ impl DrawCache {
                     row_top = Some(gap_start);
                 }
                 let row_top = row_top.unwrap();
+                let (page, page_top) = (row_top / self.height, row_top % self.height);
                 // calculate the target rect
                 let row = self.rows.get_refresh(&row_top).unwrap();
                 let aligned_tex_coords = Rectangle {
-                    min: [row.width, row_top],
-                    max: [row.width + aligned_width, row_top + aligned_height],
+                    min: [row.width, page_top],
+                    max: [row.width + aligned_width, page_top + aligned_height],
                 };
                 let unaligned_tex_coords = Rectangle {
-                    min: [row.width, row_top],
-                    max: [row.width + unaligned_width, row_top + unaligned_height],
+                    min: [row.width, page_top],
+                    max: [row.width + unaligned_width, page_top + unaligned_height],
                 };
 
-                let g = outlined.glyph();
-
                 // add the glyph to the row
-                row.glyphs.push(GlyphTexInfo {
+                row.glyphs.push(GlyphTexInfo::new(
                     glyph_info,
-                    tex_coords: unaligned_tex_coords,
-                    bounds_minus_position_over_scale: Rect {
-                        min: point(
-                            (bounds.min.x - g.position.x) / g.scale.x,
-                            (bounds.min.y - g.position.y) / g.scale.y,
-                        ),
-                        max: point(
-                            (bounds.max.x - g.position.x) / g.scale.x,
-                            (bounds.max.y - g.position.y) / g.scale.y,
-                        ),
-                    },
-                });
+                    glyph,
+                    page,
+                    unaligned_tex_coords,
+                    bounds,
+                    outlined.glyph(),
+                ));
                 row.width += aligned_width;
                 in_use_rows.insert(row_top);
 
-                draw_and_upload.push((aligned_tex_coords, outlined));
+                draw_and_upload.push((page, aligned_tex_coords, outlined));
 
                 self.all_glyphs
                     .insert(glyph_info, (row_top, row.glyphs.len() as u32 - 1));
@@ -796,21 +1221,26 @@ impl DrawCache {
             // draw & upload
             if queue_success {
                 if from_empty && draw_and_upload.len() > 1 {
-                    // if previously empty draw into memory and perform a single upload
-                    let max_v = draw_and_upload
+                    // if previously empty draw into memory and perform a single upload per page
+                    let mut max_v = vec![0; self.pages as usize];
+                    for (page, rect, _) in &draw_and_upload {
+                        let max_v = &mut max_v[*page as usize];
+                        *max_v = rect.max[1].max(*max_v);
+                    }
+                    let bpp = self.texture_format.bytes_per_pixel();
+                    let mut texture_up: Vec<_> = max_v
                         .iter()
-                        .map(|rect| rect.0.max[1])
-                        .max()
-                        .unwrap();
-                    let mut texture_up = vec![0; (self.width * max_v) as _];
+                        .map(|max_v| vec![0; (self.width * max_v) as usize * bpp])
+                        .collect();
 
-                    self.draw_and_upload(draw_and_upload, &mut |rect, data| {
-                        let min_h = rect.min[0] as usize;
+                    self.draw_and_upload(draw_and_upload, &mut |page, rect, data| {
+                        let texture_up = &mut texture_up[page as usize];
+                        let min_h = rect.min[0] as usize * bpp;
                         let min_v = rect.min[1];
-                        let glyph_w = rect.width() as usize;
+                        let glyph_w = rect.width() as usize * bpp;
 
                         for v in min_v..rect.max[1] {
-                            let tex_left = min_h + (self.width * v) as usize;
+                            let tex_left = min_h + (self.width * v) as usize * bpp;
                             let data_left = glyph_w * (v - min_v) as usize;
                             texture_up.splice(
                                 tex_left..tex_left + glyph_w,
@@ -819,27 +1249,42 @@ impl DrawCache {
                         }
                     });
 
-                    uploader(
-                        Rectangle {
-                            min: [0, 0],
-                            max: [self.width, max_v],
-                        },
-                        &texture_up,
-                    );
+                    for (page, (max_v, texture_up)) in max_v.into_iter().zip(texture_up).enumerate()
+                    {
+                        if max_v > 0 {
+                            self.stats.bytes_uploaded += texture_up.len() as u64;
+                            uploader(
+                                page as u32,
+                                Rectangle {
+                                    min: [0, 0],
+                                    max: [self.width, max_v],
+                                },
+                                &texture_up,
+                            );
+                        }
+                    }
                 } else {
-                    self.draw_and_upload(draw_and_upload, &mut uploader);
+                    let mut bytes_uploaded = 0;
+                    self.draw_and_upload(draw_and_upload, &mut |page, rect, data| {
+                        bytes_uploaded += data.len() as u64;
+                        uploader(page, rect, data);
+                    });
+                    self.stats.bytes_uploaded += bytes_uploaded;
                 }
             }
         }
 
         if queue_success {
             self.queue.clear();
+            self.stats.last_cached_by = Some(CachedBy::Adding);
             Ok(CachedBy::Adding)
         } else {
             // clear the cache then try again with optimal packing
             self.clear();
-            self.cache_queued(fonts, uploader)
-                .map(|_| CachedBy::Reordering)
+            self.cache_queued_paged(fonts, uploader)?;
+            self.stats.reorders += 1;
+            self.stats.last_cached_by = Some(CachedBy::Reordering);
+            Ok(CachedBy::Reordering)
         }
     }
 
@@ -850,10 +1295,10 @@ impl DrawCache {
     #[cfg(not(target_arch = "wasm32"))]
     fn draw_and_upload<U>(
         &self,
-        draw_and_upload: Vec<(Rectangle<u32>, OutlinedGlyph)>,
+        draw_and_upload: Vec<(u32, Rectangle<u32>, OutlinedGlyph)>,
         uploader: &mut U,
     ) where
-        U: FnMut(Rectangle<u32>, &[u8]),
+        U: FnMut(u32, Rectangle<u32>, &[u8]),
     {
         use std::sync::Arc;
 
@@ -866,7 +1311,7 @@ impl DrawCache {
         let work_magnitude = {
             let tallest_h = draw_and_upload
                 .get(0)
-                .map(|(r, _)| r.height() as usize)
+                .map(|(_, r, _)| r.height() as usize)
                 .unwrap_or(0);
             glyph_count
                 .saturating_mul(tallest_h)
@@ -883,6 +1328,9 @@ impl DrawCache {
             let rasterize_queue = Arc::new(crossbeam_deque::Injector::new());
             let (to_main, from_stealers) = crossbeam_channel::unbounded();
             let pad_glyphs = self.pad_glyphs;
+            let sdf = self.sdf;
+            let texture_format = self.texture_format;
+            let coverage_lut = self.coverage_lut;
 
             let mut worker_qs: Vec<_> = (0..threads).map(|_| Worker::new_fifo()).collect();
             let stealers: Arc<Vec<_>> = Arc::new(worker_qs.iter().map(|w| w.stealer()).collect());
@@ -909,9 +1357,16 @@ impl DrawCache {
                     });
 
                     match task {
-                        Some((tex_coords, glyph)) => {
-                            let pixels = draw_glyph(tex_coords, &glyph, pad_glyphs);
-                            to_main.send((tex_coords, pixels)).unwrap();
+                        Some((page, tex_coords, glyph)) => {
+                            let pixels = draw_glyph(
+                                tex_coords,
+                                &glyph,
+                                pad_glyphs,
+                                sdf,
+                                texture_format,
+                                coverage_lut.as_ref(),
+                            );
+                            to_main.send((page, tex_coords, pixels)).unwrap();
                         }
                         None => break,
                     }
@@ -933,9 +1388,16 @@ impl DrawCache {
                 });
 
                 match task {
-                    Some((tex_coords, glyph)) => {
-                        let pixels = draw_glyph(tex_coords, &glyph, pad_glyphs);
-                        uploader(tex_coords, pixels.as_slice());
+                    Some((page, tex_coords, glyph)) => {
+                        let pixels = draw_glyph(
+                            tex_coords,
+                            &glyph,
+                            pad_glyphs,
+                            sdf,
+                            texture_format,
+                            coverage_lut.as_ref(),
+                        );
+                        uploader(page, tex_coords, pixels.as_slice());
                     }
                     None if workers_finished => break,
                     None => {}
@@ -943,7 +1405,9 @@ impl DrawCache {
 
                 while !workers_finished {
                     match from_stealers.try_recv() {
-                        Ok((tex_coords, pixels)) => uploader(tex_coords, pixels.as_slice()),
+                        Ok((page, tex_coords, pixels)) => {
+                            uploader(page, tex_coords, pixels.as_slice())
+                        }
                         Err(TryRecvError::Disconnected) => workers_finished = true,
                         Err(TryRecvError::Empty) => break,
                     }
@@ -958,10 +1422,10 @@ impl DrawCache {
     #[inline]
     fn draw_and_upload<U>(
         &self,
-        draw_and_upload: Vec<(Rectangle<u32>, OutlinedGlyph)>,
+        draw_and_upload: Vec<(u32, Rectangle<u32>, OutlinedGlyph)>,
         uploader: &mut U,
     ) where
-        U: FnMut(Rectangle<u32>, &[u8]),
+        U: FnMut(u32, Rectangle<u32>, &[u8]),
     {
         self.draw_and_upload_1_thread(draw_and_upload, uploader)
     }
@@ -970,14 +1434,21 @@ impl DrawCache {
     #[inline]
     fn draw_and_upload_1_thread<U>(
         &self,
-        draw_and_upload: Vec<(Rectangle<u32>, OutlinedGlyph)>,
+        draw_and_upload: Vec<(u32, Rectangle<u32>, OutlinedGlyph)>,
         uploader: &mut U,
     ) where
-        U: FnMut(Rectangle<u32>, &[u8]),
+        U: FnMut(u32, Rectangle<u32>, &[u8]),
     {
-        for (tex_coords, outlined) in draw_and_upload {
-            let pixels = draw_glyph(tex_coords, &outlined, self.pad_glyphs);
-            uploader(tex_coords, pixels.as_slice());
+        for (page, tex_coords, outlined) in draw_and_upload {
+            let pixels = draw_glyph(
+                tex_coords,
+                &outlined,
+                self.pad_glyphs,
+                self.sdf,
+                self.texture_format,
+                self.coverage_lut.as_ref(),
+            );
+            uploader(page, tex_coords, pixels.as_slice());
         }
     }
 
@@ -988,7 +1459,8 @@ impl DrawCache {
     /// these pixel-space coordinates should be identical to the bounding box of
     /// the input glyph. They only differ if the cache has returned a substitute
     /// glyph that is deemed close enough to the requested glyph as specified by
-    /// the cache tolerance parameters.
+    /// the cache tolerance parameters. The texture page holding the glyph is
+    /// also returned, always `0` for single page caches.
     ///
     /// A sucessful result is `Some` if the glyph is not an empty glyph (no
     /// shape, and thus no rect to return).
@@ -996,15 +1468,34 @@ impl DrawCache {
     /// Ensure that `font_id` matches the `font_id` that was passed to
     /// `queue_glyph` with this `glyph`.
     pub fn rect_for(&self, font_id: usize, glyph: &Glyph) -> Option<TextureCoords> {
-        let (row, index) = self.all_glyphs.get(&self.lossy_info_for(font_id, glyph))?;
+        self.styled_rect_for(font_id, glyph, SyntheticStyle::default())
+    }
+
+    /// Retrieves the (floating point) texture coordinates of the quad for a glyph
+    /// queued with [`queue_styled_glyph`](#method.queue_styled_glyph).
+    ///
+    /// Ensure that `font_id` & `style` match those passed to `queue_styled_glyph`
+    /// with this `glyph`.
+    pub fn styled_rect_for(
+        &self,
+        font_id: usize,
+        glyph: &Glyph,
+        style: SyntheticStyle,
+    ) -> Option<TextureCoords> {
+        let glyph_info = self.lossy_info_for(font_id, glyph, style);
+        let tex_info = match self.all_glyphs.get(&glyph_info) {
+            Some((row, index)) => &self.rows[row].glyphs[*index as usize],
+            None => self.skyline_glyphs.get(&glyph_info)?,
+        };
 
         let (tex_width, tex_height) = (self.width as f32, self.height as f32);
 
         let GlyphTexInfo {
+            page,
             tex_coords: mut tex_rect,
             bounds_minus_position_over_scale,
             ..
-        } = self.rows[&row].glyphs[*index as usize];
+        } = *tex_info;
         if self.pad_glyphs {
             tex_rect = tex_rect.unpadded();
         }
@@ -1030,12 +1521,37 @@ impl DrawCache {
             ) + glyph.position,
         };
 
-        Some((uv_rect, equivalent_bounds))
+        Some((uv_rect, equivalent_bounds, page))
     }
 }
 
 #[inline]
-fn draw_glyph(tex_coords: Rectangle<u32>, glyph: &OutlinedGlyph, pad_glyphs: bool) -> ByteArray2d {
+fn draw_glyph(
+    tex_coords: Rectangle<u32>,
+    glyph: &OutlinedGlyph,
+    pad_glyphs: bool,
+    sdf: Option<Sdf>,
+    texture_format: TextureFormat,
+    coverage_lut: Option<&[u8; 256]>,
+) -> ByteArray2d {
+    if let Some(sdf) = sdf {
+        return sdf.draw(
+            glyph,
+            tex_coords.width() as usize,
+            tex_coords.height() as usize,
+            pad_glyphs,
+        );
+    }
+    if texture_format == TextureFormat::LcdRgb8 {
+        return lcd::draw(
+            glyph,
+            tex_coords.width() as usize,
+            tex_coords.height() as usize,
+            pad_glyphs,
+            coverage_lut,
+        );
+    }
+
     let mut pixels = ByteArray2d::zeros(tex_coords.height() as usize, tex_coords.width() as usize);
     if pad_glyphs {
         glyph.draw(|x, y, v| {
@@ -1049,6 +1565,11 @@ fn draw_glyph(tex_coords: Rectangle<u32>, glyph: &OutlinedGlyph, pad_glyphs: boo
             pixels[(y as usize, x as usize)] = v;
         });
     }
+    if let Some(lut) = coverage_lut {
+        for v in &mut pixels.inner_array {
+            *v = lut[*v as usize];
+        }
+    }
     pixels
 }
 
@@ -1145,37 +1666,425 @@ mod test {
         let miss_2 = gid.with_scale_and_position(12.0, point(0.0, 0.0));
         let miss_3 = gid.with_scale_and_position(9.91, point(0.3, 0.0));
 
-        let small_info = cache.lossy_info_for(0, &small);
+        let small_info = cache.lossy_info_for(0, &small, <_>::default());
+
+        assert_eq!(
+            small_info,
+            cache.lossy_info_for(0, &match_1, <_>::default())
+        );
+        assert_eq!(
+            small_info,
+            cache.lossy_info_for(0, &match_2, <_>::default())
+        );
+        assert_eq!(
+            small_info,
+            cache.lossy_info_for(0, &match_3, <_>::default())
+        );
 
-        assert_eq!(small_info, cache.lossy_info_for(0, &match_1));
-        assert_eq!(small_info, cache.lossy_info_for(0, &match_2));
-        assert_eq!(small_info, cache.lossy_info_for(0, &match_3));
+        assert_ne!(small_info, cache.lossy_info_for(0, &miss_1, <_>::default()));
+        assert_ne!(small_info, cache.lossy_info_for(0, &miss_2, <_>::default()));
+        assert_ne!(small_info, cache.lossy_info_for(0, &miss_3, <_>::default()));
+    }
+
+    #[test]
+    fn synthetic_styles_cached_separately() {
+        let font = FontRef::try_from_slice(FONT).unwrap();
+        let glyph = font
+            .glyph_id('l')
+            .with_scale_and_position(24.0, point(10.0, 30.0));
+
+        let bold = SyntheticStyle {
+            bold: true,
+            ..<_>::default()
+        };
+        let oblique = SyntheticStyle {
+            oblique: true,
+            ..<_>::default()
+        };
+
+        let mut cache = DrawCache::builder().dimensions(64, 64).build();
+        cache.queue_glyph(0, glyph.clone());
+        cache.queue_styled_glyph(0, glyph.clone(), bold);
+        cache.cache_queued(&[&font], |_, _| {}).unwrap();
+
+        let (regular_uv, regular, _) = cache.rect_for(0, &glyph).unwrap();
+        let (bold_uv, bold_rect, _) = cache.styled_rect_for(0, &glyph, bold).unwrap();
+        assert_ne!(regular_uv, bold_uv);
+        // widened by 1px (24 * BOLD_STRENGTH)
+        assert_relative_eq!(bold_rect.width(), regular.width() + 1.0, epsilon = 1.0);
+        assert!(bold_rect.width() > regular.width());
+
+        assert!(cache.styled_rect_for(0, &glyph, oblique).is_none());
+        cache.queue_styled_glyph(0, glyph.clone(), oblique);
+        cache.cache_queued(&[&font], |_, _| {}).unwrap();
+
+        let (_, oblique_rect, _) = cache.styled_rect_for(0, &glyph, oblique).unwrap();
+        // skewed right above the baseline
+        assert!(oblique_rect.width() > regular.width());
+        assert!(oblique_rect.max.x > regular.max.x);
+        assert_relative_eq!(oblique_rect.height(), regular.height());
+    }
+
+    #[test]
+    fn sdf_cached_once_for_all_scales() {
+        let font = FontRef::try_from_slice(FONT).unwrap();
+        let small = font
+            .glyph_id('l')
+            .with_scale_and_position(12.0, point(10.3, 30.0));
+        let big = font
+            .glyph_id('l')
+            .with_scale_and_position(48.0, point(50.0, 80.7));
+
+        let sdf = Sdf {
+            reference_scale: 32.0,
+            spread: 4.0,
+        };
+        let mut cache = DrawCache::builder().dimensions(64, 64).sdf(sdf).build();
+        assert_eq!(cache.sdf(), Some(sdf));
+
+        cache.queue_glyph(0, small.clone());
+        cache.queue_glyph(0, big.clone());
+        let mut uploads = vec![];
+        cache
+            .cache_queued(&[&font], |rect, data| uploads.push((rect, data.to_vec())))
+            .unwrap();
+        assert_eq!(uploads.len(), 1);
+
+        let (small_uv, small_rect, _) = cache.rect_for(0, &small).unwrap();
+        let (big_uv, big_rect, _) = cache.rect_for(0, &big).unwrap();
+        assert_eq!(small_uv, big_uv);
+        assert_relative_eq!(big_rect.width(), small_rect.width() * 4.0, epsilon = 0.001);
+        assert_relative_eq!(
+            big_rect.height(),
+            small_rect.height() * 4.0,
+            epsilon = 0.001
+        );
+
+        // quad includes the spread border scaled to 48px
+        let outline_bounds = font.outline_glyph(big).unwrap().px_bounds();
+        assert_relative_eq!(big_rect.min.x, outline_bounds.min.x - 6.0, epsilon = 1.5);
+        assert_relative_eq!(big_rect.max.y, outline_bounds.max.y + 6.0, epsilon = 1.5);
+
+        // border is outside, the stroke inside
+        let (rect, data) = &uploads[0];
+        let width = rect.width() as usize;
+        assert_eq!(data[width + 1], 0);
+        assert!(data.iter().any(|&v| v > 128));
+        assert!(data.iter().any(|&v| v > 0 && v < 128));
+    }
+
+    #[test]
+    fn coverage_adjustment() {
+        let font = FontRef::try_from_slice(FONT).unwrap();
+        let total_coverage = |adjustment, scale: f32| {
+            let glyph = font
+                .glyph_id('l')
+                .with_scale_and_position(scale, point(10.0, 50.0));
+            let mut cache = DrawCache::builder()
+                .dimensions(64, 64)
+                .coverage_adjustment(adjustment)
+                .build();
+            cache.queue_glyph(0, glyph);
+            let mut total = 0;
+            cache
+                .cache_queued(&[&font], |_, data| {
+                    total += data.iter().map(|&v| u32::from(v)).sum::<u32>()
+                })
+                .unwrap();
+            total
+        };
+
+        let light_on_dark = CoverageAdjustment::light_on_dark();
+        let without_darkening = CoverageAdjustment {
+            stem_darkening: 0.0,
+            ..light_on_dark
+        };
+        let none = CoverageAdjustment::NONE;
+        assert!(total_coverage(light_on_dark, 12.0) > total_coverage(without_darkening, 12.0));
+        assert!(total_coverage(without_darkening, 12.0) > total_coverage(none, 12.0));
+        // no stem darkening at large scales
+        assert_eq!(
+            total_coverage(light_on_dark, 60.0),
+            total_coverage(without_darkening, 60.0)
+        );
 
-        assert_ne!(small_info, cache.lossy_info_for(0, &miss_1));
-        assert_ne!(small_info, cache.lossy_info_for(0, &miss_2));
-        assert_ne!(small_info, cache.lossy_info_for(0, &miss_3));
+        let dark_on_light = CoverageAdjustment::dark_on_light();
+        assert!(total_coverage(dark_on_light, 12.0) < total_coverage(none, 12.0));
+    }
+
+    #[test]
+    fn lcd_rgb_texture_format() {
+        let font = FontRef::try_from_slice(FONT).unwrap();
+        let glyph = font
+            .glyph_id('l')
+            .with_scale_and_position(20.0, point(10.3, 30.0));
+
+        let cache_glyph = |texture_format| {
+            let mut cache = DrawCache::builder()
+                .dimensions(64, 64)
+                .texture_format(texture_format)
+                .build();
+            cache.queue_glyph(0, glyph.clone());
+            let mut uploads = vec![];
+            cache
+                .cache_queued(&[&font], |rect, data| uploads.push((rect, data.to_vec())))
+                .unwrap();
+            assert_eq!(uploads.len(), 1);
+            let (rect, data) = uploads.pop().unwrap();
+            assert_eq!(
+                data.len(),
+                (rect.width() * rect.height()) as usize * texture_format.bytes_per_pixel()
+            );
+            (cache.rect_for(0, &glyph).unwrap().1, data)
+        };
+
+        let (a8_rect, a8_data) = cache_glyph(TextureFormat::A8);
+        let (lcd_rect, lcd_data) = cache_glyph(TextureFormat::LcdRgb8);
+
+        // whole pixel quad around the glyph
+        assert_eq!(lcd_rect.min.x, lcd_rect.min.x.round());
+        assert_eq!(lcd_rect.max.x, lcd_rect.max.x.round());
+        assert!(lcd_rect.min.x < a8_rect.min.x && lcd_rect.max.x > a8_rect.max.x);
+        assert_relative_eq!(lcd_rect.min.y, a8_rect.min.y);
+        assert_relative_eq!(lcd_rect.max.y, a8_rect.max.y);
+
+        // each channel has similar total coverage to grayscale
+        let a8_total: u32 = a8_data.iter().map(|&v| u32::from(v)).sum();
+        for channel in 0..3 {
+            let total: u32 = lcd_data.chunks(3).map(|rgb| u32::from(rgb[channel])).sum();
+            let ratio = total as f32 / a8_total as f32;
+            assert!((0.9..1.1).contains(&ratio), "ratio {}", ratio);
+        }
+        // with colour fringes at the glyph edges
+        assert!(lcd_data.chunks(3).any(|rgb| rgb[0] != rgb[2]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn lcd_rgb_sdf_panics() {
+        DrawCache::builder()
+            .sdf(Sdf::default())
+            .texture_format(TextureFormat::LcdRgb8)
+            .build();
+    }
+
+    #[test]
+    fn stats() {
+        let font = FontRef::try_from_slice(FONT).unwrap();
+        let lay_out = |text| -> Vec<_> {
+            Layout::default_single_line()
+                .calculate_glyphs(
+                    &[&font],
+                    &SectionGeometry::default(),
+                    &[SectionText {
+                        text,
+                        scale: 16.0.into(),
+                        ..<_>::default()
+                    }],
+                )
+                .into_iter()
+                .map(|sg| sg.glyph)
+                .collect()
+        };
+        // room for a single row
+        let mut cache = DrawCache::builder()
+            .dimensions(32, 20)
+            .multithread(false)
+            .build();
+        assert_eq!(cache.stats(), DrawCacheStats::default());
+
+        let mut uploaded = 0;
+        // duplicate glyphs are rasterized once
+        let glyphs = lay_out("AB");
+        for glyph in glyphs.iter().chain(&glyphs[..1]) {
+            cache.queue_glyph(0, glyph.clone());
+        }
+        cache
+            .cache_queued(&[&font], |_, data| uploaded += data.len() as u64)
+            .unwrap();
+        let stats = cache.stats();
+        assert_eq!(stats.glyphs, 2);
+        assert_eq!(stats.rows, 1);
+        assert!(stats.occupied_area_ratio > 0.0 && stats.occupied_area_ratio < 1.0);
+        assert_eq!((stats.hits, stats.misses), (0, 2));
+        assert_eq!(stats.bytes_uploaded, uploaded);
+        assert_eq!(stats.last_cached_by, Some(CachedBy::Adding));
+
+        for glyph in lay_out("CD") {
+            cache.queue_glyph(0, glyph);
+        }
+        cache
+            .cache_queued(&[&font], |_, data| uploaded += data.len() as u64)
+            .unwrap();
+        let stats = cache.stats();
+        assert_eq!(stats.glyphs, 2);
+        assert_eq!(stats.evictions, 2);
+        assert_eq!(stats.bytes_uploaded, uploaded);
+
+        let glyphs = lay_out("CD");
+        for glyph in glyphs.iter().chain(&glyphs) {
+            cache.queue_glyph(0, glyph.clone());
+        }
+        cache.cache_queued(&[&font], |_, _| {}).unwrap();
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses), (4, 0));
+        assert_eq!(stats.bytes_uploaded, uploaded);
+        assert_eq!(stats.reorders, 0);
+    }
+
+    #[test]
+    fn multiple_pages() {
+        let font = FontRef::try_from_slice(FONT).unwrap();
+        let glyphs = Layout::default_single_line().calculate_glyphs(
+            &[&font],
+            &SectionGeometry::default(),
+            &[SectionText {
+                text: "ABCDEFGHIJKL",
+                scale: 16.0.into(),
+                ..<_>::default()
+            }],
+        );
+        let cache_with_pages = |pages| {
+            let mut cache = DrawCache::builder()
+                .dimensions(32, 32)
+                .pages(pages)
+                .multithread(false)
+                .build();
+            for sg in &glyphs {
+                cache.queue_glyph(0, sg.glyph.clone());
+            }
+            let mut uploads = vec![];
+            let result = cache.cache_queued_paged(&[&font], |page, rect, _| {
+                uploads.push((page, rect));
+            });
+            (cache, result, uploads)
+        };
+
+        let (_, result, _) = cache_with_pages(1);
+        assert_eq!(result, Err(CacheWriteErr::NoRoomForWholeQueue));
+
+        let (cache, result, uploads) = cache_with_pages(3);
+        assert_eq!(result, Ok(CachedBy::Adding));
+        assert!(uploads.iter().any(|(page, _)| *page > 0), "{:?}", uploads);
+        for (page, rect) in &uploads {
+            assert!(*page < 3);
+            assert!(rect.max[0] <= 32 && rect.max[1] <= 32, "{:?}", rect);
+        }
+
+        let pages: HashSet<_> = glyphs
+            .iter()
+            .map(|sg| {
+                let (uv, _, page) = cache.rect_for(0, &sg.glyph).unwrap();
+                assert!(uv.max.x <= 1.0 && uv.max.y <= 1.0, "{:?}", uv);
+                page
+            })
+            .collect();
+        assert!(pages.contains(&0) && pages.contains(&1), "{:?}", pages);
+    }
+
+    #[test]
+    fn skyline_packs_mixed_sizes() {
+        let font = FontRef::try_from_slice(FONT).unwrap();
+        let lay_out = |text, scale: f32| -> Vec<_> {
+            Layout::default_single_line()
+                .calculate_glyphs(
+                    &[&font],
+                    &SectionGeometry::default(),
+                    &[SectionText {
+                        text,
+                        scale: scale.into(),
+                        ..<_>::default()
+                    }],
+                )
+                .into_iter()
+                .map(|sg| sg.glyph)
+                .collect()
+        };
+        // small glyphs only fit beside the large ones if stacked
+        let mut glyphs = lay_out("ABCD", 40.0);
+        glyphs.extend(lay_out("efghkmnopqrsuvwxyz", 12.0));
+
+        let cache_with = |packer| {
+            let mut cache = DrawCache::builder()
+                .dimensions(76, 64)
+                .packer(packer)
+                .build();
+            for glyph in &glyphs {
+                cache.queue_glyph(0, glyph.clone());
+            }
+            let result = cache.cache_queued(&[&font], |_, _| {});
+            (cache, result)
+        };
+
+        let (_, result) = cache_with(Packer::Rows);
+        assert_eq!(result, Err(CacheWriteErr::NoRoomForWholeQueue));
+
+        let (mut cache, result) = cache_with(Packer::Skyline);
+        assert_eq!(result, Ok(CachedBy::Adding));
+        let uvs: Vec<_> = glyphs
+            .iter()
+            .map(|glyph| cache.rect_for(0, glyph).unwrap().0)
+            .collect();
+        for (idx, a) in uvs.iter().enumerate() {
+            for b in &uvs[idx + 1..] {
+                assert!(
+                    a.max.x <= b.min.x
+                        || b.max.x <= a.min.x
+                        || a.max.y <= b.min.y
+                        || b.max.y <= a.min.y,
+                    "overlapping {:?} {:?}",
+                    a,
+                    b
+                );
+            }
+        }
+
+        // when full the skyline is repacked with the latest queue
+        for glyph in lay_out("EF", 40.0) {
+            cache.queue_glyph(0, glyph);
+        }
+        assert_eq!(
+            cache.cache_queued(&[&font], |_, _| {}),
+            Ok(CachedBy::Reordering)
+        );
+        assert!(cache.rect_for(0, &glyphs[0]).is_none());
+        assert_eq!(cache.stats().reorders, 1);
+        assert_eq!(cache.stats().evictions, 0);
     }
 
     #[test]
     fn cache_to_builder() {
         let cache = DrawCacheBuilder {
             dimensions: (32, 64),
+            pages: 3,
+            packer: Packer::Skyline,
             scale_tolerance: 0.2,
             position_tolerance: 0.3,
             pad_glyphs: false,
             align_4x4: false,
             multithread: false,
+            sdf: Some(Sdf::default()),
+            coverage_adjustment: CoverageAdjustment::dark_on_light(),
+            texture_format: TextureFormat::A8,
         }
         .build();
 
         let to_builder: DrawCacheBuilder = cache.to_builder();
 
         assert_eq!(to_builder.dimensions, (32, 64));
+        assert_eq!(to_builder.pages, 3);
+        assert_eq!(to_builder.packer, Packer::Skyline);
         assert_relative_eq!(to_builder.scale_tolerance, 0.2);
         assert_relative_eq!(to_builder.position_tolerance, 0.3);
         assert!(!to_builder.pad_glyphs);
         assert!(!to_builder.align_4x4);
         assert!(!to_builder.multithread);
+        assert_eq!(to_builder.sdf, Some(Sdf::default()));
+        assert_eq!(
+            to_builder.coverage_adjustment,
+            CoverageAdjustment::dark_on_light()
+        );
+        assert_eq!(to_builder.texture_format, TextureFormat::A8);
     }
 
     #[test]
@@ -1286,7 +2195,7 @@ mod test {
                 assert_eq!(rect.height(), expected_height);
             })
             .unwrap();
-        let (uv, _screen_rect) = cache.rect_for(0, &glyph).unwrap();
+        let (uv, _screen_rect, _) = cache.rect_for(0, &glyph).unwrap();
 
         assert_relative_eq!(uv.min.x, 0.015_625);
         assert_relative_eq!(uv.min.y, 0.015_625);
@@ -0,0 +1,166 @@
+/// Texture packing strategy used to allocate glyph texture space.
+///
+/// # Example
+/// ```
+/// use glyph_brush_draw_cache::{DrawCache, Packer};
+///
+/// let cache = DrawCache::builder().packer(Packer::Skyline).build();
+/// assert_eq!(cache.packer(), Packer::Skyline);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Packer {
+    /// Glyphs are packed into rows as tall as the first glyph in the row.
+    ///
+    /// The least recently used rows are removed to make room for new glyphs, so
+    /// glyphs are rarely reordered in a cache much larger than each queue. However,
+    /// shorter glyphs in tall rows leave unused space, e.g. when mixing text sizes.
+    Rows,
+    /// Glyphs are packed bottom-left onto a "skyline" of the tops of previously packed
+    /// glyphs, so short glyphs stack within the height of taller neighbours.
+    ///
+    /// Packs mixed sizes more densely than `Rows`. However, space is not reclaimed from
+    /// individual glyphs, so when full the cache is reordered to fit the latest queue.
+    Skyline,
+}
+
+impl Default for Packer {
+    /// `Packer::Rows`
+    #[inline]
+    fn default() -> Self {
+        Self::Rows
+    }
+}
+
+/// Horizontal span of the skyline at a height.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Segment {
+    x: u32,
+    y: u32,
+    width: u32,
+}
+
+/// Bottom-left skyline packer over multiple equal size pages.
+#[derive(Debug, Clone)]
+pub(crate) struct Skyline {
+    width: u32,
+    height: u32,
+    /// Skyline of each page sorted by x, spanning the page width.
+    pages: Vec<Vec<Segment>>,
+}
+
+impl Skyline {
+    pub(crate) fn new(width: u32, height: u32, pages: u32) -> Self {
+        let empty = vec![Segment { x: 0, y: 0, width }];
+        Self {
+            width,
+            height,
+            pages: vec![empty; pages as usize],
+        }
+    }
+
+    /// Allocates a `width` x `height` area returning the page & top-left position,
+    /// `None` if no page has room. Earlier pages are filled first.
+    pub(crate) fn allocate(&mut self, width: u32, height: u32) -> Option<(u32, [u32; 2])> {
+        let (page_width, page_height) = (self.width, self.height);
+        for (page, segments) in self.pages.iter_mut().enumerate() {
+            // lowest top, then leftmost
+            let best = (0..segments.len())
+                .filter_map(|idx| {
+                    let y = rest_height(segments, idx, width, page_width)?;
+                    Some((y, idx))
+                })
+                .filter(|(y, _)| y + height <= page_height)
+                .min();
+
+            if let Some((y, idx)) = best {
+                let x = segments[idx].x;
+                add(segments, idx, y, width, height);
+                return Some((page as u32, [x, y]));
+            }
+        }
+        None
+    }
+}
+
+/// Returns the top an area of `width` starting at segment `idx` would rest at,
+/// `None` if the area would exceed the page width.
+#[inline]
+fn rest_height(segments: &[Segment], idx: usize, width: u32, page_width: u32) -> Option<u32> {
+    let x = segments[idx].x;
+    if x + width > page_width {
+        return None;
+    }
+    let right = x + width;
+    Some(
+        segments[idx..]
+            .iter()
+            .take_while(|s| s.x < right)
+            .map(|s| s.y)
+            .max()
+            .unwrap_or(0),
+    )
+}
+
+/// Raises the skyline over the area at segment `idx`.
+fn add(segments: &mut Vec<Segment>, idx: usize, y: u32, width: u32, height: u32) {
+    let x = segments[idx].x;
+    let right = x + width;
+    segments.insert(
+        idx,
+        Segment {
+            x,
+            y: y + height,
+            width,
+        },
+    );
+
+    // shrink or remove segments now below the area
+    while idx + 1 < segments.len() && segments[idx + 1].x < right {
+        let next = &mut segments[idx + 1];
+        let next_right = next.x + next.width;
+        if next_right <= right {
+            segments.remove(idx + 1);
+        } else {
+            next.width = next_right - right;
+            next.x = right;
+            break;
+        }
+    }
+
+    // merge neighbours of equal height
+    let mut idx = 0;
+    while idx + 1 < segments.len() {
+        if segments[idx].y == segments[idx + 1].y {
+            segments[idx].width += segments[idx + 1].width;
+            segments.remove(idx + 1);
+        } else {
+            idx += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn skyline_stacks_short_areas() {
+        let mut skyline = Skyline::new(10, 10, 2);
+        assert_eq!(skyline.allocate(4, 8), Some((0, [0, 0])));
+        assert_eq!(skyline.allocate(6, 3), Some((0, [4, 0])));
+        // stacks on the shorter area, beside the taller one
+        assert_eq!(skyline.allocate(6, 3), Some((0, [4, 3])));
+        assert_eq!(skyline.allocate(10, 2), Some((0, [0, 8])));
+        // next page when full
+        assert_eq!(skyline.allocate(5, 5), Some((1, [0, 0])));
+        assert_eq!(skyline.allocate(11, 1), None);
+        assert_eq!(
+            skyline.pages[0],
+            vec![Segment {
+                x: 0,
+                y: 10,
+                width: 10
+            }]
+        );
+    }
+}
@@ -0,0 +1,212 @@
+use crate::ByteArray2d;
+use ::ab_glyph::*;
+
+/// Signed distance field rasterization settings.
+///
+/// Glyphs are rasterized once at the `reference_scale` as distance fields & scaled to
+/// any requested size, so the scale & position tolerances do not apply.
+///
+/// Each texel stores the distance to the glyph outline in reference scale pixels,
+/// mapped so `0.5` (`128`) is the outline, higher values are inside & lower values
+/// outside. Values reach `0` & `1` at `spread` pixels from the outline.
+///
+/// Rendering requires a shader that thresholds texture samples at `0.5`, e.g.
+/// `smoothstep(0.5 - w, 0.5 + w, sample)` with `w` derived from `fwidth(sample)`.
+///
+/// # Example
+/// ```
+/// use glyph_brush_draw_cache::{DrawCache, Sdf};
+///
+/// let cache = DrawCache::builder()
+///     .sdf(Sdf {
+///         reference_scale: 48.0,
+///         spread: 6.0,
+///     })
+///     .build();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sdf {
+    /// Pixel scale glyphs are rasterized at.
+    pub reference_scale: f32,
+    /// Max distance from the outline encoded, in reference scale pixels.
+    /// This is also the size of the border added around each glyph.
+    pub spread: f32,
+}
+
+impl Default for Sdf {
+    /// `Sdf { reference_scale: 64.0, spread: 8.0 }`
+    #[inline]
+    fn default() -> Self {
+        Self {
+            reference_scale: 64.0,
+            spread: 8.0,
+        }
+    }
+}
+
+impl Sdf {
+    /// Returns the glyph at the reference scale & origin.
+    #[inline]
+    pub(crate) fn reference_glyph(&self, glyph: &Glyph) -> Glyph {
+        glyph
+            .id
+            .with_scale_and_position(self.reference_scale, point(0.0, 0.0))
+    }
+
+    /// Border pixels around each glyph.
+    #[inline]
+    fn border(&self) -> f32 {
+        self.spread.ceil()
+    }
+
+    /// Returns glyph pixel bounds including the border.
+    #[inline]
+    pub(crate) fn bordered(&self, mut bounds: Rect) -> Rect {
+        let border = self.border();
+        bounds.min.x -= border;
+        bounds.min.y -= border;
+        bounds.max.x += border;
+        bounds.max.y += border;
+        bounds
+    }
+
+    /// Draws the glyph distance field into `width` x `height` pixels, offset by 1 pixel
+    /// if `padded`.
+    pub(crate) fn draw(
+        &self,
+        glyph: &OutlinedGlyph,
+        width: usize,
+        height: usize,
+        padded: bool,
+    ) -> ByteArray2d {
+        let pad = padded as usize;
+        let border = self.border() as usize;
+        let (w, h) = (width - 2 * pad, height - 2 * pad);
+
+        // Squared distances to the outline from outside & inside,
+        // seeded with sub-pixel distances from coverage
+        let mut outer = vec![INF; w * h];
+        let mut inner = vec![0.0; w * h];
+        glyph.draw(|x, y, coverage| {
+            let (x, y) = (x as usize + border, y as usize + border);
+            if x < w && y < h {
+                let idx = y * w + x;
+                // quantize, as rasterization leaves tiny values in empty pixels
+                let coverage = f64::from((coverage * 255.0).round()) / 255.0;
+                if coverage >= 1.0 {
+                    outer[idx] = 0.0;
+                    inner[idx] = INF;
+                } else if coverage > 0.0 {
+                    outer[idx] = (0.5 - coverage).max(0.0).powi(2);
+                    inner[idx] = (coverage - 0.5).max(0.0).powi(2);
+                }
+            }
+        });
+
+        let mut edt = Edt::new(w.max(h));
+        edt.transform_2d(&mut outer, w, h);
+        edt.transform_2d(&mut inner, w, h);
+
+        let spread = f64::from(self.spread);
+        let mut pixels = ByteArray2d::zeros(height, width);
+        for y in 0..h {
+            for x in 0..w {
+                let idx = y * w + x;
+                let distance_inside = inner[idx].sqrt() - outer[idx].sqrt();
+                let v = (0.5 + distance_inside / (2.0 * spread)).clamp(0.0, 1.0);
+                pixels[(y + pad, x + pad)] = (v * 255.0).round() as u8;
+            }
+        }
+        pixels
+    }
+}
+
+const INF: f64 = 1e20;
+
+/// Felzenszwalb & Huttenlocher squared euclidean distance transform buffers.
+struct Edt {
+    f: Vec<f64>,
+    d: Vec<f64>,
+    v: Vec<usize>,
+    z: Vec<f64>,
+}
+
+impl Edt {
+    fn new(len: usize) -> Self {
+        Self {
+            f: vec![0.0; len],
+            d: vec![0.0; len],
+            v: vec![0; len],
+            z: vec![0.0; len + 1],
+        }
+    }
+
+    /// Transforms `grid` of squared distances in place, columns then rows.
+    fn transform_2d(&mut self, grid: &mut [f64], width: usize, height: usize) {
+        for x in 0..width {
+            for y in 0..height {
+                self.f[y] = grid[y * width + x];
+            }
+            self.transform_1d(height);
+            for y in 0..height {
+                grid[y * width + x] = self.d[y];
+            }
+        }
+        for y in 0..height {
+            let row = &mut grid[y * width..(y + 1) * width];
+            self.f[..width].copy_from_slice(row);
+            self.transform_1d(width);
+            row.copy_from_slice(&self.d[..width]);
+        }
+    }
+
+    fn transform_1d(&mut self, n: usize) {
+        let Self { f, d, v, z } = self;
+        let mut k = 0;
+        v[0] = 0;
+        z[0] = -INF;
+        z[1] = INF;
+
+        for q in 1..n {
+            let qf = q as f64;
+            let mut s;
+            loop {
+                let r = v[k] as f64;
+                // subtract distances first, avoiding precision loss with `INF`
+                s = (f[q] - f[v[k]] + qf * qf - r * r) / (2.0 * (qf - r));
+                if s > z[k] {
+                    break;
+                }
+                // z[0] is -INF so k never underflows
+                k -= 1;
+            }
+            k += 1;
+            v[k] = q;
+            z[k] = s;
+            z[k + 1] = INF;
+        }
+
+        k = 0;
+        for (q, d) in d.iter_mut().enumerate().take(n) {
+            let qf = q as f64;
+            while z[k + 1] < qf {
+                k += 1;
+            }
+            let r = v[k] as f64;
+            *d = (qf - r) * (qf - r) + f[v[k]];
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn edt_1d() {
+        let mut edt = Edt::new(5);
+        edt.f.copy_from_slice(&[INF, INF, 0.0, INF, INF]);
+        edt.transform_1d(5);
+        assert_eq!(edt.d, [4.0, 1.0, 0.0, 1.0, 4.0]);
+    }
+}
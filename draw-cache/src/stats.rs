@@ -0,0 +1,41 @@
+use crate::CachedBy;
+
+/// Draw cache usage statistics, see [`DrawCache::stats`](struct.DrawCache.html#method.stats).
+///
+/// Counters are totals since the cache was built, other than `hits` & `misses` which
+/// describe the latest `cache_queued` call.
+///
+/// # Example
+/// ```
+/// use glyph_brush_draw_cache::DrawCache;
+///
+/// let cache = DrawCache::builder().build();
+/// let stats = cache.stats();
+/// assert_eq!(stats.glyphs, 0);
+/// assert_eq!(stats.reorders, 0);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[non_exhaustive]
+pub struct DrawCacheStats {
+    /// Number of cached glyph textures.
+    pub glyphs: usize,
+    /// Number of texture rows, always `0` using `Packer::Skyline`.
+    pub rows: usize,
+    /// Fraction `0.0..=1.0` of the total texture area, across all pages, occupied by
+    /// cached glyph textures.
+    pub occupied_area_ratio: f32,
+    /// Queued glyphs already cached in the latest `cache_queued`.
+    pub hits: usize,
+    /// Distinct queued glyphs not already cached in the latest `cache_queued`, i.e. the
+    /// number of glyphs rasterized.
+    pub misses: usize,
+    /// Total glyphs removed from least recently used rows to make room for new glyphs.
+    pub evictions: u64,
+    /// Total `cache_queued` calls that cleared & re-ordered the cache, i.e. returned
+    /// `CachedBy::Reordering`.
+    pub reorders: u64,
+    /// Total bytes of texture data passed to uploaders.
+    pub bytes_uploaded: u64,
+    /// How the latest successful `cache_queued` cached the queue.
+    pub last_cached_by: Option<CachedBy>,
+}
@@ -0,0 +1,185 @@
+use ::ab_glyph::*;
+
+/// Synthetic emboldening & oblique skew applied to glyph outlines before rasterization.
+///
+/// Useful to emphasise text using fonts that have no bold or italic faces.
+///
+/// # Example
+/// ```
+/// use glyph_brush_draw_cache::SyntheticStyle;
+///
+/// let bold = SyntheticStyle {
+///     bold: true,
+///     ..SyntheticStyle::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct SyntheticStyle {
+    /// Embolden glyphs by widening outlines horizontally by
+    /// [`BOLD_STRENGTH`](#associatedconstant.BOLD_STRENGTH) of the x-scale.
+    pub bold: bool,
+    /// Skew glyphs to the right by [`OBLIQUE_ANGLE`](#associatedconstant.OBLIQUE_ANGLE).
+    pub oblique: bool,
+}
+
+impl SyntheticStyle {
+    /// Amount synthetic bold glyphs are widened as a fraction of the x-scale,
+    /// i.e. a glyph with `PxScale { x: 24.0, .. }` is widened by `1px`.
+    ///
+    /// Matches `glyph_brush_layout::SYNTHETIC_BOLD_STRENGTH` which built-in layouts add to the
+    /// advance of emboldened glyphs.
+    pub const BOLD_STRENGTH: f32 = 1.0 / 24.0;
+
+    /// Synthetic oblique skew angle, in degrees.
+    pub const OBLIQUE_ANGLE: f32 = 12.0;
+
+    /// Returns true if this style does not alter glyphs.
+    #[inline]
+    pub fn is_none(&self) -> bool {
+        !self.bold && !self.oblique
+    }
+}
+
+/// Outlines a glyph applying any synthetic style & `stem_darkening` pixel emboldening.
+pub(crate) fn outline_glyph<F: Font>(
+    font: &F,
+    glyph: Glyph,
+    style: SyntheticStyle,
+    stem_darkening: f32,
+) -> Option<OutlinedGlyph> {
+    if style.is_none() && stem_darkening <= 0.0 {
+        return font.outline_glyph(glyph);
+    }
+
+    let mut outline = font.outline(glyph.id)?;
+    let scale_factor = font.as_scaled(glyph.scale).scale_factor();
+    if style.oblique {
+        oblique(&mut outline);
+    }
+    if style.bold {
+        embolden(
+            &mut outline,
+            font.height_unscaled() * SyntheticStyle::BOLD_STRENGTH,
+            scale_factor.horizontal,
+        );
+    }
+    if stem_darkening > 0.0 {
+        embolden(
+            &mut outline,
+            stem_darkening / scale_factor.horizontal,
+            scale_factor.horizontal,
+        );
+    }
+    Some(OutlinedGlyph::new(glyph, outline, scale_factor))
+}
+
+/// Skews the outline to the right, relative to the baseline.
+fn oblique(outline: &mut Outline) {
+    let skew = SyntheticStyle::OBLIQUE_ANGLE.to_radians().tan();
+    // unscaled outlines are y-up so positive y moves right
+    let shear = |p: &mut Point| p.x += p.y * skew;
+
+    for curve in &mut outline.curves {
+        match curve {
+            OutlineCurve::Line(a, b) => {
+                shear(a);
+                shear(b);
+            }
+            OutlineCurve::Quad(a, b, c) => {
+                shear(a);
+                shear(b);
+                shear(c);
+            }
+            OutlineCurve::Cubic(a, b, c, d) => {
+                shear(a);
+                shear(b);
+                shear(c);
+                shear(d);
+            }
+        }
+    }
+
+    let Rect { min, max } = &mut outline.bounds;
+    let (min_y_shift, max_y_shift) = (min.y * skew, max.y * skew);
+    min.x += min_y_shift.min(max_y_shift);
+    max.x += min_y_shift.max(max_y_shift);
+}
+
+/// Widens the outline horizontally by `strength` unscaled units, by overlaying
+/// shifted copies of the curves. Copies are at most half a pixel apart, at `px_per_unit`
+/// horizontal scale, so thin strokes leave no gaps.
+///
+/// Overlapping same-winding curves accumulate coverage which is clamped when drawn.
+fn embolden(outline: &mut Outline, strength: f32, px_per_unit: f32) {
+    let steps = (strength * px_per_unit * 2.0).ceil().max(1.0) as u32;
+
+    let original = outline.curves.clone();
+    outline.curves.reserve(original.len() * steps as usize);
+    for step in 1..=steps {
+        let shift = point(strength * step as f32 / steps as f32, 0.0);
+        outline
+            .curves
+            .extend(original.iter().map(|curve| match *curve {
+                OutlineCurve::Line(a, b) => OutlineCurve::Line(a + shift, b + shift),
+                OutlineCurve::Quad(a, b, c) => OutlineCurve::Quad(a + shift, b + shift, c + shift),
+                OutlineCurve::Cubic(a, b, c, d) => {
+                    OutlineCurve::Cubic(a + shift, b + shift, c + shift, d + shift)
+                }
+            }));
+    }
+    outline.bounds.max.x += strength;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use approx::*;
+
+    #[test]
+    fn bold_strength_matches_layout_advance() {
+        assert_relative_eq!(
+            SyntheticStyle::BOLD_STRENGTH,
+            glyph_brush_layout::SYNTHETIC_BOLD_STRENGTH
+        );
+    }
+
+    #[test]
+    fn embolden_steps_scale_with_width() {
+        // 1 unit wide bar
+        let bar = Outline {
+            bounds: Rect {
+                min: point(0.0, 0.0),
+                max: point(1.0, 10.0),
+            },
+            curves: vec![
+                OutlineCurve::Line(point(0.0, 0.0), point(0.0, 10.0)),
+                OutlineCurve::Line(point(0.0, 10.0), point(1.0, 10.0)),
+                OutlineCurve::Line(point(1.0, 10.0), point(1.0, 0.0)),
+                OutlineCurve::Line(point(1.0, 0.0), point(0.0, 0.0)),
+            ],
+        };
+
+        // widen by 20px at 2px per unit
+        let mut outline = bar.clone();
+        embolden(&mut outline, 10.0, 2.0);
+        assert_relative_eq!(outline.bounds.max.x, 11.0);
+
+        let mut shifts: Vec<f32> = outline
+            .curves
+            .chunks(bar.curves.len())
+            .map(|copy| match copy[0] {
+                OutlineCurve::Line(a, _) => a.x,
+                _ => unreachable!(),
+            })
+            .collect();
+        shifts.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_relative_eq!(shifts[0], 0.0);
+        assert_relative_eq!(*shifts.last().unwrap(), 10.0);
+        // copies are at most half a pixel apart
+        for pair in shifts.windows(2) {
+            assert!(pair[1] - pair[0] <= 0.25 + 1e-4, "{:?}", pair);
+        }
+    }
+}
@@ -72,6 +72,7 @@ fn main() -> Result<(), Box<dyn Error>> {
             text: include_str!("lipsum.txt"),
             scale: PxScale::from(30.0),
             font_id: FontId(0),
+            ..gfx_glyph::SectionText::default()
         }],
     );
 
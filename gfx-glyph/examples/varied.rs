@@ -105,6 +105,8 @@ fn main() -> Result<(), Box<dyn Error>> {
                             text: "Lorem ipsum dolor sit amet, ferri simul omittantur eam eu, ",
                             scale: PxScale::from(45.0),
                             font_id: sans_font,
+                            variations: &[],
+                            synthetic_style: SyntheticStyle::default(),
                             extra: Extra {
                                 color: [0.9, 0.3, 0.3, 1.0],
                                 z: 0.0,
@@ -114,6 +116,8 @@ fn main() -> Result<(), Box<dyn Error>> {
                             text: "dolorem",
                             scale: PxScale::from(150.0),
                             font_id: serif_font,
+                            variations: &[],
+                            synthetic_style: SyntheticStyle::default(),
                             extra: Extra {
                                 color: [0.3, 0.9, 0.3, 1.0],
                                 z: 0.0,
@@ -123,6 +127,8 @@ fn main() -> Result<(), Box<dyn Error>> {
                             text: " Iriure vocibus est te, natum delicata dignissim pri ea.",
                             scale: PxScale::from(25.0),
                             font_id: sans_font,
+                            variations: &[],
+                            synthetic_style: SyntheticStyle::default(),
                             extra: Extra {
                                 color: [0.3, 0.3, 0.9, 1.0],
                                 z: 0.0,
@@ -130,6 +136,7 @@ fn main() -> Result<(), Box<dyn Error>> {
                         },
                     ],
                     layout: Layout::default().v_align(VerticalAlign::Center),
+                    transform: None,
                 });
 
                 glyph_brush.queue(Section {
@@ -140,6 +147,8 @@ fn main() -> Result<(), Box<dyn Error>> {
                             text: "foo += bar;",
                             scale: PxScale::from(45.0),
                             font_id: mono_font,
+                            variations: &[],
+                            synthetic_style: SyntheticStyle::default(),
                             extra: Extra {
                                 color: [0.3, 0.3, 0.9, 1.0],
                                 z: 0.0,
@@ -149,6 +158,8 @@ fn main() -> Result<(), Box<dyn Error>> {
                             text: " eruditi habemus qualisque eam an. No atqui apeirian phaedrum pri ex, hinc omnes sapientem. ",
                             scale: PxScale::from(30.0),
                             font_id: italic_font,
+                            variations: &[],
+                            synthetic_style: SyntheticStyle::default(),
                             extra: Extra {
                                 color: [0.9, 0.3, 0.3, 1.0],
                                 z: 0.0,
@@ -158,6 +169,8 @@ fn main() -> Result<(), Box<dyn Error>> {
                             text: "Eu facilisi maluisset eos.",
                             scale: PxScale::from(55.0),
                             font_id: sans_font,
+                            variations: &[],
+                            synthetic_style: SyntheticStyle::default(),
                             extra: Extra {
                                 color: [0.3, 0.9, 0.3, 1.0],
                                 z: 0.0,
@@ -167,6 +180,8 @@ fn main() -> Result<(), Box<dyn Error>> {
                             text: " ius nullam impetus. ",
                             scale: PxScale { x: 25.0, y: 45.0 },
                             font_id: serif_font,
+                            variations: &[],
+                            synthetic_style: SyntheticStyle::default(),
                             extra: Extra {
                                 color: [0.9, 0.9, 0.3, 1.0],
                                 z: 0.0,
@@ -176,6 +191,8 @@ fn main() -> Result<(), Box<dyn Error>> {
                             text: "Ut quo elitr viderer constituam, pro omnesque forensibus at. Timeam scaevola mediocrem ut pri, te pro congue delicatissimi. Mei wisi nostro imperdiet ea, ridens salutatus per no, ut viris partem disputationi sit. Exerci eripuit referrentur vix at, sale mediocrem repudiare per te, modus admodum an eam. No vocent indoctum vis, ne quodsi patrioque vix. Vocent labores omittam et usu.",
                             scale: PxScale::from(22.0),
                             font_id: italic_font,
+                            variations: &[],
+                            synthetic_style: SyntheticStyle::default(),
                             extra: Extra {
                                 color: [0.8, 0.3, 0.5, 1.0],
                                 z: 0.0,
@@ -183,6 +200,7 @@ fn main() -> Result<(), Box<dyn Error>> {
                         },
                     ],
                     layout: Layout::default().h_align(HorizontalAlign::Right).v_align(VerticalAlign::Center),
+                    transform: None,
                 });
 
                 glyph_brush.use_queue().draw(&mut encoder, &main_color).unwrap();
@@ -145,13 +145,34 @@ where
     {
         let inner = self.inner.build();
         let (cache_width, cache_height) = inner.texture_dimensions();
-        let font_cache_tex = create_texture(&mut factory, cache_width, cache_height).unwrap();
+        let texture_format = inner.draw_cache_texture_format();
+        let font_cache_tex = create_texture(
+            &mut factory,
+            cache_width,
+            cache_height,
+            inner.texture_pages(),
+            texture_format,
+        )
+        .unwrap();
+        let frag_shader: &[u8] = match (inner.draw_cache_sdf(), texture_format) {
+            (Some(_), _) => include_bytes!("shader/frag_sdf.glsl"),
+            (None, TextureFormat::LcdRgb8) => include_bytes!("shader/frag_lcd.glsl"),
+            (None, TextureFormat::A8) => include_bytes!("shader/frag.glsl"),
+        };
         let program = factory
-            .link_program(
-                include_bytes!("shader/vert.glsl"),
-                include_bytes!("shader/frag.glsl"),
-            )
+            .link_program(include_bytes!("shader/vert.glsl"), frag_shader)
             .unwrap();
+        let lcd_mask_program = match texture_format {
+            TextureFormat::LcdRgb8 => Some(
+                factory
+                    .link_program(
+                        include_bytes!("shader/vert.glsl"),
+                        include_bytes!("shader/frag_lcd_mask.glsl"),
+                    )
+                    .unwrap(),
+            ),
+            TextureFormat::A8 => None,
+        };
 
         GlyphBrush {
             font_cache_tex,
@@ -160,6 +181,7 @@ where
 
             factory,
             program,
+            lcd_mask_program,
             draw_cache: None,
 
             depth_test: self.depth_test,
@@ -1,5 +1,6 @@
 use crate::{
     default_transform, GlyphBrush, RawAndFormat, RawDepthStencilView, RawRenderTargetView,
+    VariableFontInstance,
 };
 use glyph_brush::ab_glyph::*;
 use std::{hash::BuildHasher, marker::PhantomData};
@@ -137,7 +138,7 @@ where
 
 impl<'a, F, R, GF, H, DV> DrawBuilder<'a, F, R, GF, H, DV>
 where
-    F: Font + Sync,
+    F: VariableFontInstance + Sync,
     R: gfx::Resources,
     GF: gfx::Factory<R>,
     H: BuildHasher,
@@ -226,7 +227,7 @@ impl<R: gfx::Resources> RawAndFormat for NoDepth<R> {
 
 impl<'a, F, R, GF, H> DrawBuilder<'a, F, R, GF, H, ()>
 where
-    F: Font + Sync,
+    F: VariableFontInstance + Sync,
     R: gfx::Resources,
     GF: gfx::Factory<R>,
     H: BuildHasher,
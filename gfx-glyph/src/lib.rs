@@ -52,15 +52,20 @@ mod draw_builder;
 
 pub use crate::{builder::*, draw_builder::*};
 pub use glyph_brush::{
-    ab_glyph, legacy, BuiltInLineBreaker, Extra, FontId, GlyphCruncher, GlyphPositioner,
-    HorizontalAlign, Layout, LineBreak, LineBreaker, OwnedSection, OwnedText, Section,
-    SectionGeometry, SectionGlyph, SectionGlyphIter, SectionText, Text, VerticalAlign,
+    ab_glyph, legacy, markup, AlignAnchor, AtlasImage, AtlasOverlay, BuiltInLineBreaker, CachedBy,
+    CellWidth, CoverageAdjustment, DrawCacheStats, Extra, FontId, FontVariation, GlyphBrushStats,
+    GlyphCruncher, GlyphPositioner, GridGlyph, GridLayout, HorizontalAlign, Layout, LineBreak,
+    LineBreaker, LineIndex, OverflowWrap, OwnedSection, OwnedText, Packer, ParagraphFormat,
+    ScrollLayout, Sdf, Section, SectionGeometry, SectionGlyph, SectionGlyphIter, SectionLine,
+    SectionText, SyntheticStyle, Text, TextureFormat, VariableFontInstance, VerticalAlign,
+    WhiteSpace,
 };
 
-use crate::pipe::{glyph_pipe, GlyphVertex, IntoDimensions, RawAndFormat};
+use crate::pipe::{glyph_pipe, GlyphVertex, IntoDimensions, RawAndFormat, LCD_MASK_BLEND};
 use gfx::{
     format,
     handle::{self, RawDepthStencilView, RawRenderTargetView},
+    memory::Typed,
     texture,
     traits::FactoryExt,
 };
@@ -78,9 +83,13 @@ type TexForm = format::U8Norm;
 type TexSurface = <TexForm as format::Formatted>::Surface;
 type TexChannel = <TexForm as format::Formatted>::Channel;
 type TexFormView = <TexForm as format::Formatted>::View;
-type TexSurfaceHandle<R> = handle::Texture<R, TexSurface>;
 type TexShaderView<R> = handle::ShaderResourceView<R, TexFormView>;
 
+// Type for `TextureFormat::LcdRgb8` glyph cache textures, rgb coverage is expanded to rgba
+type LcdTexForm = format::Rgba8;
+type LcdTexSurface = <LcdTexForm as format::Formatted>::Surface;
+type LcdTexChannel = <LcdTexForm as format::Formatted>::Channel;
+
 /// Returns the default 4 dimensional matrix orthographic projection used for drawing.
 ///
 /// # Example
@@ -114,6 +123,14 @@ pub fn default_transform<D: IntoDimensions>(d: D) -> [[f32; 4]; 4] {
     ]
 }
 
+/// Section transform of untransformed sections.
+const IDENTITY_MATRIX4: [[f32; 4]; 4] = [
+    [1.0, 0.0, 0.0, 0.0],
+    [0.0, 1.0, 0.0, 0.0],
+    [0.0, 0.0, 1.0, 0.0],
+    [0.0, 0.0, 0.0, 1.0],
+];
+
 /// Object allowing glyph drawing, containing cache state. Manages glyph positioning cacheing,
 /// glyph draw caching & efficient GPU texture cache updating and re-sizing on demand.
 ///
@@ -165,12 +182,14 @@ pub fn default_transform<D: IntoDimensions>(d: D) -> [[f32; 4]; 4] {
 pub struct GlyphBrush<R: gfx::Resources, GF: gfx::Factory<R>, F = FontArc, H = DefaultSectionHasher>
 {
     font_cache_tex: (
-        gfx::handle::Texture<R, TexSurface>,
+        gfx::handle::RawTexture<R>,
         gfx_core::handle::ShaderResourceView<R, f32>,
     ),
     texture_filter_method: texture::FilterMethod,
     factory: GF,
     program: gfx::handle::Program<R>,
+    /// Program of the first `TextureFormat::LcdRgb8` draw pass.
+    lcd_mask_program: Option<gfx::handle::Program<R>>,
     draw_cache: Option<DrawnGlyphBrush<R>>,
     glyph_brush: glyph_brush::GlyphBrush<GlyphVertex, Extra, F, H>,
 
@@ -201,7 +220,7 @@ where
 
 impl<R, GF, F, H> GlyphCruncher<F, Extra> for GlyphBrush<R, GF, F, H>
 where
-    F: Font,
+    F: VariableFontInstance,
     R: gfx::Resources,
     GF: gfx::Factory<R>,
     H: BuildHasher,
@@ -225,6 +244,11 @@ where
         self.glyph_brush.fonts()
     }
 
+    #[inline]
+    fn font(&self, font_id: FontId) -> &F {
+        self.glyph_brush.font(font_id)
+    }
+
     #[inline]
     fn glyph_bounds_custom_layout<'a, S, L>(
         &mut self,
@@ -238,11 +262,25 @@ where
         self.glyph_brush
             .glyph_bounds_custom_layout(section, custom_layout)
     }
+
+    #[inline]
+    fn layout_bounds_custom_layout<'a, S, L>(
+        &mut self,
+        section: S,
+        custom_layout: &L,
+    ) -> Option<Rect>
+    where
+        L: GlyphPositioner + Hash,
+        S: Into<Cow<'a, Section<'a>>>,
+    {
+        self.glyph_brush
+            .layout_bounds_custom_layout(section, custom_layout)
+    }
 }
 
 impl<R, GF, F, H> GlyphBrush<R, GF, F, H>
 where
-    F: Font + Sync,
+    F: VariableFontInstance + Sync,
     R: gfx::Resources,
     GF: gfx::Factory<R>,
     H: BuildHasher,
@@ -356,6 +394,22 @@ where
         self.glyph_brush.fonts()
     }
 
+    /// Returns layout & draw cache statistics of the latest draw.
+    ///
+    /// See [`glyph_brush::GlyphBrush::stats`](https://docs.rs/glyph_brush/*/glyph_brush/struct.GlyphBrush.html#method.stats).
+    #[inline]
+    pub fn stats(&self) -> GlyphBrushStats {
+        self.glyph_brush.stats()
+    }
+
+    /// Redraws the glyph cache texture contents into an 8-bit greyscale image.
+    ///
+    /// See [`glyph_brush::GlyphBrush::atlas_image`](https://docs.rs/glyph_brush/*/glyph_brush/struct.GlyphBrush.html#method.atlas_image).
+    #[inline]
+    pub fn atlas_image(&self, overlay: AtlasOverlay) -> AtlasImage {
+        self.glyph_brush.atlas_image(overlay)
+    }
+
     /// Draws all queued sections
     pub(crate) fn draw<C, CV, DV>(
         &mut self,
@@ -370,48 +424,87 @@ where
         DV: RawAndFormat<Raw = RawDepthStencilView<R>>,
     {
         let mut brush_action;
+        let texture_format = self.glyph_brush.draw_cache_texture_format();
 
         loop {
             let tex = self.font_cache_tex.0.clone();
 
-            brush_action = self.glyph_brush.process_queued(
-                |rect, tex_data| {
+            brush_action = self.glyph_brush.process_queued_paged(
+                |page, rect, tex_data| {
                     let offset = [rect.min[0] as u16, rect.min[1] as u16];
                     let size = [rect.width() as u16, rect.height() as u16];
-                    update_texture(&mut encoder, &tex, offset, size, tex_data);
+                    update_texture(
+                        &mut encoder,
+                        &tex,
+                        texture_format,
+                        page as u16,
+                        offset,
+                        size,
+                        tex_data,
+                    );
                 },
                 to_vertex,
             );
 
             match brush_action {
                 Ok(_) => break,
-                Err(BrushError::TextureTooSmall { suggested }) => {
+                Err(BrushError::TextureTooSmall {
+                    suggested,
+                    suggested_pages,
+                }) => {
                     let max_image_dimension =
                         self.factory.get_capabilities().max_texture_size as u32;
-                    let (new_width, new_height) = if (suggested.0 > max_image_dimension
-                        || suggested.1 > max_image_dimension)
-                        && (self.glyph_brush.texture_dimensions().0 < max_image_dimension
-                            || self.glyph_brush.texture_dimensions().1 < max_image_dimension)
+                    let (width, height) = self.glyph_brush.texture_dimensions();
+                    let pages = self.glyph_brush.texture_pages();
+                    let (new_width, new_height, new_pages) = if suggested_pages > pages {
+                        (width, height, suggested_pages)
+                    } else if suggested.0 > max_image_dimension || suggested.1 > max_image_dimension
                     {
-                        (max_image_dimension, max_image_dimension)
+                        if width < max_image_dimension || height < max_image_dimension {
+                            (max_image_dimension, max_image_dimension, pages)
+                        } else {
+                            // already max size, add a texture array layer instead
+                            (width, height, pages + 1)
+                        }
                     } else {
-                        suggested
+                        (suggested.0, suggested.1, pages)
                     };
 
                     if log_enabled!(log::Level::Warn) {
-                        warn!(
-                            "Increasing glyph texture size {old:?} -> {new:?}. \
-                             Consider building with `.initial_cache_size({new:?})` to avoid \
-                             resizing. Called from:\n{trace}",
-                            old = self.glyph_brush.texture_dimensions(),
-                            new = (new_width, new_height),
-                            trace = outer_backtrace!()
-                        );
+                        if new_pages != pages {
+                            warn!(
+                                "Increasing glyph texture pages {old} -> {new}. \
+                                 Consider building with `.initial_cache_pages({new})` to avoid \
+                                 resizing. Called from:\n{trace}",
+                                old = pages,
+                                new = new_pages,
+                                trace = outer_backtrace!()
+                            );
+                        } else {
+                            warn!(
+                                "Increasing glyph texture size {old:?} -> {new:?}. \
+                                 Consider building with `.initial_cache_size({new:?})` to avoid \
+                                 resizing. Called from:\n{trace}",
+                                old = (width, height),
+                                new = (new_width, new_height),
+                                trace = outer_backtrace!()
+                            );
+                        }
                     }
 
-                    match create_texture(&mut self.factory, new_width, new_height) {
+                    match create_texture(
+                        &mut self.factory,
+                        new_width,
+                        new_height,
+                        new_pages,
+                        texture_format,
+                    ) {
                         Ok((new_tex, tex_view)) => {
-                            self.glyph_brush.resize_texture(new_width, new_height);
+                            if new_pages != pages {
+                                self.glyph_brush.resize_texture_pages(new_pages);
+                            } else {
+                                self.glyph_brush.resize_texture(new_width, new_height);
+                            }
 
                             if let Some(ref mut cache) = self.draw_cache {
                                 cache.pipe_data.font_tex.0 = tex_view.clone();
@@ -422,8 +515,8 @@ where
                         }
                         Err(_) => {
                             return Err(format!(
-                                "Failed to create {}x{} glyph texture",
-                                new_width, new_height
+                                "Failed to create {}x{} glyph texture with {} layers",
+                                new_width, new_height, new_pages
                             ));
                         }
                     }
@@ -453,6 +546,8 @@ where
                     target.format(),
                     self.pso_using(target.format(), depth_target.map(|d| d.format())),
                 );
+                cache.lcd_mask_pso =
+                    self.lcd_mask_pso_using(target.format(), depth_target.map(|d| d.format()));
             }
             self.draw_cache = Some(cache);
         }
@@ -491,6 +586,8 @@ where
                             target.format(),
                             self.pso_using(target.format(), depth_target.map(|d| d.format())),
                         ),
+                        lcd_mask_pso: self
+                            .lcd_mask_pso_using(target.format(), depth_target.map(|d| d.format())),
                         slice: gfx::Slice {
                             instances: Some((verts.len() as _, 0)),
                             ..Self::empty_slice()
@@ -505,12 +602,15 @@ where
 
         if let Some(&mut DrawnGlyphBrush {
             ref pso,
+            ref lcd_mask_pso,
             ref slice,
             ref mut pipe_data,
-            ..
         }) = self.draw_cache.as_mut()
         {
             pipe_data.transform = transform;
+            if let Some(lcd_mask_pso) = lcd_mask_pso {
+                encoder.draw(slice, lcd_mask_pso, pipe_data);
+            }
             encoder.draw(slice, &pso.1, pipe_data);
         }
 
@@ -522,16 +622,45 @@ where
         color_format: gfx::format::Format,
         depth_format: Option<gfx::format::Format>,
     ) -> gfx::PipelineState<R, glyph_pipe::Meta> {
+        // lcd text is drawn additively after the mask pass
+        let blend = match self.lcd_mask_program {
+            Some(_) => gfx::preset::blend::ADD,
+            None => gfx::preset::blend::ALPHA,
+        };
         self.factory
             .create_pipeline_from_program(
                 &self.program,
                 gfx::Primitive::TriangleStrip,
                 gfx::state::Rasterizer::new_fill(),
-                glyph_pipe::Init::new(color_format, depth_format, self.depth_test),
+                glyph_pipe::Init::new(color_format, depth_format, self.depth_test, blend),
             )
             .unwrap()
     }
 
+    /// Returns the first pass pipeline for `TextureFormat::LcdRgb8` drawing, if used.
+    fn lcd_mask_pso_using(
+        &mut self,
+        color_format: gfx::format::Format,
+        depth_format: Option<gfx::format::Format>,
+    ) -> Option<gfx::PipelineState<R, glyph_pipe::Meta>> {
+        let program = self.lcd_mask_program.as_ref()?;
+        // depth is written by the second pass
+        let depth_test = gfx::state::Depth {
+            write: false,
+            ..self.depth_test
+        };
+        let pso = self
+            .factory
+            .create_pipeline_from_program(
+                program,
+                gfx::Primitive::TriangleStrip,
+                gfx::state::Rasterizer::new_fill(),
+                glyph_pipe::Init::new(color_format, depth_format, depth_test, LCD_MASK_BLEND),
+            )
+            .unwrap();
+        Some(pso)
+    }
+
     fn empty_slice() -> gfx::Slice<R> {
         gfx::Slice {
             start: 0,
@@ -546,6 +675,7 @@ where
 struct DrawnGlyphBrush<R: gfx::Resources> {
     pipe_data: glyph_pipe::Data<R>,
     pso: (gfx::format::Format, gfx::PipelineState<R, glyph_pipe::Meta>),
+    lcd_mask_pso: Option<gfx::PipelineState<R, glyph_pipe::Meta>>,
     slice: gfx::Slice<R>,
 }
 
@@ -572,9 +702,12 @@ fn new_vertex_buffer<R: gfx::Resources, F: gfx::Factory<R>, C: gfx::CommandBuffe
 fn to_vertex(
     glyph_brush::GlyphVertex {
         mut tex_coords,
+        tex_page,
         pixel_coords,
         bounds,
         extra,
+        transform,
+        ..
     }: glyph_brush::GlyphVertex,
 ) -> GlyphVertex {
     let gl_bounds = bounds;
@@ -606,50 +739,83 @@ fn to_vertex(
         tex_coords.min.y = tex_coords.max.y - tex_coords.height() * gl_rect.height() / old_height;
     }
 
+    let section_transform = transform.copied().unwrap_or(IDENTITY_MATRIX4);
+
     GlyphVertex {
         left_top: [gl_rect.min.x, gl_rect.max.y, extra.z],
         right_bottom: [gl_rect.max.x, gl_rect.min.y],
         tex_left_top: [tex_coords.min.x, tex_coords.max.y],
         tex_right_bottom: [tex_coords.max.x, tex_coords.min.y],
+        tex_page: tex_page as f32,
         color: extra.color,
+        section_transform_0: section_transform[0],
+        section_transform_1: section_transform[1],
+        section_transform_2: section_transform[2],
+        section_transform_3: section_transform[3],
     }
 }
 
-// Creates a gfx texture with the given data
+// Creates a gfx 2D texture array with the given data
 fn create_texture<GF, R>(
     factory: &mut GF,
     width: u32,
     height: u32,
-) -> Result<(TexSurfaceHandle<R>, TexShaderView<R>), Box<dyn Error>>
+    pages: u32,
+    texture_format: TextureFormat,
+) -> Result<(handle::RawTexture<R>, TexShaderView<R>), Box<dyn Error>>
 where
     R: gfx::Resources,
     GF: gfx::Factory<R>,
 {
-    let kind = texture::Kind::D2(
+    let kind = texture::Kind::D2Array(
         width as texture::Size,
         height as texture::Size,
+        pages as texture::Layer,
         texture::AaMode::Single,
     );
 
-    let tex = factory.create_texture(
-        kind,
-        1,
-        gfx::memory::Bind::SHADER_RESOURCE,
-        gfx::memory::Usage::Dynamic,
-        Some(<TexChannel as format::ChannelTyped>::get_channel_type()),
-    )?;
-
-    let view =
-        factory.view_texture_as_shader_resource::<TexForm>(&tex, (0, 0), format::Swizzle::new())?;
-
-    Ok((tex, view))
+    match texture_format {
+        TextureFormat::A8 => {
+            let tex = factory.create_texture::<TexSurface>(
+                kind,
+                1,
+                gfx::memory::Bind::SHADER_RESOURCE,
+                gfx::memory::Usage::Dynamic,
+                Some(<TexChannel as format::ChannelTyped>::get_channel_type()),
+            )?;
+            let view = factory.view_texture_as_shader_resource::<TexForm>(
+                &tex,
+                (0, 0),
+                format::Swizzle::new(),
+            )?;
+            Ok((tex.raw().clone(), view))
+        }
+        TextureFormat::LcdRgb8 => {
+            let tex = factory.create_texture::<LcdTexSurface>(
+                kind,
+                1,
+                gfx::memory::Bind::SHADER_RESOURCE,
+                gfx::memory::Usage::Dynamic,
+                Some(<LcdTexChannel as format::ChannelTyped>::get_channel_type()),
+            )?;
+            let view = factory.view_texture_as_shader_resource::<LcdTexForm>(
+                &tex,
+                (0, 0),
+                format::Swizzle::new(),
+            )?;
+            // shaders sample all channels regardless of the view type
+            Ok((tex.raw().clone(), Typed::new(view.raw().clone())))
+        }
+    }
 }
 
 // Updates a texture with the given data (used for updating the GlyphCache texture)
 #[inline]
 fn update_texture<R, C>(
     encoder: &mut gfx::Encoder<R, C>,
-    texture: &handle::Texture<R, TexSurface>,
+    texture: &handle::RawTexture<R>,
+    texture_format: TextureFormat,
+    page: u16,
     offset: [u16; 2],
     size: [u16; 2],
     data: &[u8],
@@ -660,14 +826,30 @@ fn update_texture<R, C>(
     let info = texture::ImageInfoCommon {
         xoffset: offset[0],
         yoffset: offset[1],
-        zoffset: 0,
+        zoffset: page,
         width: size[0],
         height: size[1],
-        depth: 0,
+        depth: 1,
         format: (),
         mipmap: 0,
     };
-    encoder
-        .update_texture::<TexSurface, TexForm>(texture, None, info, data)
-        .unwrap();
+    match texture_format {
+        TextureFormat::A8 => encoder
+            .update_texture::<TexSurface, TexForm>(&Typed::new(texture.clone()), None, info, data)
+            .unwrap(),
+        TextureFormat::LcdRgb8 => {
+            let rgba: Vec<_> = data
+                .chunks_exact(3)
+                .map(|rgb| [rgb[0], rgb[1], rgb[2], 255])
+                .collect();
+            encoder
+                .update_texture::<LcdTexSurface, LcdTexForm>(
+                    &Typed::new(texture.clone()),
+                    None,
+                    info,
+                    &rgba,
+                )
+                .unwrap()
+        }
+    }
 }
@@ -58,8 +58,15 @@ gfx_defines! {
         /// texture position
         tex_left_top: [f32; 2] = "tex_left_top",
         tex_right_bottom: [f32; 2] = "tex_right_bottom",
+        /// texture array layer
+        tex_page: f32 = "tex_page",
         /// text color
         color: [f32; 4] = "color",
+        /// section transform matrix columns
+        section_transform_0: [f32; 4] = "section_transform_0",
+        section_transform_1: [f32; 4] = "section_transform_1",
+        section_transform_2: [f32; 4] = "section_transform_2",
+        section_transform_3: [f32; 4] = "section_transform_3",
     }
 }
 
@@ -71,11 +78,28 @@ gfx_pipeline_base!( glyph_pipe {
     out_depth: RawDepthTarget,
 });
 
+/// First pass of `TextureFormat::LcdRgb8` drawing, multiplying each target colour channel
+/// by `1 - subpixel coverage`. Followed by an additive pass of the text colour multiplied
+/// by the subpixel coverage, together providing per-channel alpha blending.
+pub const LCD_MASK_BLEND: state::Blend = state::Blend {
+    color: state::BlendChannel {
+        equation: state::Equation::Add,
+        source: state::Factor::Zero,
+        destination: state::Factor::OneMinus(state::BlendValue::SourceColor),
+    },
+    alpha: state::BlendChannel {
+        equation: state::Equation::Add,
+        source: state::Factor::Zero,
+        destination: state::Factor::One,
+    },
+};
+
 impl glyph_pipe::Init<'_> {
     pub fn new(
         color_format: format::Format,
         depth_format: Option<format::Format>,
         depth_test: state::Depth,
+        blend: state::Blend,
     ) -> Self {
         glyph_pipe::Init {
             vbuf: (),
@@ -85,7 +109,7 @@ impl glyph_pipe::Init<'_> {
                 "Target0",
                 color_format,
                 state::ColorMask::all(),
-                Some(preset::blend::ALPHA),
+                Some(blend),
             ),
             out_depth: depth_format.map(|d| (d, depth_test)),
         }
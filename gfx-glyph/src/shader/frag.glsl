@@ -1,8 +1,8 @@
 #version 150
 
-uniform sampler2D font_tex;
+uniform sampler2DArray font_tex;
 
-in vec2 f_tex_pos;
+in vec3 f_tex_pos;
 in vec4 f_color;
 
 out vec4 Target0;
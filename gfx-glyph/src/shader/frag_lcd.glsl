@@ -0,0 +1,18 @@
+#version 150
+
+uniform sampler2DArray font_tex;
+
+in vec3 f_tex_pos;
+in vec4 f_color;
+
+out vec4 Target0;
+
+void main() {
+    // subpixel coverage, blended additively after the lcd mask pass
+    vec3 coverage = texture(font_tex, f_tex_pos).rgb * f_color.a;
+    float alpha = max(max(coverage.r, coverage.g), coverage.b);
+    if (alpha <= 0.0) {
+        discard;
+    }
+    Target0 = vec4(f_color.rgb * coverage, (coverage.r + coverage.g + coverage.b) / 3.0);
+}
@@ -0,0 +1,19 @@
+#version 150
+
+uniform sampler2DArray font_tex;
+
+in vec3 f_tex_pos;
+in vec4 f_color;
+
+out vec4 Target0;
+
+void main() {
+    // signed distance field, the glyph outline is at 0.5
+    float distance = texture(font_tex, f_tex_pos).r;
+    float width = max(0.7 * fwidth(distance), 0.0001);
+    float alpha = smoothstep(0.5 - width, 0.5 + width, distance);
+    if (alpha <= 0.0) {
+        discard;
+    }
+    Target0 = f_color * vec4(1.0, 1.0, 1.0, alpha);
+}
@@ -13,9 +13,14 @@ in vec3 left_top;
 in vec2 right_bottom;
 in vec2 tex_left_top;
 in vec2 tex_right_bottom;
+in float tex_page;
 in vec4 color;
+in vec4 section_transform_0;
+in vec4 section_transform_1;
+in vec4 section_transform_2;
+in vec4 section_transform_3;
 
-out vec2 f_tex_pos;
+out vec3 f_tex_pos;
 out vec4 f_color;
 
 // generate positional data based on vertex ID
@@ -29,22 +34,29 @@ void main() {
     switch (gl_VertexID) {
         case 0:
             pos = vec2(left, top);
-            f_tex_pos = tex_left_top;
+            f_tex_pos = vec3(tex_left_top, tex_page);
             break;
         case 1:
             pos = vec2(right, top);
-            f_tex_pos = vec2(tex_right_bottom.x, tex_left_top.y);
+            f_tex_pos = vec3(tex_right_bottom.x, tex_left_top.y, tex_page);
             break;
         case 2:
             pos = vec2(left, bottom);
-            f_tex_pos = vec2(tex_left_top.x, tex_right_bottom.y);
+            f_tex_pos = vec3(tex_left_top.x, tex_right_bottom.y, tex_page);
             break;
         case 3:
             pos = vec2(right, bottom);
-            f_tex_pos = tex_right_bottom;
+            f_tex_pos = vec3(tex_right_bottom, tex_page);
             break;
     }
 
+    mat4 section_transform = mat4(
+        section_transform_0,
+        section_transform_1,
+        section_transform_2,
+        section_transform_3
+    );
+
     f_color = color;
-    gl_Position = INVERT_Y_AXIS * transform * vec4(pos, left_top.z, 1.0);
+    gl_Position = INVERT_Y_AXIS * transform * section_transform * vec4(pos, left_top.z, 1.0);
 }
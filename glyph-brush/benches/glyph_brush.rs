@@ -620,6 +620,7 @@ fn gl_to_vertex(
         pixel_coords,
         bounds,
         extra,
+        ..
     }: glyph_brush::GlyphVertex,
 ) -> [f32; 13] {
     let gl_bounds = bounds;
@@ -329,6 +329,7 @@ pub fn to_vertex(
         pixel_coords,
         bounds,
         extra,
+        ..
     }: glyph_brush::GlyphVertex,
 ) -> Vertex {
     let gl_bounds = bounds;
@@ -0,0 +1,779 @@
+//! Software rendering of queued text without a GPU.
+use crate::{
+    ab_glyph::*, BrushAction, BrushError, Color, Extra, GlyphBrush, GlyphVertex, Rectangle, Sdf,
+    TextureFormat,
+};
+use std::hash::BuildHasher;
+
+/// Pixel format of a [`Canvas`](struct.Canvas.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CanvasFormat {
+    /// 4 bytes per pixel, red, green, blue & alpha. Colors are not premultiplied.
+    Rgba8,
+    /// 1 byte per pixel, alpha only. Text colors contribute their alpha.
+    A8,
+}
+
+impl CanvasFormat {
+    /// Returns the number of bytes per pixel.
+    #[inline]
+    pub fn bytes_per_pixel(self) -> usize {
+        match self {
+            CanvasFormat::Rgba8 => 4,
+            CanvasFormat::A8 => 1,
+        }
+    }
+}
+
+/// An image buffer text is drawn onto by a [`CpuRenderer`](struct.CpuRenderer.html).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Canvas {
+    pub width: u32,
+    pub height: u32,
+    pub format: CanvasFormat,
+    /// Row-major pixel data, from the top-left.
+    pub data: Vec<u8>,
+}
+
+impl Canvas {
+    /// Returns a new fully transparent canvas.
+    pub fn new(format: CanvasFormat, width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            format,
+            data: vec![0; width as usize * height as usize * format.bytes_per_pixel()],
+        }
+    }
+
+    /// Sets every pixel to the `color`. For `A8` canvases only the alpha is used.
+    pub fn fill(&mut self, color: [u8; 4]) {
+        match self.format {
+            CanvasFormat::Rgba8 => {
+                for pixel in self.data.chunks_exact_mut(4) {
+                    pixel.copy_from_slice(&color);
+                }
+            }
+            CanvasFormat::A8 => {
+                for pixel in &mut self.data {
+                    *pixel = color[3];
+                }
+            }
+        }
+    }
+
+    /// Returns the pixel bytes at `(x, y)`, `None` if out of bounds.
+    #[inline]
+    pub fn pixel(&self, x: u32, y: u32) -> Option<&[u8]> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let bpp = self.format.bytes_per_pixel();
+        let idx = (y as usize * self.width as usize + x as usize) * bpp;
+        self.data.get(idx..idx + bpp)
+    }
+
+    /// Blends `color` with `coverage` over the pixel at `(x, y)`.
+    #[inline]
+    fn blend(&mut self, x: u32, y: u32, color: Color, coverage: f32) {
+        let src_a = (color[3] * coverage).min(1.0);
+        if src_a <= 0.0 {
+            return;
+        }
+        let bpp = self.format.bytes_per_pixel();
+        let idx = (y as usize * self.width as usize + x as usize) * bpp;
+
+        match self.format {
+            CanvasFormat::Rgba8 => {
+                let dst = &mut self.data[idx..idx + 4];
+                let dst_a = f32::from(dst[3]) / 255.0;
+                let out_a = src_a + dst_a * (1.0 - src_a);
+                for c in 0..3 {
+                    let src_c = color[c].clamp(0.0, 1.0);
+                    let dst_c = f32::from(dst[c]) / 255.0;
+                    let out_c = (src_c * src_a + dst_c * dst_a * (1.0 - src_a)) / out_a;
+                    dst[c] = to_u8(out_c);
+                }
+                dst[3] = to_u8(out_a);
+            }
+            CanvasFormat::A8 => {
+                let dst_a = f32::from(self.data[idx]) / 255.0;
+                self.data[idx] = to_u8(src_a + dst_a * (1.0 - src_a));
+            }
+        }
+    }
+
+    /// Blends `color` over the pixel at `(x, y)` blending each colour channel with
+    /// the matching subpixel `coverage`.
+    #[inline]
+    fn blend_subpixel(&mut self, x: u32, y: u32, color: Color, coverage: [f32; 3]) {
+        if self.format == CanvasFormat::A8 {
+            let mean = (coverage[0] + coverage[1] + coverage[2]) / 3.0;
+            return self.blend(x, y, color, mean);
+        }
+        if coverage == [0.0; 3] {
+            return;
+        }
+        let idx = (y as usize * self.width as usize + x as usize) * 4;
+        let dst = &mut self.data[idx..idx + 4];
+        let dst_a = f32::from(dst[3]) / 255.0;
+        for c in 0..3 {
+            let src_a = (color[3] * coverage[c]).min(1.0);
+            let out_a = src_a + dst_a * (1.0 - src_a);
+            if out_a > 0.0 {
+                let src_c = color[c].clamp(0.0, 1.0);
+                let dst_c = f32::from(dst[c]) / 255.0;
+                dst[c] = to_u8((src_c * src_a + dst_c * dst_a * (1.0 - src_a)) / out_a);
+            }
+        }
+        let src_a = (color[3] * (coverage[0] + coverage[1] + coverage[2]) / 3.0).min(1.0);
+        dst[3] = to_u8(src_a + dst_a * (1.0 - src_a));
+    }
+}
+
+#[inline]
+fn to_u8(v: f32) -> u8 {
+    (v * 255.0).round().clamp(0.0, 255.0) as u8
+}
+
+/// Vertex of a single glyph quad drawn by a [`CpuRenderer`](struct.CpuRenderer.html).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CpuVertex {
+    /// Normalized texture coordinates of the glyph in the draw cache texture.
+    pub tex_coords: Rect,
+    /// Draw cache texture page of the `tex_coords`.
+    pub tex_page: u32,
+    /// Pixel position of the glyph quad.
+    pub pixel_coords: Rect,
+    /// Section bounds, the quad is clipped to these.
+    pub bounds: Rect,
+    pub color: Color,
+    /// Column-major section transform, only the 2D affine part is used.
+    pub transform: Option<[[f32; 4]; 4]>,
+}
+
+/// Renders text into a [`Canvas`](struct.Canvas.html) using a CPU-side copy of the
+/// `GlyphBrush` draw cache texture. Useful to render text without a GPU, e.g. in CI or
+/// on servers.
+///
+/// Glyph coverage is sampled with nearest filtering & alpha blended using each text's
+/// `Extra::color`, clipped to section bounds. Signed distance field textures, see
+/// [`GlyphBrushBuilder::draw_cache_sdf`](struct.GlyphBrushBuilder.html#method.draw_cache_sdf),
+/// are sampled with bilinear filtering & thresholded. `TextureFormat::LcdRgb8` textures
+/// blend each colour channel with its own coverage.
+///
+/// # Example
+/// ```
+/// use glyph_brush::{ab_glyph::*, *};
+/// # fn main() -> Result<(), BrushError> {
+/// # let dejavu = FontArc::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf")).unwrap();
+/// let mut glyph_brush = GlyphBrushBuilder::using_font(dejavu).build();
+/// let mut renderer = CpuRenderer::new(glyph_brush.texture_dimensions());
+/// let mut canvas = Canvas::new(CanvasFormat::Rgba8, 200, 50);
+/// canvas.fill([255, 255, 255, 255]);
+///
+/// glyph_brush.queue(Section::default().add_text(Text::new("Hello").with_scale(30.0)));
+/// renderer.render(&mut glyph_brush, &mut canvas)?;
+/// # assert!(canvas.data.chunks(4).any(|p| p[0] < 128));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CpuRenderer {
+    texture: Vec<u8>,
+    texture_width: u32,
+    texture_height: u32,
+    texture_pages: u32,
+    vertices: Vec<CpuVertex>,
+    sdf: Option<Sdf>,
+    texture_format: TextureFormat,
+    /// Indicates `render` has rebuilt the brush texture to match this renderer.
+    synced: bool,
+}
+
+impl CpuRenderer {
+    /// Returns a new renderer with a single page texture of the `GlyphBrush::texture_dimensions`.
+    pub fn new((width, height): (u32, u32)) -> Self {
+        Self {
+            texture: vec![0; width as usize * height as usize],
+            texture_width: width,
+            texture_height: height,
+            texture_pages: 1,
+            vertices: vec![],
+            sdf: None,
+            texture_format: TextureFormat::A8,
+            synced: false,
+        }
+    }
+
+    /// Sets the texture signed distance field settings, matching
+    /// `GlyphBrush::draw_cache_sdf`. This is set automatically by [`render`](#method.render).
+    #[inline]
+    pub fn with_sdf(mut self, sdf: Option<Sdf>) -> Self {
+        self.sdf = sdf;
+        self
+    }
+
+    /// Sets the texture pixel format, matching `GlyphBrush::draw_cache_texture_format`.
+    /// This is set automatically by [`render`](#method.render).
+    #[inline]
+    pub fn with_texture_format(mut self, texture_format: TextureFormat) -> Self {
+        self.texture_format = texture_format;
+        self.resize_texture(self.texture_width, self.texture_height);
+        self
+    }
+
+    /// Sets the number of texture pages, matching `GlyphBrush::texture_pages`.
+    /// This is set automatically by [`render`](#method.render).
+    #[inline]
+    pub fn with_texture_pages(mut self, pages: u32) -> Self {
+        self.texture_pages = pages;
+        self.resize_texture(self.texture_width, self.texture_height);
+        self
+    }
+
+    /// Resizes & clears the texture, should be called along with `GlyphBrush::resize_texture`.
+    pub fn resize_texture(&mut self, width: u32, height: u32) {
+        let bpp = self.texture_format.bytes_per_pixel();
+        let pages = self.texture_pages as usize;
+        self.texture = vec![0; width as usize * height as usize * pages * bpp];
+        self.texture_width = width;
+        self.texture_height = height;
+        self.vertices.clear();
+    }
+
+    /// Updates the texture, suitable as the `process_queued_paged` `update_texture` function.
+    pub fn update_texture(&mut self, page: u32, rect: Rectangle<u32>, tex_data: &[u8]) {
+        let bpp = self.texture_format.bytes_per_pixel();
+        let width = rect.width() as usize * bpp;
+        for (row, y) in (rect.min[1]..rect.max[1]).enumerate() {
+            let y = page * self.texture_height + y;
+            let start = (y as usize * self.texture_width as usize + rect.min[0] as usize) * bpp;
+            self.texture[start..start + width]
+                .copy_from_slice(&tex_data[row * width..(row + 1) * width]);
+        }
+    }
+
+    /// Maps `GlyphVertex` data to a `CpuVertex`, suitable as the `process_queued` `to_vertex`
+    /// function.
+    #[inline]
+    pub fn to_vertex(
+        GlyphVertex {
+            tex_coords,
+            tex_page,
+            pixel_coords,
+            bounds,
+            extra,
+            transform,
+            ..
+        }: GlyphVertex<'_, Extra>,
+    ) -> CpuVertex {
+        CpuVertex {
+            tex_coords,
+            tex_page,
+            pixel_coords,
+            bounds,
+            color: extra.color,
+            transform: transform.copied(),
+        }
+    }
+
+    /// Processes the queued sections of the `glyph_brush` & draws them onto the `canvas`,
+    /// resizing the texture as necessary.
+    ///
+    /// The first call rebuilds the brush draw cache, so glyphs cached before this
+    /// renderer was created are uploaded again.
+    pub fn render<F, H>(
+        &mut self,
+        glyph_brush: &mut GlyphBrush<CpuVertex, Extra, F, H>,
+        canvas: &mut Canvas,
+    ) -> Result<(), BrushError>
+    where
+        F: Font + Sync,
+        H: BuildHasher,
+    {
+        let max_dimension = 16384;
+        // minimum `GL_MAX_ARRAY_TEXTURE_LAYERS`
+        let max_pages = 256;
+        self.sdf = glyph_brush.draw_cache_sdf();
+        if !self.synced
+            || self.texture_format != glyph_brush.draw_cache_texture_format()
+            || self.texture_pages != glyph_brush.texture_pages()
+        {
+            self.synced = true;
+            self.texture_format = glyph_brush.draw_cache_texture_format();
+            self.texture_pages = glyph_brush.texture_pages();
+            // re-upload all glyphs to this texture
+            let (width, height) = glyph_brush.texture_dimensions();
+            glyph_brush.resize_texture(width, height);
+            self.resize_texture(width, height);
+        }
+        loop {
+            let action = glyph_brush.process_queued_paged(
+                |page, rect, tex_data| self.update_texture(page, rect, tex_data),
+                Self::to_vertex,
+            );
+            match action {
+                Ok(BrushAction::Draw(vertices)) => self.vertices = vertices,
+                Ok(BrushAction::ReDraw) => {}
+                Err(BrushError::TextureTooSmall {
+                    suggested_pages, ..
+                }) if suggested_pages > self.texture_pages && suggested_pages <= max_pages => {
+                    glyph_brush.resize_texture_pages(suggested_pages);
+                    self.texture_pages = suggested_pages;
+                    let (width, height) = glyph_brush.texture_dimensions();
+                    self.resize_texture(width, height);
+                    continue;
+                }
+                Err(BrushError::TextureTooSmall {
+                    suggested,
+                    suggested_pages,
+                }) if suggested_pages == self.texture_pages
+                    && suggested.0 <= max_dimension
+                    && suggested.1 <= max_dimension =>
+                {
+                    glyph_brush.resize_texture(suggested.0, suggested.1);
+                    self.resize_texture(suggested.0, suggested.1);
+                    continue;
+                }
+                Err(err) => return Err(err),
+            }
+            break;
+        }
+
+        self.draw(&self.vertices, canvas);
+        Ok(())
+    }
+
+    /// Draws the `vertices` onto the `canvas`.
+    pub fn draw(&self, vertices: &[CpuVertex], canvas: &mut Canvas) {
+        for vertex in vertices {
+            self.draw_vertex(vertex, canvas);
+        }
+    }
+
+    fn draw_vertex(&self, vertex: &CpuVertex, canvas: &mut Canvas) {
+        let CpuVertex {
+            tex_coords,
+            tex_page,
+            pixel_coords,
+            bounds,
+            color,
+            transform,
+        } = *vertex;
+
+        // quad in untransformed pixel space, clipped to the section bounds
+        let quad = Rect {
+            min: point(
+                pixel_coords.min.x.max(bounds.min.x),
+                pixel_coords.min.y.max(bounds.min.y),
+            ),
+            max: point(
+                pixel_coords.max.x.min(bounds.max.x),
+                pixel_coords.max.y.min(bounds.max.y),
+            ),
+        };
+        if quad.width() <= 0.0 || quad.height() <= 0.0 {
+            return;
+        }
+
+        let affine = transform.map(Affine::from);
+        let inverse = match affine.map(|a| a.inverse()) {
+            Some(None) => return,
+            Some(inverse) => inverse,
+            None => None,
+        };
+
+        // canvas area covered by the quad
+        let covered = match affine {
+            Some(affine) => {
+                let corners = [
+                    affine.apply(quad.min),
+                    affine.apply(point(quad.max.x, quad.min.y)),
+                    affine.apply(point(quad.min.x, quad.max.y)),
+                    affine.apply(quad.max),
+                ];
+                corners.iter().skip(1).fold(
+                    Rect {
+                        min: corners[0],
+                        max: corners[0],
+                    },
+                    |r, c| Rect {
+                        min: point(r.min.x.min(c.x), r.min.y.min(c.y)),
+                        max: point(r.max.x.max(c.x), r.max.y.max(c.y)),
+                    },
+                )
+            }
+            None => quad,
+        };
+        let x_range = covered.min.x.floor().max(0.0) as u32
+            ..(covered.max.x.ceil().max(0.0) as u32).min(canvas.width);
+        let y_range = covered.min.y.floor().max(0.0) as u32
+            ..(covered.max.y.ceil().max(0.0) as u32).min(canvas.height);
+
+        let (tex_w, tex_h) = (self.texture_width as f32, self.texture_height as f32);
+        let texel_scale_x = tex_coords.width() * tex_w / pixel_coords.width();
+        let texel_scale_y = tex_coords.height() * tex_h / pixel_coords.height();
+        // distance field texel value change per canvas pixel
+        let sdf_factor = self.sdf.map(|sdf| {
+            let transform_scale = affine.map_or(1.0, |a| (a.a * a.d - a.b * a.c).abs().sqrt());
+            2.0 * sdf.spread * transform_scale / ((texel_scale_x + texel_scale_y) / 2.0)
+        });
+
+        for y in y_range {
+            for x in x_range.clone() {
+                let center = point(x as f32 + 0.5, y as f32 + 0.5);
+                let local = match &inverse {
+                    Some(inverse) => inverse.apply(center),
+                    None => center,
+                };
+                if local.x < quad.min.x
+                    || local.x >= quad.max.x
+                    || local.y < quad.min.y
+                    || local.y >= quad.max.y
+                {
+                    continue;
+                }
+
+                let tex_x =
+                    tex_coords.min.x * tex_w + (local.x - pixel_coords.min.x) * texel_scale_x;
+                let tex_y =
+                    tex_coords.min.y * tex_h + (local.y - pixel_coords.min.y) * texel_scale_y;
+                if self.texture_format == TextureFormat::LcdRgb8 {
+                    let rgb = self.texel_rgb(tex_page, tex_x as u32, tex_y as u32);
+                    canvas.blend_subpixel(x, y, color, rgb.map(|v| f32::from(v) / 255.0));
+                    continue;
+                }
+
+                let coverage = match sdf_factor {
+                    Some(factor) => {
+                        let distance = self.sample_bilinear(tex_page, tex_x, tex_y);
+                        ((distance - 0.5) * factor + 0.5).clamp(0.0, 1.0)
+                    }
+                    None => f32::from(self.texel(tex_page, tex_x as u32, tex_y as u32)) / 255.0,
+                };
+
+                canvas.blend(x, y, color, coverage);
+            }
+        }
+    }
+
+    /// Returns the texel index of `(x, y)` on the `page`, clamped to the page edges.
+    #[inline]
+    fn texel_index(&self, page: u32, x: u32, y: u32) -> usize {
+        let x = x.min(self.texture_width.saturating_sub(1));
+        let y = page * self.texture_height + y.min(self.texture_height.saturating_sub(1));
+        y as usize * self.texture_width as usize + x as usize
+    }
+
+    #[inline]
+    fn texel(&self, page: u32, x: u32, y: u32) -> u8 {
+        self.texture[self.texel_index(page, x, y)]
+    }
+
+    /// Returns the red, green & blue coverage of a `TextureFormat::LcdRgb8` texel.
+    #[inline]
+    fn texel_rgb(&self, page: u32, x: u32, y: u32) -> [u8; 3] {
+        let idx = self.texel_index(page, x, y) * 3;
+        [
+            self.texture[idx],
+            self.texture[idx + 1],
+            self.texture[idx + 2],
+        ]
+    }
+
+    /// Returns the `[0, 1]` texture value interpolated between the 4 nearest texel centres.
+    #[inline]
+    fn sample_bilinear(&self, page: u32, x: f32, y: f32) -> f32 {
+        let (x, y) = ((x - 0.5).max(0.0), (y - 0.5).max(0.0));
+        let (x0, y0) = (x as u32, y as u32);
+        let (fx, fy) = (x.fract(), y.fract());
+        let v = |x, y| f32::from(self.texel(page, x, y));
+
+        let top = v(x0, y0) * (1.0 - fx) + v(x0 + 1, y0) * fx;
+        let bottom = v(x0, y0 + 1) * (1.0 - fx) + v(x0 + 1, y0 + 1) * fx;
+        (top * (1.0 - fy) + bottom * fy) / 255.0
+    }
+}
+
+/// 2D affine part of a column-major 4x4 matrix.
+#[derive(Debug, Clone, Copy)]
+struct Affine {
+    a: f32,
+    b: f32,
+    c: f32,
+    d: f32,
+    tx: f32,
+    ty: f32,
+}
+
+impl From<[[f32; 4]; 4]> for Affine {
+    #[inline]
+    fn from(m: [[f32; 4]; 4]) -> Self {
+        Self {
+            a: m[0][0],
+            b: m[0][1],
+            c: m[1][0],
+            d: m[1][1],
+            tx: m[3][0],
+            ty: m[3][1],
+        }
+    }
+}
+
+impl Affine {
+    #[inline]
+    fn apply(&self, p: Point) -> Point {
+        point(
+            self.a * p.x + self.c * p.y + self.tx,
+            self.b * p.x + self.d * p.y + self.ty,
+        )
+    }
+
+    fn inverse(&self) -> Option<Self> {
+        let det = self.a * self.d - self.b * self.c;
+        if det.abs() < f32::EPSILON {
+            return None;
+        }
+        let (a, b, c, d) = (self.d / det, -self.b / det, -self.c / det, self.a / det);
+        Some(Self {
+            a,
+            b,
+            c,
+            d,
+            tx: -(a * self.tx + c * self.ty),
+            ty: -(b * self.tx + d * self.ty),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::*;
+
+    fn brush() -> GlyphBrush<CpuVertex, Extra, FontRef<'static>> {
+        let font = FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf")).unwrap();
+        GlyphBrushBuilder::using_font(font)
+            .initial_cache_size((16, 16))
+            .build()
+    }
+
+    /// Returns the rect of pixels with any alpha.
+    fn inked_bounds(canvas: &Canvas) -> Option<(u32, u32, u32, u32)> {
+        let mut bounds: Option<(u32, u32, u32, u32)> = None;
+        for y in 0..canvas.height {
+            for x in 0..canvas.width {
+                if canvas.pixel(x, y).unwrap().last() != Some(&0) {
+                    bounds = Some(match bounds {
+                        None => (x, y, x, y),
+                        Some((x0, y0, x1, y1)) => (x0.min(x), y0.min(y), x1.max(x), y1.max(y)),
+                    });
+                }
+            }
+        }
+        bounds
+    }
+
+    #[test]
+    fn render_rgba_colors_and_clips() {
+        let mut brush = brush();
+        let mut renderer = CpuRenderer::new(brush.texture_dimensions());
+        let mut canvas = Canvas::new(CanvasFormat::Rgba8, 100, 40);
+
+        brush.queue(
+            Section::default()
+                .with_screen_position((5.0, 5.0))
+                .with_bounds((30.0, f32::INFINITY))
+                .add_text(
+                    Text::new("IIIIIIIIII")
+                        .with_scale(24.0)
+                        .with_color([1.0, 0.0, 0.0, 1.0]),
+                ),
+        );
+        // texture grows from 16x16
+        renderer.render(&mut brush, &mut canvas).unwrap();
+        assert!(brush.texture_dimensions().0 > 16);
+
+        let (min_x, min_y, max_x, _) = inked_bounds(&canvas).expect("no text drawn");
+        assert!(min_x >= 5 && min_y >= 5);
+        // clipped to the bounds
+        assert!(max_x < 35, "{}", max_x);
+
+        // solid red in the middle of the first 'I' stroke
+        let solid = (0..canvas.height)
+            .flat_map(|y| (0..canvas.width).map(move |x| (x, y)))
+            .filter_map(|(x, y)| canvas.pixel(x, y))
+            .find(|p| p[3] == 255)
+            .expect("no opaque pixel");
+        assert_eq!(solid, &[255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn render_glyphs_cached_before_renderer() {
+        let mut brush = brush();
+        let section = Section::default().add_text(Text::new("Hello").with_scale(24.0));
+
+        // cache glyphs without a renderer
+        brush.queue(section.clone());
+        while let Err(BrushError::TextureTooSmall { suggested, .. }) =
+            brush.process_queued(|_, _| {}, CpuRenderer::to_vertex)
+        {
+            brush.resize_texture(suggested.0, suggested.1);
+        }
+
+        let mut renderer = CpuRenderer::new(brush.texture_dimensions());
+        let mut canvas = Canvas::new(CanvasFormat::Rgba8, 100, 40);
+        brush.queue(section);
+        renderer.render(&mut brush, &mut canvas).unwrap();
+        assert!(inked_bounds(&canvas).is_some(), "no text drawn");
+    }
+
+    #[test]
+    fn render_a8_transformed() {
+        let mut brush = brush();
+        let mut renderer = CpuRenderer::new(brush.texture_dimensions());
+        let text = || Text::new("Hello").with_scale(20.0);
+
+        let mut plain = Canvas::new(CanvasFormat::A8, 100, 100);
+        brush.queue(Section::default().add_text(text()));
+        renderer.render(&mut brush, &mut plain).unwrap();
+
+        // translated by (20, 30)
+        let mut moved = Canvas::new(CanvasFormat::A8, 100, 100);
+        brush.queue(Section::default().add_text(text()).with_transform([
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [20.0, 30.0, 0.0, 1.0],
+        ]));
+        renderer.render(&mut brush, &mut moved).unwrap();
+
+        let (x0, y0, x1, y1) = inked_bounds(&plain).unwrap();
+        assert_eq!(
+            inked_bounds(&moved),
+            Some((x0 + 20, y0 + 30, x1 + 20, y1 + 30))
+        );
+        assert_eq!(plain.pixel(x0, y0), moved.pixel(x0 + 20, y0 + 30));
+    }
+
+    #[test]
+    fn render_sdf_similar_to_coverage() {
+        let font = FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf")).unwrap();
+        let mut coverage_brush = GlyphBrushBuilder::using_font(font.clone()).build();
+        let mut sdf_brush = GlyphBrushBuilder::using_font(font)
+            .draw_cache_sdf(Sdf::default())
+            .build();
+        let mut coverage_renderer = CpuRenderer::new(coverage_brush.texture_dimensions());
+        let mut sdf_renderer = CpuRenderer::new(sdf_brush.texture_dimensions());
+
+        for scale in [14.0, 40.0] {
+            let section = Section::default()
+                .with_screen_position((3.0, 3.0))
+                .add_text(Text::new("Ready?").with_scale(scale));
+
+            let mut coverage = Canvas::new(CanvasFormat::A8, 160, 60);
+            coverage_brush.queue(&section);
+            coverage_renderer
+                .render(&mut coverage_brush, &mut coverage)
+                .unwrap();
+
+            let mut sdf = Canvas::new(CanvasFormat::A8, 160, 60);
+            sdf_brush.queue(&section);
+            sdf_renderer.render(&mut sdf_brush, &mut sdf).unwrap();
+
+            let (x0, y0, x1, y1) = inked_bounds(&coverage).unwrap();
+            let (sx0, sy0, sx1, sy1) = inked_bounds(&sdf).unwrap();
+            for (a, b) in [(x0, sx0), (y0, sy0), (x1, sx1), (y1, sy1)] {
+                assert!(a.max(b) - a.min(b) <= 1, "{:?}", (scale, a, b));
+            }
+
+            let ink = |c: &Canvas| c.data.iter().map(|&v| u32::from(v)).sum::<u32>() as f32;
+            let ratio = ink(&sdf) / ink(&coverage);
+            assert!((0.95..1.05).contains(&ratio), "{:?}", (scale, ratio));
+        }
+    }
+
+    #[test]
+    fn render_lcd_rgb_per_channel() {
+        let font = FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf")).unwrap();
+        let mut a8_brush = GlyphBrushBuilder::using_font(font.clone()).build();
+        let mut lcd_brush = GlyphBrushBuilder::using_font(font)
+            .draw_cache_texture_format(TextureFormat::LcdRgb8)
+            .build();
+        let section = Section::default()
+            .with_screen_position((3.0, 3.0))
+            .add_text(Text::new("Ready?").with_scale(16.0));
+
+        let render = |brush: &mut GlyphBrush<_, _, _>| {
+            let mut canvas = Canvas::new(CanvasFormat::Rgba8, 80, 24);
+            canvas.fill([255, 255, 255, 255]);
+            brush.queue(&section);
+            CpuRenderer::new(brush.texture_dimensions())
+                .render(brush, &mut canvas)
+                .unwrap();
+            canvas
+        };
+        let a8 = render(&mut a8_brush);
+        let lcd = render(&mut lcd_brush);
+
+        // each channel darkened about as much as grayscale text
+        let ink = |c: &Canvas, channel| {
+            c.data
+                .chunks(4)
+                .map(|p| 255 - u32::from(p[channel]))
+                .sum::<u32>() as f32
+        };
+        for channel in 0..3 {
+            let ratio = ink(&lcd, channel) / ink(&a8, channel);
+            assert!((0.9..1.1).contains(&ratio), "{:?}", (channel, ratio));
+        }
+        // with colour fringes
+        assert!(lcd.data.chunks(4).any(|p| p[0] != p[2]));
+        assert!(a8.data.chunks(4).all(|p| p[0] == p[2]));
+    }
+
+    #[test]
+    fn render_multiple_pages() {
+        let font = FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf")).unwrap();
+        let mut single_brush = GlyphBrushBuilder::using_font(font.clone())
+            .initial_cache_size((256, 256))
+            .build();
+        let mut paged_brush = GlyphBrushBuilder::using_font(font)
+            .initial_cache_size((32, 32))
+            .initial_cache_pages(8)
+            .build();
+        let section = Section::default()
+            .with_screen_position((4.0, 4.0))
+            .add_text(Text::new("Hello pages").with_scale(20.0));
+
+        let render = |brush: &mut GlyphBrush<_, _, _>, section: &Section<'_>| {
+            let mut canvas = Canvas::new(CanvasFormat::A8, 160, 40);
+            brush.queue(section);
+            CpuRenderer::new(brush.texture_dimensions())
+                .render(brush, &mut canvas)
+                .unwrap();
+            canvas
+        };
+        let single = render(&mut single_brush, &section);
+        let paged = render(&mut paged_brush, &section);
+
+        // glyphs spread over pages rather than growing the texture
+        assert_eq!(paged_brush.texture_dimensions(), (32, 32));
+        assert!(single.data.iter().any(|&a| a > 0));
+        assert_eq!(single, paged);
+
+        // full multi-page caches add pages
+        let mut growing_brush = GlyphBrushBuilder::using_font(paged_brush.fonts()[0].clone())
+            .initial_cache_size((32, 32))
+            .initial_cache_pages(2)
+            .build();
+        let section = Section::default()
+            .with_screen_position((4.0, 4.0))
+            .add_text(Text::new("ABCDEFGHIJ").with_scale(20.0));
+        let grown = render(&mut growing_brush, &section);
+        assert_eq!(growing_brush.texture_dimensions(), (32, 32));
+        assert!(growing_brush.texture_pages() > 2);
+        assert_eq!(render(&mut single_brush, &section), grown);
+    }
+}
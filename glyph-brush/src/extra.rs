@@ -4,6 +4,8 @@ use std::hash::{Hash, Hasher};
 
 /// Default `extra` field type. Non-layout data for vertex generation.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Extra {
     pub color: Color,
     pub z: f32,
@@ -1,6 +1,7 @@
 mod builder;
+mod stats;
 
-pub use self::builder::*;
+pub use self::{builder::*, stats::*};
 
 use super::*;
 use glyph_brush_draw_cache::*;
@@ -50,7 +51,8 @@ type SectionHash = u64;
 ///
 /// This behaviour can be adjusted with [`GlyphBrushBuilder::draw_cache_position_tolerance`].
 pub struct GlyphBrush<V, X = Extra, F = FontArc, H = DefaultSectionHasher> {
-    fonts: Vec<F>,
+    // user fonts followed by font instances created for text variations
+    fonts: FontInstances<F>,
     texture_cache: DrawCache,
     last_draw: LastDrawInfo,
 
@@ -76,6 +78,21 @@ pub struct GlyphBrush<V, X = Extra, F = FontArc, H = DefaultSectionHasher> {
 
     last_pre_positioned: Vec<Glyphed<V, X>>,
     pre_positioned: Vec<Glyphed<V, X>>,
+
+    // measured words shared by all section layouts
+    word_cache: WordCache,
+
+    // queued sections with layouts deferred until `process_queued_parallel`
+    pending_layouts: FxHashMap<SectionHash, PendingLayout<X>>,
+    // whether the last `process_queued*` call calculated layouts in parallel,
+    // if so `queue` defers layouts expecting the next call to do the same
+    parallel_layout: bool,
+
+    // layout cache lookups since the last `process_queued`
+    layout_cache_hits: usize,
+    layout_cache_misses: usize,
+    // stats of the latest `process_queued`
+    stats: GlyphBrushStats,
 }
 
 impl<F, V, X, H> fmt::Debug for GlyphBrush<V, X, F, H> {
@@ -87,7 +104,7 @@ impl<F, V, X, H> fmt::Debug for GlyphBrush<V, X, F, H> {
 impl<F, V, X, H> GlyphCruncher<F, X> for GlyphBrush<V, X, F, H>
 where
     X: Clone + Hash,
-    F: Font,
+    F: VariableFontInstance,
     V: Clone + 'static,
     H: BuildHasher,
 {
@@ -127,7 +144,7 @@ where
             .positioned
             .glyphs()
             .fold(None, |b: Option<Rect>, sg| {
-                let bounds = self.fonts[sg.font_id.0].glyph_bounds(&sg.glyph);
+                let bounds = self.font(sg.font_id).glyph_bounds(&sg.glyph);
                 b.map(|b| {
                     let min_x = b.min.x.min(bounds.min.x);
                     let max_x = b.max.x.max(bounds.max.x);
@@ -151,9 +168,38 @@ where
             })
     }
 
+    fn layout_bounds_custom_layout<'a, S, L>(
+        &mut self,
+        section: S,
+        custom_layout: &L,
+    ) -> Option<Rect>
+    where
+        X: 'a,
+        L: GlyphPositioner + Hash,
+        S: Into<Cow<'a, Section<'a, X>>>,
+    {
+        let section = section.into();
+        let with_final_line = section.with_final_line()?;
+        let section_hash = self.cache_glyphs(&with_final_line, custom_layout);
+        self.keep_in_cache.insert(section_hash);
+        match self.calculate_glyph_cache[&section_hash]
+            .positioned
+            .layout_bounds()
+        {
+            Some(bounds) => Some(bounds),
+            // layout without lines
+            None => self.glyph_bounds_custom_layout(section, custom_layout),
+        }
+    }
+
     #[inline]
     fn fonts(&self) -> &[F] {
-        &self.fonts
+        self.fonts.user_fonts()
+    }
+
+    #[inline]
+    fn font(&self, font_id: FontId) -> &F {
+        self.fonts.font(&[], font_id)
     }
 }
 
@@ -162,14 +208,38 @@ impl<F, V, X, H: BuildHasher> GlyphBrush<V, X, F, H> {
     ///
     /// Returns a new [`FontId`](struct.FontId.html) to reference this font.
     pub fn add_font<I: Into<F>>(&mut self, font_data: I) -> FontId {
-        self.fonts.push(font_data.into());
-        FontId(self.fonts.len() - 1)
+        let (font_id, remap) = self.fonts.add_user_font(font_data.into());
+        // font instance ids follow the user fonts so move along one
+        if let Some(remap) = remap {
+            self.remap_font_ids(&remap);
+        }
+        font_id
+    }
+
+    /// Updates the font ids of cached & pending layouts after font instances have moved.
+    fn remap_font_ids(&mut self, remap: &FontIdRemap) {
+        for glyphed in self.calculate_glyph_cache.values_mut() {
+            glyphed.remap_font_ids(remap);
+        }
+        for pending in self.pending_layouts.values_mut() {
+            pending.remap_font_ids(remap);
+        }
+        // cached words & glyphs of moved instances are keyed by the old ids
+        self.evict_fonts(|font_id| remap.get(font_id) != font_id);
+        self.last_draw = LastDrawInfo::default();
+    }
+
+    /// Removes cached words & glyphs of fonts for which `evict` returns `true`.
+    fn evict_fonts(&mut self, evict: impl Fn(FontId) -> bool) {
+        self.word_cache.retain_fonts(|font_id| !evict(font_id));
+        self.texture_cache
+            .retain_fonts(|font_id| !evict(FontId(font_id)));
     }
 }
 
 impl<F, V, X, H> GlyphBrush<V, X, F, H>
 where
-    F: Font,
+    F: VariableFontInstance,
     X: Clone + Hash,
     V: Clone + 'static,
     H: BuildHasher,
@@ -191,7 +261,10 @@ where
         let section = section.into();
         if cfg!(debug_assertions) {
             for text in &section.text {
-                assert!(self.fonts.len() > text.font_id.0, "Invalid font id");
+                assert!(
+                    self.fonts.user_fonts().len() > text.font_id.0,
+                    "Invalid font id"
+                );
             }
         }
         let section_hash = self.cache_glyphs(&section, custom_layout);
@@ -205,6 +278,10 @@ where
     ///
     /// Benefits from caching, see [caching behaviour](#caching-behaviour).
     ///
+    /// After a [`process_queued_parallel`](#method.process_queued_parallel) call that used
+    /// multiple threads the layout of uncached sections is deferred to the next call &
+    /// calculated in parallel.
+    ///
     /// ```no_run
     /// # use glyph_brush::{ab_glyph::*, *};
     /// # let font: FontArc = unimplemented!();
@@ -218,18 +295,13 @@ where
     {
         let section = section.into();
         let layout = section.layout;
-        self.queue_custom_layout(section, &layout)
-    }
-
-    /// Queues pre-positioned glyphs to be processed by the next call of
-    /// [`process_queued`](struct.GlyphBrush.html#method.process_queued). Can be called multiple
-    /// times.
-    pub fn queue_pre_positioned(&mut self, glyphs: Vec<SectionGlyph>, extra: Vec<X>, bounds: Rect) {
-        self.pre_positioned.push(Glyphed::new(GlyphedSection {
-            bounds,
-            glyphs,
-            extra,
-        }));
+        if self.cache_glyph_positioning && self.parallel_layout {
+            let section_hash = self.defer_glyphs(&section, layout);
+            self.section_buffer.push(section_hash);
+            self.keep_in_cache.insert(section_hash);
+        } else {
+            self.queue_custom_layout(section, &layout)
+        }
     }
 
     /// Returns the calculate_glyph_cache key for this sections glyphs
@@ -244,72 +316,192 @@ where
         self.frame_seq_id_sections.push(section_hash);
 
         if self.cache_glyph_positioning {
-            if !self.calculate_glyph_cache.contains_key(&section_hash.full) {
+            if self.calculate_glyph_cache.contains_key(&section_hash.full) {
+                self.layout_cache_hits += 1;
+            } else {
+                self.layout_cache_misses += 1;
                 let geometry = SectionGeometry::from(section);
-
-                let recalculated_glyphs = self
-                    .last_frame_seq_id_sections
-                    .get(frame_seq_id)
-                    .cloned()
-                    .and_then(|hash| {
-                        let change = hash.layout_diff(section_hash);
-                        if let Some(GlyphChange::Unknown) = change {
-                            return None;
-                        }
-
-                        if self.keep_in_cache.contains(&hash.full) {
-                            let cached = self.calculate_glyph_cache.get(&hash.full)?;
-                            match change {
-                                None => Some(cached.positioned.glyphs.clone()),
-                                Some(change) => Some(layout.recalculate_glyphs(
-                                    cached.positioned.glyphs.iter().cloned(),
-                                    change,
-                                    &self.fonts,
-                                    &geometry,
-                                    &section.text,
-                                )),
-                            }
-                        } else {
-                            let old = self.calculate_glyph_cache.remove(&hash.full)?;
-                            match change {
-                                None => Some(old.positioned.glyphs),
-                                Some(change) => Some(layout.recalculate_glyphs(
-                                    old.positioned.glyphs.into_iter(),
-                                    change,
-                                    &self.fonts,
-                                    &geometry,
-                                    &section.text,
-                                )),
-                            }
-                        }
-                    });
+                let section_text = self.section_text(section);
+
+                let (glyphs, lines) = match self.previous_glyphs(frame_seq_id, section_hash) {
+                    Some((glyphs, lines, None)) => (glyphs, lines),
+                    Some((glyphs, lines, Some(change))) => layout.recalculate_glyphs_and_lines(
+                        glyphs,
+                        lines,
+                        change,
+                        self.fonts.fonts(),
+                        &geometry,
+                        &section_text,
+                        &mut self.word_cache,
+                    ),
+                    None => layout.calculate_glyphs_and_lines(
+                        self.fonts.fonts(),
+                        &geometry,
+                        &section_text,
+                        &mut self.word_cache,
+                    ),
+                };
 
                 self.calculate_glyph_cache.insert(
                     section_hash.full,
                     Glyphed::new(GlyphedSection {
                         bounds: layout.bounds_rect(&geometry),
-                        glyphs: recalculated_glyphs.unwrap_or_else(|| {
-                            layout.calculate_glyphs(&self.fonts, &geometry, &section.text)
-                        }),
+                        glyphs,
+                        lines,
                         extra: section.clone_extras(),
+                        styles: section.synthetic_styles(),
+                        transform: section.transform,
                     }),
                 );
             }
         } else {
+            self.layout_cache_misses += 1;
             let geometry = SectionGeometry::from(section);
-            let glyphs = layout.calculate_glyphs(&self.fonts, &geometry, &section.text);
+            let section_text = self.section_text(section);
+            let (glyphs, lines) = layout.calculate_glyphs_and_lines(
+                self.fonts.fonts(),
+                &geometry,
+                &section_text,
+                &mut self.word_cache,
+            );
             self.calculate_glyph_cache.insert(
                 section_hash.full,
                 Glyphed::new(GlyphedSection {
                     bounds: layout.bounds_rect(&geometry),
                     glyphs,
+                    lines,
                     extra: section.text.iter().map(|s| s.extra.clone()).collect(),
+                    styles: section.synthetic_styles(),
+                    transform: section.transform,
                 }),
             );
         }
         section_hash.full
     }
 
+    /// Like `cache_glyphs` but defers layout calculation of uncached sections to the next
+    /// `process_queued_parallel` call, so multiple section layouts can be calculated in parallel.
+    fn defer_glyphs(
+        &mut self,
+        section: &Section<'_, X>,
+        layout: Layout<BuiltInLineBreaker>,
+    ) -> SectionHash {
+        if cfg!(debug_assertions) {
+            for text in &section.text {
+                assert!(
+                    self.fonts.user_fonts().len() > text.font_id.0,
+                    "Invalid font id"
+                );
+            }
+        }
+
+        let section_hash = SectionHashDetail::new(&self.section_hasher, section, &layout);
+        let frame_seq_id = self.frame_seq_id_sections.len();
+        self.frame_seq_id_sections.push(section_hash);
+
+        if self.calculate_glyph_cache.contains_key(&section_hash.full)
+            || self.pending_layouts.contains_key(&section_hash.full)
+        {
+            self.layout_cache_hits += 1;
+            return section_hash.full;
+        }
+        self.layout_cache_misses += 1;
+
+        let geometry = SectionGeometry::from(section);
+        let previous = match self.previous_glyphs(frame_seq_id, section_hash) {
+            Some((glyphs, lines, None)) => {
+                // no layout required
+                self.calculate_glyph_cache.insert(
+                    section_hash.full,
+                    Glyphed::new(GlyphedSection {
+                        bounds: layout.bounds_rect(&geometry),
+                        glyphs,
+                        lines,
+                        extra: section.clone_extras(),
+                        styles: section.synthetic_styles(),
+                        transform: section.transform,
+                    }),
+                );
+                return section_hash.full;
+            }
+            Some((glyphs, lines, Some(change))) => Some((glyphs, lines, change)),
+            None => None,
+        };
+
+        let text = self
+            .section_text(section)
+            .iter()
+            .map(PendingText::from)
+            .collect();
+        self.pending_layouts.insert(
+            section_hash.full,
+            PendingLayout {
+                layout,
+                geometry,
+                text,
+                previous,
+                extra: section.clone_extras(),
+                styles: section.synthetic_styles(),
+                transform: section.transform,
+            },
+        );
+        section_hash.full
+    }
+
+    /// Returns the glyphs & lines of the section queued at the same position last frame, if it
+    /// had the same text & layout, along with the geometry change required to reuse them.
+    #[allow(clippy::type_complexity)]
+    fn previous_glyphs(
+        &mut self,
+        frame_seq_id: usize,
+        section_hash: SectionHashDetail,
+    ) -> Option<(Vec<SectionGlyph>, Vec<SectionLine>, Option<GlyphChange>)> {
+        let hash = self.last_frame_seq_id_sections.get(frame_seq_id).cloned()?;
+        let change = hash.layout_diff(section_hash);
+        if let Some(GlyphChange::Unknown) = change {
+            return None;
+        }
+
+        let positioned = if self.keep_in_cache.contains(&hash.full) {
+            let cached = self.calculate_glyph_cache.get(&hash.full)?;
+            (
+                cached.positioned.glyphs.clone(),
+                cached.positioned.lines.clone(),
+            )
+        } else {
+            let old = self.calculate_glyph_cache.remove(&hash.full)?;
+            (old.positioned.glyphs, old.positioned.lines)
+        };
+        Some((positioned.0, positioned.1, change))
+    }
+
+    /// Returns layout section text using font instances for any text variations.
+    fn section_text<'a>(&mut self, section: &'a Section<'a, X>) -> Vec<SectionText<'a>> {
+        self.fonts.section_text(&[], &section.text)
+    }
+}
+
+impl<F, V, X, H> GlyphBrush<V, X, F, H>
+where
+    F: Font,
+    X: Clone + Hash,
+    V: Clone + 'static,
+    H: BuildHasher,
+{
+    /// Queues pre-positioned glyphs to be processed by the next call of
+    /// [`process_queued`](struct.GlyphBrush.html#method.process_queued). Can be called multiple
+    /// times.
+    pub fn queue_pre_positioned(&mut self, glyphs: Vec<SectionGlyph>, extra: Vec<X>, bounds: Rect) {
+        self.pre_positioned.push(Glyphed::new(GlyphedSection {
+            bounds,
+            glyphs,
+            lines: vec![],
+            extra,
+            styles: vec![],
+            transform: None,
+        }));
+    }
+
     /// Rebuilds the logical texture cache with new dimensions. Should be avoided if possible.
     ///
     /// # Example
@@ -334,11 +526,121 @@ where
         }
     }
 
+    /// Rebuilds the logical texture cache with a new number of pages, e.g. after adding
+    /// a layer to a texture array. Useful when the texture dimensions cannot be increased.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use glyph_brush::{ab_glyph::*, *};
+    /// # let dejavu = FontArc::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf")).unwrap();
+    /// # let mut glyph_brush: GlyphBrush<()> = GlyphBrushBuilder::using_font(dejavu).build();
+    /// glyph_brush.resize_texture_pages(glyph_brush.texture_pages() + 1);
+    /// ```
+    pub fn resize_texture_pages(&mut self, new_pages: u32) {
+        self.texture_cache
+            .to_builder()
+            .pages(new_pages)
+            .rebuild(&mut self.texture_cache);
+
+        self.last_draw = LastDrawInfo::default();
+
+        for glyphed in self.calculate_glyph_cache.values_mut() {
+            glyphed.invalidate_texture_positions();
+        }
+    }
+
+    /// Discards the vertices generated by previous
+    /// [`process_queued`](#method.process_queued) calls, so the next call generates vertices
+    /// for all queued sections with its `to_vertex` function. Section layouts are still reused.
+    ///
+    /// Useful to animate glyphs using the [`GlyphVertex`](struct.GlyphVertex.html) context,
+    /// e.g. a per-character wave effect changing every frame.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use glyph_brush::{ab_glyph::*, *};
+    /// # fn main() -> Result<(), BrushError> {
+    /// # let dejavu = FontArc::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf")).unwrap();
+    /// # let mut glyph_brush: GlyphBrush<Rect> = GlyphBrushBuilder::using_font(dejavu).build();
+    /// # let time = 0.0_f32;
+    /// glyph_brush.queue(Section::default().add_text(Text::new("Wavy text")));
+    ///
+    /// glyph_brush.regenerate_vertices();
+    /// glyph_brush.process_queued(
+    ///     |_rect, _tex_data| {},
+    ///     |vertex| {
+    ///         let mut rect = vertex.pixel_coords;
+    ///         let dy = (time + vertex.glyph_index as f32 * 0.5).sin() * 3.0;
+    ///         rect.min.y += dy;
+    ///         rect.max.y += dy;
+    ///         rect
+    ///     },
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn regenerate_vertices(&mut self) {
+        self.last_draw = LastDrawInfo::default();
+
+        for glyphed in self.calculate_glyph_cache.values_mut() {
+            glyphed.invalidate_texture_positions();
+        }
+    }
+
     /// Returns the logical texture cache pixel dimensions `(width, height)`.
     pub fn texture_dimensions(&self) -> (u32, u32) {
         self.texture_cache.dimensions()
     }
 
+    /// Returns the number of logical texture cache pages.
+    pub fn texture_pages(&self) -> u32 {
+        self.texture_cache.pages()
+    }
+
+    /// Returns the signed distance field settings if the texture cache contains
+    /// distance fields rather than coverage.
+    ///
+    /// See [`GlyphBrushBuilder::draw_cache_sdf`](struct.GlyphBrushBuilder.html#method.draw_cache_sdf).
+    pub fn draw_cache_sdf(&self) -> Option<Sdf> {
+        self.texture_cache.sdf()
+    }
+
+    /// Returns the pixel format of the texture cache.
+    ///
+    /// See [`GlyphBrushBuilder::draw_cache_texture_format`](struct.GlyphBrushBuilder.html#method.draw_cache_texture_format).
+    pub fn draw_cache_texture_format(&self) -> TextureFormat {
+        self.texture_cache.texture_format()
+    }
+
+    /// Returns layout & draw cache statistics, useful to find the cause of slow frames,
+    /// e.g. a draw cache reorder re-rasterizing all glyphs.
+    ///
+    /// See [`GlyphBrushStats`](struct.GlyphBrushStats.html).
+    pub fn stats(&self) -> GlyphBrushStats {
+        GlyphBrushStats {
+            layout_cache_len: self.calculate_glyph_cache.len(),
+            draw_cache: self.texture_cache.stats(),
+            ..self.stats
+        }
+    }
+
+    /// Redraws the draw cache texture contents into an 8-bit greyscale image, optionally
+    /// outlining texture rows & glyph rects. Useful to check texture uploads & coordinates.
+    ///
+    /// See [`glyph_brush_draw_cache::DrawCache::atlas_image`](https://docs.rs/glyph_brush_draw_cache/*/glyph_brush_draw_cache/struct.DrawCache.html#method.atlas_image).
+    pub fn atlas_image(&self, overlay: AtlasOverlay) -> AtlasImage {
+        self.texture_cache.atlas_image(self.fonts.fonts(), overlay)
+    }
+
+    /// Returns the texture packing strategy of the texture cache.
+    ///
+    /// See [`GlyphBrushBuilder::draw_cache_packer`](struct.GlyphBrushBuilder.html#method.draw_cache_packer).
+    pub fn draw_cache_packer(&self) -> Packer {
+        self.texture_cache.packer()
+    }
+
     fn cleanup_frame(&mut self) {
         if self.cache_glyph_positioning {
             // clear section_buffer & trim calculate_glyph_cache to active sections
@@ -364,6 +666,19 @@ where
 
         mem::swap(&mut self.last_pre_positioned, &mut self.pre_positioned);
         self.pre_positioned.clear();
+
+        self.pending_layouts.clear();
+        self.word_cache.trim();
+
+        let in_use = self
+            .calculate_glyph_cache
+            .values()
+            .flat_map(|glyphed| &glyphed.positioned.glyphs)
+            .map(|sg| sg.font_id);
+        let removed = self.fonts.trim(in_use);
+        if !removed.is_empty() {
+            self.evict_fonts(|font_id| removed.contains(&font_id));
+        }
     }
 
     /// Retains the section in the cache as if it had been used in the last draw-frame.
@@ -380,7 +695,10 @@ where
         let section = section.into();
         if cfg!(debug_assertions) {
             for text in &section.text {
-                assert!(self.fonts.len() > text.font_id.0, "Invalid font id");
+                assert!(
+                    self.fonts.user_fonts().len() > text.font_id.0,
+                    "Invalid font id"
+                );
             }
         }
 
@@ -417,13 +735,17 @@ where
     /// * `update_texture` is called when new glyph texture data has been drawn for update in the
     ///   actual texture.
     ///   The arguments are the rect position of the data in the texture & the byte data itself
-    ///   which is a single `u8` alpha value per pixel.
+    ///   which is a single `u8` alpha value per pixel, or 3 `u8` red, green & blue values per
+    ///   pixel with [`TextureFormat::LcdRgb8`](enum.TextureFormat.html#variant.LcdRgb8).
     /// * `to_vertex` maps a single glyph's `GlyphVertex` data into a generic vertex type. The
     ///   mapped vertices are returned in an `Ok(BrushAction::Draw(vertices))` result.
     ///   It's recommended to use a single vertex per glyph quad for best performance.
     ///
     /// Trims the cache, see [caching behaviour](#caching-behaviour).
     ///
+    /// See [`process_queued_parallel`](#method.process_queued_parallel) to calculate section
+    /// layouts & vertices in parallel.
+    ///
     /// ```no_run
     /// # use glyph_brush::{ab_glyph::*, *};
     /// # fn main() -> Result<(), BrushError> {
@@ -439,14 +761,116 @@ where
     /// # Ok(())
     /// # }
     /// ```
+    ///
+    /// The texture page is not provided to `update_texture`, brushes with multiple
+    /// [`initial_cache_pages`](struct.GlyphBrushBuilder.html#method.initial_cache_pages)
+    /// should use [`process_queued_paged`](#method.process_queued_paged).
     pub fn process_queued<Up, VF>(
+        &mut self,
+        mut update_texture: Up,
+        to_vertex: VF,
+    ) -> Result<BrushAction<V>, BrushError>
+    where
+        Up: FnMut(Rectangle<u32>, &[u8]),
+        VF: Fn(GlyphVertex<X>) -> V + Copy,
+    {
+        self.process_queued_paged(|_, rect, data| update_texture(rect, data), to_vertex)
+    }
+
+    /// Processes all queued sections, see [`process_queued`](#method.process_queued).
+    ///
+    /// `update_texture` is additionally provided the texture page, e.g. texture array layer,
+    /// to update. Vertex texture pages are provided by
+    /// [`GlyphVertex::tex_page`](struct.GlyphVertex.html#structfield.tex_page).
+    ///
+    /// ```no_run
+    /// # use glyph_brush::{ab_glyph::*, *};
+    /// # fn main() -> Result<(), BrushError> {
+    /// # let dejavu = FontArc::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf")).unwrap();
+    /// let mut glyph_brush = GlyphBrushBuilder::using_font(dejavu)
+    ///     .initial_cache_pages(4)
+    ///     .build();
+    /// # fn update_texture_layer(_: u32, _: Rectangle<u32>, _: &[u8]) {}
+    /// # fn into_vertex(v: glyph_brush::GlyphVertex) { () }
+    /// glyph_brush.process_queued_paged(
+    ///     |page, rect, tex_data| update_texture_layer(page, rect, tex_data),
+    ///     |vertex_data| into_vertex(vertex_data),
+    /// )?
+    /// # ;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn process_queued_paged<Up, VF>(
         &mut self,
         update_texture: Up,
         to_vertex: VF,
     ) -> Result<BrushAction<V>, BrushError>
+    where
+        Up: FnMut(u32, Rectangle<u32>, &[u8]),
+        VF: Fn(GlyphVertex<X>) -> V + Copy,
+    {
+        self.parallel_layout = false;
+        self.calculate_pending_layouts();
+        self.process_laid_out(update_texture, to_vertex, |_| {})
+    }
+
+    /// Processes all queued sections like [`process_queued`](#method.process_queued), but
+    /// when [multithreading](struct.GlyphBrushBuilder.html#method.multithread) calculates
+    /// section vertices in parallel.
+    ///
+    /// Once called using multiple threads the layout of uncached sections is deferred by
+    /// [`queue`](#method.queue) & calculated in parallel by the next call.
+    /// Vertices are always returned in queue order.
+    pub fn process_queued_parallel<Up, VF>(
+        &mut self,
+        mut update_texture: Up,
+        to_vertex: VF,
+    ) -> Result<BrushAction<V>, BrushError>
     where
         Up: FnMut(Rectangle<u32>, &[u8]),
+        VF: Fn(GlyphVertex<X>) -> V + Copy + Send + Sync,
+        V: Send,
+        X: Send,
+    {
+        self.process_queued_paged_parallel(|_, rect, data| update_texture(rect, data), to_vertex)
+    }
+
+    /// Processes all queued sections like [`process_queued_paged`](#method.process_queued_paged),
+    /// calculating layouts & vertices in parallel,
+    /// see [`process_queued_parallel`](#method.process_queued_parallel).
+    pub fn process_queued_paged_parallel<Up, VF>(
+        &mut self,
+        update_texture: Up,
+        to_vertex: VF,
+    ) -> Result<BrushAction<V>, BrushError>
+    where
+        Up: FnMut(u32, Rectangle<u32>, &[u8]),
+        VF: Fn(GlyphVertex<X>) -> V + Copy + Send + Sync,
+        V: Send,
+        X: Send,
+    {
+        self.parallel_layout = self.multithread();
+        if !self.parallel_layout {
+            return self.process_queued_paged(update_texture, to_vertex);
+        }
+        self.calculate_pending_layouts_parallel();
+        self.process_laid_out(update_texture, to_vertex, |brush| {
+            brush.ensure_vertices_parallel(to_vertex)
+        })
+    }
+
+    /// Processes queued sections with calculated layouts, `ensure_vertices` may generate
+    /// section vertices before they are collected.
+    fn process_laid_out<Up, VF, EV>(
+        &mut self,
+        update_texture: Up,
+        to_vertex: VF,
+        ensure_vertices: EV,
+    ) -> Result<BrushAction<V>, BrushError>
+    where
+        Up: FnMut(u32, Rectangle<u32>, &[u8]),
         VF: Fn(GlyphVertex<X>) -> V + Copy,
+        EV: FnOnce(&mut Self),
     {
         let draw_info = LastDrawInfo {
             text_state: {
@@ -465,15 +889,15 @@ where
             // be retained in the texture cache avoiding cache thrashing if they are rendered
             // in a 2-draw per frame style.
             for section_hash in &self.keep_in_cache {
-                for sg in self
-                    .calculate_glyph_cache
-                    .get(section_hash)
-                    .iter()
-                    .flat_map(|gs| &gs.positioned.glyphs)
-                {
-                    self.texture_cache
-                        .queue_glyph(sg.font_id.0, sg.glyph.clone());
-                    some_text = true;
+                if let Some(gs) = self.calculate_glyph_cache.get(section_hash) {
+                    for sg in &gs.positioned.glyphs {
+                        self.texture_cache.queue_styled_glyph(
+                            sg.font_id.0,
+                            sg.glyph.clone(),
+                            gs.positioned.style(sg.section_index),
+                        );
+                        some_text = true;
+                    }
                 }
             }
 
@@ -488,17 +912,32 @@ where
             }
 
             if some_text {
-                match self.texture_cache.cache_queued(&self.fonts, update_texture) {
+                match self
+                    .texture_cache
+                    .cache_queued_paged(self.fonts.fonts(), update_texture)
+                {
                     Ok(CachedBy::Adding) => {}
                     Ok(CachedBy::Reordering) => {
                         for glyphed in self.calculate_glyph_cache.values_mut() {
                             glyphed.invalidate_texture_positions();
                         }
                     }
-                    Err(_) => {
+                    Err(err) => {
                         let (width, height) = self.texture_cache.dimensions();
-                        return Err(BrushError::TextureTooSmall {
-                            suggested: (width * 2, height * 2),
+                        let pages = self.texture_cache.pages();
+                        // multi-page caches add a page rather than enlarging every page,
+                        // unless a single glyph doesn't fit
+                        return Err(match err {
+                            CacheWriteErr::NoRoomForWholeQueue if pages > 1 => {
+                                BrushError::TextureTooSmall {
+                                    suggested: (width, height),
+                                    suggested_pages: pages + 1,
+                                }
+                            }
+                            _ => BrushError::TextureTooSmall {
+                                suggested: (width * 2, height * 2),
+                                suggested_pages: pages,
+                            },
                         });
                     }
                 }
@@ -507,6 +946,8 @@ where
             self.last_draw = draw_info;
 
             BrushAction::Draw({
+                ensure_vertices(self);
+
                 let mut verts = Vec::new();
 
                 for hash in &self.section_buffer {
@@ -522,23 +963,130 @@ where
                     verts.append(&mut glyphed.vertices);
                 }
 
+                self.stats.vertices_generated = verts.len();
                 verts
             })
         } else {
+            self.stats.vertices_generated = 0;
+            self.stats.redraw_skipped_frames += 1;
             BrushAction::ReDraw
         };
 
+        self.stats.sections_queued = self.section_buffer.len() + self.pre_positioned.len();
+        self.stats.layout_cache_hits = mem::take(&mut self.layout_cache_hits);
+        self.stats.layout_cache_misses = mem::take(&mut self.layout_cache_misses);
+
         self.cleanup_frame();
         Ok(result)
     }
 
-    /// Returns `true` if this glyph is currently present in the draw cache texture.
+    /// Returns `true` if multithreading with multiple threads available.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn multithread(&self) -> bool {
+        self.texture_cache.multithread() && rayon::current_num_threads() > 1
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn multithread(&self) -> bool {
+        false
+    }
+
+    /// Calculates deferred section layouts.
+    fn calculate_pending_layouts(&mut self) {
+        for (hash, pending) in mem::take(&mut self.pending_layouts) {
+            let positioned = pending.calculate(self.fonts.fonts(), &mut self.word_cache);
+            self.calculate_glyph_cache
+                .entry(hash)
+                .or_insert_with(|| Glyphed::new(positioned));
+        }
+    }
+
+    /// Calculates deferred section layouts in parallel.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn calculate_pending_layouts_parallel(&mut self)
+    where
+        X: Send,
+    {
+        use rayon::prelude::*;
+
+        if self.pending_layouts.is_empty() {
+            return;
+        }
+
+        let fonts = self.fonts.fonts();
+        let word_cache = &self.word_cache;
+        // each split reads the shared word cache & caches new words in a fork
+        let laid_out: Vec<_> = mem::take(&mut self.pending_layouts)
+            .into_par_iter()
+            .fold(
+                || (word_cache.fork(), vec![]),
+                |(mut word_cache, mut laid_out), (hash, pending)| {
+                    laid_out.push((hash, pending.calculate(fonts, &mut word_cache)));
+                    (word_cache, laid_out)
+                },
+            )
+            .collect();
+
+        for (forked_words, laid_out) in laid_out {
+            self.word_cache.merge(forked_words);
+            for (hash, positioned) in laid_out {
+                // may have already been calculated by a `GlyphCruncher` call
+                self.calculate_glyph_cache
+                    .entry(hash)
+                    .or_insert_with(|| Glyphed::new(positioned));
+            }
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn calculate_pending_layouts_parallel(&mut self) {
+        self.calculate_pending_layouts();
+    }
+
+    /// Generates vertices of queued sections in parallel.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn ensure_vertices_parallel<VF>(&mut self, to_vertex: VF)
+    where
+        VF: Fn(GlyphVertex<X>) -> V + Copy + Send + Sync,
+        V: Send,
+        X: Send,
+    {
+        use rayon::prelude::*;
+
+        let queued: FxHashSet<_> = self.section_buffer.iter().collect();
+        let texture_cache = &self.texture_cache;
+        self.calculate_glyph_cache
+            .par_iter_mut()
+            .filter(|(hash, _)| queued.contains(hash))
+            .for_each(|(_, glyphed)| glyphed.ensure_vertices(texture_cache, to_vertex));
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn ensure_vertices_parallel<VF>(&mut self, _: VF) {}
+
+    /// Returns `true` if this glyph, without synthetic styling, is currently present in the
+    /// draw cache texture.
     ///
     /// So `false` means either this glyph is invisible, like `' '`, or hasn't been queued &
-    /// processed yet.
+    /// processed yet. Use [`is_styled_draw_cached`](#method.is_styled_draw_cached) for glyphs
+    /// of text with a [`synthetic_style`](struct.Text.html#structfield.synthetic_style).
     #[inline]
     pub fn is_draw_cached(&self, font_id: FontId, glyph: &Glyph) -> bool {
-        self.texture_cache.rect_for(font_id.0, glyph).is_some()
+        self.is_styled_draw_cached(font_id, glyph, SyntheticStyle::default())
+    }
+
+    /// Returns `true` if this glyph with the synthetic style is currently present in the
+    /// draw cache texture.
+    #[inline]
+    pub fn is_styled_draw_cached(
+        &self,
+        font_id: FontId,
+        glyph: &Glyph,
+        style: SyntheticStyle,
+    ) -> bool {
+        self.texture_cache
+            .styled_rect_for(font_id.0, glyph, style)
+            .is_some()
     }
 }
 
@@ -560,7 +1108,7 @@ impl<F: Font + Clone, V, X, H: BuildHasher + Clone> GlyphBrush<V, X, F, H> {
     /// assert_eq!(new_brush.texture_dimensions(), (128, 128));
     /// ```
     pub fn to_builder(&self) -> GlyphBrushBuilder<F, H> {
-        let mut builder = GlyphBrushBuilder::using_fonts(self.fonts.clone())
+        let mut builder = GlyphBrushBuilder::using_fonts(self.fonts.user_fonts().to_vec())
             .cache_glyph_positioning(self.cache_glyph_positioning)
             .cache_redraws(self.cache_redraws)
             .section_hasher(self.section_hasher.clone());
@@ -578,9 +1126,27 @@ struct LastDrawInfo {
 #[derive(Debug)]
 pub struct GlyphVertex<'x, X = Extra> {
     pub tex_coords: Rect,
+    /// Texture page of the `tex_coords`, see
+    /// [`GlyphBrushBuilder::initial_cache_pages`](struct.GlyphBrushBuilder.html#method.initial_cache_pages).
+    /// Always `0` for single page caches.
+    pub tex_page: u32,
     pub pixel_coords: Rect,
     pub bounds: Rect,
     pub extra: &'x X,
+    /// Index of the `Text` in the `Section` the glyph is from.
+    pub section_index: usize,
+    /// Byte index of the glyph's character in the `Text` text.
+    pub byte_index: usize,
+    /// Index of the glyph in the laid out glyphs of the `Section`.
+    ///
+    /// Includes invisible glyphs, like spaces, that generate no vertices.
+    pub glyph_index: usize,
+    /// Index of the laid out line containing the glyph. Always `0` for custom layouts
+    /// that don't provide lines & pre-positioned glyphs.
+    pub line_index: usize,
+    /// Column-major matrix to transform the glyph quad's pixel coordinates with, see
+    /// [`Section::transform`](struct.Section.html#structfield.transform).
+    pub transform: Option<&'x [[f32; 4]; 4]>,
 }
 
 /// Actions that should be taken after processing queue data
@@ -596,8 +1162,13 @@ pub enum BrushAction<V> {
 pub enum BrushError {
     /// Texture is too small to cache queued glyphs
     ///
-    /// A larger suggested size is included.
-    TextureTooSmall { suggested: (u32, u32) },
+    /// A larger suggested size & number of pages is included. Caches with multiple
+    /// [`pages`](struct.GlyphBrushBuilder.html#method.initial_cache_pages) are suggested an
+    /// additional page, see [`resize_texture_pages`](struct.GlyphBrush.html#method.resize_texture_pages).
+    TextureTooSmall {
+        suggested: (u32, u32),
+        suggested_pages: u32,
+    },
 }
 impl fmt::Display for BrushError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -640,6 +1211,7 @@ impl SectionHashDetail {
         let text = s.finish();
 
         parts.hash_extra(&mut s);
+        parts.hash_transform(&mut s);
         parts.hash_geometry(&mut s);
         let full = s.finish();
 
@@ -664,6 +1236,93 @@ impl SectionHashDetail {
     }
 }
 
+/// A queued section waiting for layout calculation.
+struct PendingLayout<X> {
+    layout: Layout<BuiltInLineBreaker>,
+    geometry: SectionGeometry,
+    text: Vec<PendingText>,
+    /// Glyphs & lines of a similar section from last frame & the change required to reuse them.
+    previous: Option<(Vec<SectionGlyph>, Vec<SectionLine>, GlyphChange)>,
+    extra: Vec<X>,
+    styles: Vec<SyntheticStyle>,
+    transform: Option<[[f32; 4]; 4]>,
+}
+
+impl<X> PendingLayout<X> {
+    /// Updates font ids after font instances have been moved, see `GlyphBrush::add_font`.
+    fn remap_font_ids(&mut self, remap: &FontIdRemap) {
+        for text in &mut self.text {
+            text.font_id = remap.get(text.font_id);
+        }
+        if let Some((glyphs, ..)) = &mut self.previous {
+            for sg in glyphs {
+                sg.font_id = remap.get(sg.font_id);
+            }
+        }
+    }
+
+    fn calculate<F: Font>(self, fonts: &[F], word_cache: &mut WordCache) -> GlyphedSection<X> {
+        let (glyphs, lines) = match self.previous {
+            Some((glyphs, lines, change)) => self.layout.recalculate_glyphs_and_lines(
+                glyphs,
+                lines,
+                change,
+                fonts,
+                &self.geometry,
+                &self.text,
+                word_cache,
+            ),
+            None => self.layout.calculate_glyphs_and_lines(
+                fonts,
+                &self.geometry,
+                &self.text,
+                word_cache,
+            ),
+        };
+
+        GlyphedSection {
+            bounds: self.layout.bounds_rect(&self.geometry),
+            glyphs,
+            lines,
+            extra: self.extra,
+            styles: self.styles,
+            transform: self.transform,
+        }
+    }
+}
+
+/// Owned `SectionText` of a pending layout.
+struct PendingText {
+    text: String,
+    scale: PxScale,
+    font_id: FontId,
+    synthetic_bold: bool,
+}
+
+impl From<&SectionText<'_>> for PendingText {
+    #[inline]
+    fn from(s: &SectionText<'_>) -> Self {
+        Self {
+            text: s.text.to_owned(),
+            scale: s.scale,
+            font_id: s.font_id,
+            synthetic_bold: s.synthetic_bold,
+        }
+    }
+}
+
+impl ToSectionText for PendingText {
+    #[inline]
+    fn to_section_text(&self) -> SectionText<'_> {
+        SectionText {
+            text: &self.text,
+            scale: self.scale,
+            font_id: self.font_id,
+            synthetic_bold: self.synthetic_bold,
+        }
+    }
+}
+
 /// Container for positioned glyphs which can generate and cache vertices
 struct Glyphed<V, X> {
     positioned: GlyphedSection<X>,
@@ -691,6 +1350,12 @@ impl<V, X> Glyphed<V, X> {
         self.vertices.clear();
     }
 
+    /// Updates font ids after font instances have been moved, see `GlyphBrush::add_font`.
+    fn remap_font_ids(&mut self, remap: &FontIdRemap) {
+        self.invalidate_texture_positions();
+        self.positioned.remap_font_ids(remap);
+    }
+
     /// Calculate vertices if not already done
     fn ensure_vertices<F>(&mut self, texture_cache: &DrawCache, to_vertex: F)
     where
@@ -700,35 +1365,55 @@ impl<V, X> Glyphed<V, X> {
             return;
         }
 
+        let positioned = &self.positioned;
         let GlyphedSection {
             bounds,
             ref extra,
             ref glyphs,
-        } = self.positioned;
+            ref lines,
+            ref transform,
+            ..
+        } = *positioned;
 
+        let mut line_index = 0;
         self.vertices.reserve(glyphs.len());
-        self.vertices.extend(glyphs.iter().filter_map(|sg| {
-            match texture_cache.rect_for(sg.font_id.0, &sg.glyph) {
-                None => None,
-                Some((tex_coords, pixel_coords)) => {
-                    if pixel_coords.min.x as f32 > bounds.max.x
-                        || pixel_coords.min.y as f32 > bounds.max.y
-                        || bounds.min.x > pixel_coords.max.x as f32
-                        || bounds.min.y > pixel_coords.max.y as f32
-                    {
-                        // glyph is totally outside the bounds
-                        None
-                    } else {
-                        Some(to_vertex(GlyphVertex {
-                            tex_coords,
-                            pixel_coords,
-                            bounds,
-                            extra: &extra[sg.section_index],
-                        }))
+        self.vertices
+            .extend(glyphs.iter().enumerate().filter_map(|(glyph_index, sg)| {
+                while lines
+                    .get(line_index + 1)
+                    .is_some_and(|line| line.glyphs.start <= glyph_index)
+                {
+                    line_index += 1;
+                }
+
+                let style = positioned.style(sg.section_index);
+                match texture_cache.styled_rect_for(sg.font_id.0, &sg.glyph, style) {
+                    None => None,
+                    Some((tex_coords, pixel_coords, tex_page)) => {
+                        if pixel_coords.min.x as f32 > bounds.max.x
+                            || pixel_coords.min.y as f32 > bounds.max.y
+                            || bounds.min.x > pixel_coords.max.x as f32
+                            || bounds.min.y > pixel_coords.max.y as f32
+                        {
+                            // glyph is totally outside the bounds
+                            None
+                        } else {
+                            Some(to_vertex(GlyphVertex {
+                                tex_coords,
+                                tex_page,
+                                pixel_coords,
+                                bounds,
+                                extra: &extra[sg.section_index],
+                                section_index: sg.section_index,
+                                byte_index: sg.byte_index,
+                                glyph_index,
+                                line_index,
+                                transform: transform.as_ref(),
+                            }))
+                        }
                     }
                 }
-            }
-        }));
+            }));
     }
 }
 
@@ -743,6 +1428,8 @@ mod hash_diff_test {
                     text: "Hello, ",
                     scale: PxScale::from(20.0),
                     font_id: FontId(0),
+                    variations: &[],
+                    synthetic_style: SyntheticStyle::default(),
                     extra: Extra {
                         color: [1.0, 0.9, 0.8, 0.7],
                         z: 0.444,
@@ -752,6 +1439,8 @@ mod hash_diff_test {
                     text: "World",
                     scale: PxScale::from(22.0),
                     font_id: FontId(1),
+                    variations: &[],
+                    synthetic_style: SyntheticStyle::default(),
                     extra: Extra {
                         color: [0.6, 0.5, 0.4, 0.3],
                         z: 0.444,
@@ -761,6 +1450,7 @@ mod hash_diff_test {
             bounds: (55.5, 66.6),
             layout: Layout::default(),
             screen_position: (999.99, 888.88),
+            transform: None,
         }
     }
 
@@ -801,6 +1491,24 @@ mod hash_diff_test {
         assert!(matches!(diff, None));
     }
 
+    #[test]
+    fn change_transform() {
+        let build_hasher = DefaultSectionHasher::default();
+        let mut section = section();
+        let hash_deets = SectionHashDetail::new(&build_hasher, &section, &section.layout);
+
+        section = section.with_transform([
+            [2.0, 0.0, 0.0, 0.0],
+            [0.0, 2.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+        let transformed = SectionHashDetail::new(&build_hasher, &section, &section.layout);
+        assert_ne!(hash_deets.full, transformed.full);
+
+        assert!(hash_deets.layout_diff(transformed).is_none());
+    }
+
     #[test]
     fn change_text() {
         let build_hasher = DefaultSectionHasher::default();
@@ -817,6 +1525,24 @@ mod hash_diff_test {
 
         assert!(matches!(diff, Some(GlyphChange::Unknown)));
     }
+
+    #[test]
+    fn change_variations() {
+        let build_hasher = DefaultSectionHasher::default();
+        let mut section = section();
+        let hash_deets = SectionHashDetail::new(&build_hasher, &section, &section.layout);
+
+        let variations = [FontVariation::new(b"wght", 700.0)];
+        section.text[0].variations = &variations;
+
+        let diff = hash_deets.layout_diff(SectionHashDetail::new(
+            &build_hasher,
+            &section,
+            &section.layout,
+        ));
+
+        assert!(matches!(diff, Some(GlyphChange::Unknown)));
+    }
 }
 
 #[cfg(test)]
@@ -856,4 +1582,365 @@ mod glyph_brush_test {
         assert!(!brush.is_draw_cached(FontId(1), &glyphs[3]));
         assert!(!brush.is_draw_cached(FontId(0), &unqueued_glyph));
     }
+
+    #[test]
+    fn variations_use_font_instances() {
+        let font = FontRef::try_from_slice(include_bytes!("../../fonts/Cantarell-VF.otf")).unwrap();
+        let thin = [FontVariation::new(b"wght", 100.0)];
+        let heavy = [FontVariation::new(b"wght", 800.0)];
+
+        let mut brush = GlyphBrushBuilder::using_font(font).build();
+
+        let section = Section::default()
+            .add_text(Text::new("mm"))
+            .add_text(Text::new("mm").with_variations(&thin))
+            .add_text(Text::new("mm").with_variations(&heavy));
+
+        brush.queue(&section);
+        let glyphs: Vec<_> = brush.glyphs(&section).cloned().collect();
+
+        // an instance is created for each distinct variation, following the user fonts
+        assert_eq!(brush.fonts().len(), 1);
+        let font_ids: Vec<_> = glyphs.iter().map(|g| g.font_id).collect();
+        assert_eq!(
+            font_ids,
+            [0, 0, 1, 1, 2, 2]
+                .iter()
+                .map(|id| FontId(*id))
+                .collect::<Vec<_>>()
+        );
+
+        let advance = |n: usize| glyphs[n + 1].glyph.position.x - glyphs[n].glyph.position.x;
+        assert!(advance(2) < advance(4), "{} < {}", advance(2), advance(4));
+
+        let h_advance = |font_id| brush.font(font_id).h_advance_unscaled(glyphs[0].glyph.id);
+        assert!(h_advance(FontId(1)) < h_advance(FontId(2)));
+
+        // re-using the same variations re-uses the same instances
+        brush.queue(Section::default().add_text(Text::new("m").with_variations(&heavy)));
+        assert_eq!(brush.fonts.fonts().len(), 3);
+
+        brush.process_queued(|_, _| {}, |_| ()).unwrap();
+        assert!(brush.is_draw_cached(FontId(1), &glyphs[2].glyph));
+        assert!(brush.is_draw_cached(FontId(2), &glyphs[4].glyph));
+    }
+
+    #[test]
+    fn multithread_layouts_use_word_cache() {
+        let font = FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf")).unwrap();
+        let mut brush = GlyphBrushBuilder::using_font(font)
+            .multithread(true)
+            .build();
+        // enables deferred parallel layout of queued sections
+        brush.process_queued_parallel(|_, _| {}, |_| ()).unwrap();
+
+        for n in 0..8 {
+            brush.queue(Section::default().add_text(Text::new(&format!("hello world {}", n))));
+        }
+        assert_eq!(
+            brush.pending_layouts.len(),
+            brush.multithread() as usize * 8
+        );
+        brush.process_queued_parallel(|_, _| {}, |_| ()).unwrap();
+
+        // words measured in parallel are merged into the shared cache
+        let cached_words = brush.word_cache.len();
+        assert_eq!(cached_words, 10, "'hello ', 'world ' & 8 numbers");
+
+        for n in 0..8 {
+            brush.queue(Section::default().add_text(Text::new(&format!("world hello {}", n))));
+        }
+        brush.process_queued_parallel(|_, _| {}, |_| ()).unwrap();
+        // all words were cached by the previous layouts
+        assert_eq!(brush.word_cache.len(), cached_words);
+    }
+
+    #[test]
+    fn unused_font_instances_are_removed() {
+        let font = FontRef::try_from_slice(include_bytes!("../../fonts/Cantarell-VF.otf")).unwrap();
+        let heavy = [FontVariation::new(b"wght", 800.0)];
+        let heavy_section = Section::default().add_text(Text::new("m").with_variations(&heavy));
+
+        let mut brush = GlyphBrushBuilder::using_font(font).build();
+
+        // animate the weight creating a new instance every frame
+        let max_instances = FontInstances::<FontRef<'_>>::MAX_INSTANCES;
+        for n in 0..max_instances * 3 {
+            let wght = [FontVariation::new(b"wght", 100.0 + n as f32)];
+            brush.queue(&heavy_section);
+            brush.queue(Section::default().add_text(Text::new("m").with_variations(&wght)));
+            brush.process_queued(|_, _| {}, |_| ()).unwrap();
+
+            let instances = brush.fonts.fonts().len() - 1;
+            assert!(instances <= max_instances + 1, "{} instances", instances);
+            // only the new instance glyph is rasterized, the draw cache is not cleared
+            assert_eq!(brush.stats().draw_cache.misses, if n == 0 { 2 } else { 1 });
+        }
+
+        // the instance used every frame is kept & still drawn
+        let sg = brush.glyphs(&heavy_section).next().unwrap().clone();
+        brush.queue(&heavy_section);
+        brush.process_queued(|_, _| {}, |_| ()).unwrap();
+        assert!(brush.is_draw_cached(sg.font_id, &sg.glyph));
+    }
+
+    #[test]
+    fn add_font_moves_font_instances() {
+        let font = FontRef::try_from_slice(include_bytes!("../../fonts/Cantarell-VF.otf")).unwrap();
+        let heavy = [FontVariation::new(b"wght", 800.0)];
+        let section = Section::default().add_text(Text::new("m").with_variations(&heavy));
+
+        let mut brush = GlyphBrushBuilder::using_font(font).build();
+        brush.queue(&section);
+        brush.process_queued(|_, _| {}, |_| ()).unwrap();
+        let glyph_id = brush.glyphs(&section).next().unwrap().glyph.id;
+        let heavy_advance = brush.font(FontId(1)).h_advance_unscaled(glyph_id);
+
+        let dejavu = FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf")).unwrap();
+        assert_eq!(brush.add_font(dejavu), FontId(1));
+
+        // the instance now follows the new font
+        let sg = brush.glyphs(&section).next().unwrap().clone();
+        assert_eq!(sg.font_id, FontId(2));
+        assert_eq!(
+            brush.font(FontId(2)).h_advance_unscaled(sg.glyph.id),
+            heavy_advance
+        );
+
+        brush.queue(&section);
+        brush.process_queued(|_, _| {}, |_| ()).unwrap();
+        assert!(brush.is_draw_cached(FontId(2), &sg.glyph));
+    }
+
+    #[test]
+    fn synthetic_style_draws_separately() {
+        let font = FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf")).unwrap();
+        let mut brush = GlyphBrushBuilder::using_font(font).build();
+
+        let bold = SyntheticStyle {
+            bold: true,
+            ..<_>::default()
+        };
+        brush.queue(
+            Section::default()
+                .add_text(Text::new("l"))
+                .add_text(Text::new("l").with_synthetic_style(bold)),
+        );
+
+        let action = brush
+            .process_queued(|_, _| {}, |v| (v.tex_coords, v.pixel_coords))
+            .unwrap();
+        let vertices = match action {
+            BrushAction::Draw(vertices) => vertices,
+            _ => panic!("expected draw"),
+        };
+
+        assert_eq!(vertices.len(), 2);
+        let (regular_uv, regular) = vertices[0];
+        let (bold_uv, bold_rect) = vertices[1];
+        assert_ne!(regular_uv, bold_uv);
+        assert!(bold_rect.width() > regular.width());
+    }
+
+    #[test]
+    fn is_styled_draw_cached() {
+        let font = FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf")).unwrap();
+        let mut brush = GlyphBrushBuilder::using_font(font).build();
+
+        let bold = SyntheticStyle {
+            bold: true,
+            ..<_>::default()
+        };
+        let section = Section::default().add_text(Text::new("l").with_synthetic_style(bold));
+        brush.queue(&section);
+        let glyph = brush.glyphs(section).next().unwrap().glyph.clone();
+        brush.process_queued(|_, _| {}, |_| ()).unwrap();
+
+        assert!(brush.is_styled_draw_cached(FontId(0), &glyph, bold));
+        assert!(!brush.is_draw_cached(FontId(0), &glyph));
+    }
+
+    #[test]
+    fn vertex_glyph_context() {
+        let font = FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf")).unwrap();
+        let mut brush = GlyphBrushBuilder::using_font(font).build();
+        let section = Section::default()
+            .add_text(Text::new("a b\n"))
+            .add_text(Text::new("\ncd"));
+
+        brush.queue(&section);
+        let action = brush
+            .process_queued(
+                |_, _| {},
+                |v| (v.section_index, v.byte_index, v.glyph_index, v.line_index),
+            )
+            .unwrap();
+        let vertices = match action {
+            BrushAction::Draw(vertices) => vertices,
+            _ => panic!("expected draw"),
+        };
+        // spaces & empty lines generate no vertices but are counted
+        assert_eq!(
+            vertices,
+            vec![(0, 0, 0, 0), (0, 2, 2, 0), (1, 1, 3, 2), (1, 2, 4, 2)]
+        );
+
+        // identical frame redraws
+        brush.queue(&section);
+        let action = brush.process_queued(|_, _| {}, |_| (0, 0, 0, 0)).unwrap();
+        assert!(matches!(action, BrushAction::ReDraw));
+
+        // regenerated with the new vertex function
+        brush.queue(&section);
+        brush.regenerate_vertices();
+        let action = brush
+            .process_queued(|_, _| {}, |v| (0, 0, v.glyph_index * 10, 0))
+            .unwrap();
+        let vertices = match action {
+            BrushAction::Draw(vertices) => vertices,
+            _ => panic!("expected draw"),
+        };
+        let glyph_indices: Vec<_> = vertices.iter().map(|v| v.2).collect();
+        assert_eq!(glyph_indices, vec![0, 20, 30, 40]);
+    }
+
+    #[test]
+    fn texture_too_small_suggests_page() {
+        let font = FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf")).unwrap();
+        let section = Section::default().add_text(Text::new("ABCDEFGHIJ").with_scale(20.0));
+
+        let mut brush = GlyphBrushBuilder::using_font(font.clone())
+            .initial_cache_size((32, 32))
+            .build();
+        brush.queue(&section);
+        assert_eq!(
+            brush.process_queued(|_, _| {}, |_| ()).unwrap_err(),
+            BrushError::TextureTooSmall {
+                suggested: (64, 64),
+                suggested_pages: 1,
+            }
+        );
+
+        let mut brush = GlyphBrushBuilder::using_font(font)
+            .initial_cache_size((32, 32))
+            .initial_cache_pages(2)
+            .build();
+        brush.queue(&section);
+        assert_eq!(
+            brush.process_queued(|_, _| {}, |_| ()).unwrap_err(),
+            BrushError::TextureTooSmall {
+                suggested: (32, 32),
+                suggested_pages: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn process_queued_lays_out_on_queue() {
+        let font = FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf")).unwrap();
+        let mut brush = GlyphBrushBuilder::using_font(font)
+            .multithread(true)
+            .build();
+
+        for _ in 0..2 {
+            brush.queue(Section::default().add_text(Text::new("hello world")));
+            // text is not copied for deferred layout
+            assert!(brush.pending_layouts.is_empty());
+            // vertices need not be `Send`
+            brush
+                .process_queued(|_, _| {}, |_| std::rc::Rc::new(()))
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn multithread_matches_single_thread() {
+        let font = FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf")).unwrap();
+        let draw_frames = |multithread: bool| {
+            let mut brush = GlyphBrushBuilder::using_font(font.clone())
+                .initial_cache_size((1024, 1024))
+                .multithread(multithread)
+                // avoid matching cached glyphs positioned differently
+                .draw_cache_position_tolerance(0.0)
+                .build();
+
+            let mut frames = vec![];
+            for frame in 0..3 {
+                for n in 0..200 {
+                    let text = format!("Section {} of frame", n);
+                    brush.queue(
+                        Section::default()
+                            // geometry-only changes each frame
+                            .with_screen_position((frame as f32 * 10.0, n as f32 * 20.0))
+                            .with_bounds((100.0 + (frame / 2) as f32 * 50.0, f32::INFINITY))
+                            .add_text(Text::new(&text).with_scale(16.0 + (n % 3) as f32)),
+                    );
+                }
+
+                let action = brush
+                    .process_queued_parallel(|_, _| {}, |v| (v.pixel_coords, v.bounds))
+                    .unwrap();
+                match action {
+                    BrushAction::Draw(vertices) => frames.push(vertices),
+                    _ => panic!("expected draw"),
+                }
+            }
+            frames
+        };
+
+        let single_thread = draw_frames(false);
+        let multithread = draw_frames(true);
+        assert!(!single_thread[0].is_empty());
+        assert_eq!(multithread.len(), single_thread.len());
+
+        // draw cache glyph positions may differ by float error
+        let close = |a: Rect, b: Rect| {
+            (a.min.x - b.min.x).abs() < 0.01
+                && (a.min.y - b.min.y).abs() < 0.01
+                && (a.max.x - b.max.x).abs() < 0.01
+                && (a.max.y - b.max.y).abs() < 0.01
+        };
+        for (mt, st) in multithread.iter().zip(&single_thread) {
+            assert_eq!(mt.len(), st.len());
+            for (a, b) in mt.iter().zip(st) {
+                assert!(close(a.0, b.0) && a.1 == b.1, "{:?} != {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn stats() {
+        let font = FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf")).unwrap();
+        let mut brush = GlyphBrushBuilder::using_font(font).build();
+        let queue_sections = |brush: &mut GlyphBrush<(), Extra, FontRef<'_>>| {
+            brush.queue(Section::default().add_text(Text::new("a b")));
+            brush.queue(
+                Section::default()
+                    .with_screen_position((0.0, 20.0))
+                    .add_text(Text::new("c")),
+            );
+        };
+
+        queue_sections(&mut brush);
+        brush.process_queued(|_, _| {}, |_| ()).unwrap();
+        let stats = brush.stats();
+        assert_eq!(stats.layout_cache_len, 2);
+        assert_eq!((stats.layout_cache_hits, stats.layout_cache_misses), (0, 2));
+        assert_eq!(stats.sections_queued, 2);
+        assert_eq!(stats.vertices_generated, 3);
+        assert_eq!(stats.redraw_skipped_frames, 0);
+        assert_eq!(stats.draw_cache.glyphs, 3);
+        assert_eq!(stats.draw_cache.last_cached_by, Some(CachedBy::Adding));
+
+        // unchanged frame
+        queue_sections(&mut brush);
+        assert!(matches!(
+            brush.process_queued(|_, _| {}, |_| ()).unwrap(),
+            BrushAction::ReDraw
+        ));
+        let stats = brush.stats();
+        assert_eq!((stats.layout_cache_hits, stats.layout_cache_misses), (2, 0));
+        assert_eq!(stats.vertices_generated, 0);
+        assert_eq!(stats.redraw_skipped_frames, 1);
+    }
 }
@@ -1,4 +1,4 @@
-use crate::{DefaultSectionHasher, Font, FontId, GlyphBrush};
+use crate::{DefaultSectionHasher, Font, FontId, FontInstances, GlyphBrush};
 use glyph_brush_draw_cache::*;
 use glyph_brush_layout::ab_glyph::*;
 use std::hash::BuildHasher;
@@ -112,6 +112,17 @@ impl<F: Font, H: BuildHasher> GlyphBrushBuilder<F, H> {
         self
     }
 
+    /// Initial number of texture pages, e.g. layers of a 2D texture array, used as a
+    /// gpu cache. Glyphs are added to further pages when earlier pages are full.
+    /// Use [`GlyphBrush::process_queued_paged`](struct.GlyphBrush.html#method.process_queued_paged)
+    /// to update pages other than the first.
+    ///
+    /// Defaults to `1`
+    pub fn initial_cache_pages(mut self, pages: u32) -> Self {
+        self.draw_cache_builder = self.draw_cache_builder.pages(pages);
+        self
+    }
+
     /// Sets the maximum allowed difference in scale used for judging whether to reuse an
     /// existing glyph in the GPU cache.
     ///
@@ -135,7 +146,9 @@ impl<F: Font, H: BuildHasher> GlyphBrushBuilder<F, H> {
         self
     }
 
-    /// When multiple CPU cores are available spread draw-cache work across all cores.
+    /// When multiple CPU cores are available spread draw-cache work across all cores, as well as
+    /// section layout & vertex generation when using
+    /// [`GlyphBrush::process_queued_parallel`](struct.GlyphBrush.html#method.process_queued_parallel).
     ///
     /// Defaults to `true`.
     pub fn multithread(mut self, multithread: bool) -> Self {
@@ -156,6 +169,55 @@ impl<F: Font, H: BuildHasher> GlyphBrushBuilder<F, H> {
         self
     }
 
+    /// Rasterize glyphs in the texture cache as signed distance fields, cached once at a
+    /// reference scale & reused for all scales. Requires a renderer that thresholds the
+    /// distance field, e.g. `CpuRenderer`.
+    ///
+    /// Defaults to `None`
+    ///
+    /// See docs for `glyph_brush_draw_cache::DrawCacheBuilder::sdf`
+    pub fn draw_cache_sdf<S: Into<Option<Sdf>>>(mut self, sdf: S) -> Self {
+        self.draw_cache_builder = self.draw_cache_builder.sdf(sdf);
+        self
+    }
+
+    /// Sets gamma, contrast & stem darkening corrections applied to rasterized glyphs.
+    /// E.g. `CoverageAdjustment::light_on_dark()` to avoid light text on dark backgrounds
+    /// looking thin.
+    ///
+    /// Defaults to `CoverageAdjustment::NONE`
+    ///
+    /// See docs for `glyph_brush_draw_cache::CoverageAdjustment`
+    pub fn draw_cache_coverage_adjustment(mut self, adjustment: CoverageAdjustment) -> Self {
+        self.draw_cache_builder = self.draw_cache_builder.coverage_adjustment(adjustment);
+        self
+    }
+
+    /// Sets the pixel format of the texture cache. `TextureFormat::LcdRgb8` rasterizes glyphs
+    /// with LCD subpixel antialiasing, uploading 3 bytes per pixel & requiring per-channel
+    /// blending when drawn.
+    ///
+    /// Cannot be combined with [`draw_cache_sdf`](#method.draw_cache_sdf).
+    ///
+    /// Defaults to `TextureFormat::A8`
+    ///
+    /// See docs for `glyph_brush_draw_cache::TextureFormat`
+    pub fn draw_cache_texture_format(mut self, texture_format: TextureFormat) -> Self {
+        self.draw_cache_builder = self.draw_cache_builder.texture_format(texture_format);
+        self
+    }
+
+    /// Sets the texture packing strategy of the texture cache. `Packer::Skyline` packs
+    /// mixed text sizes more densely.
+    ///
+    /// Defaults to `Packer::Rows`
+    ///
+    /// See docs for `glyph_brush_draw_cache::Packer`
+    pub fn draw_cache_packer(mut self, packer: Packer) -> Self {
+        self.draw_cache_builder = self.draw_cache_builder.packer(packer);
+        self
+    }
+
     /// Sets whether perform the calculation of glyph positioning according to the layout
     /// every time, or use a cached result if the input `Section` and `GlyphPositioner` are the
     /// same hash as a previous call.
@@ -223,7 +285,7 @@ impl<F: Font, H: BuildHasher> GlyphBrushBuilder<F, H> {
     /// ```
     pub fn build<V, X>(self) -> GlyphBrush<V, X, F, H> {
         GlyphBrush {
-            fonts: self.font_data,
+            fonts: FontInstances::with_user_fonts(self.font_data),
             texture_cache: self.draw_cache_builder.build(),
 
             last_draw: <_>::default(),
@@ -242,6 +304,14 @@ impl<F: Font, H: BuildHasher> GlyphBrushBuilder<F, H> {
 
             last_pre_positioned: <_>::default(),
             pre_positioned: <_>::default(),
+
+            word_cache: <_>::default(),
+            pending_layouts: <_>::default(),
+            parallel_layout: false,
+
+            layout_cache_hits: 0,
+            layout_cache_misses: 0,
+            stats: <_>::default(),
         }
     }
 
@@ -271,9 +341,14 @@ impl<F: Font, H: BuildHasher> GlyphBrushBuilder<F, H> {
 /// * `add_font_bytes`
 /// * `add_font`
 /// * `initial_cache_size`
+/// * `initial_cache_pages`
 /// * `draw_cache_scale_tolerance`
 /// * `draw_cache_position_tolerance`
 /// * `draw_cache_align_4x4`
+/// * `draw_cache_sdf`
+/// * `draw_cache_coverage_adjustment`
+/// * `draw_cache_texture_format`
+/// * `draw_cache_packer`
 /// * `cache_glyph_positioning`
 /// * `cache_redraws`
 ///
@@ -334,6 +409,15 @@ macro_rules! delegate_glyph_brush_builder_fns {
             self
         }
 
+        /// Initial number of texture pages, e.g. layers of a 2D texture array, used as a
+        /// gpu cache. Glyphs are added to further pages when earlier pages are full.
+        ///
+        /// Defaults to `1`
+        pub fn initial_cache_pages(mut self, pages: u32) -> Self {
+            self.$inner = self.$inner.initial_cache_pages(pages);
+            self
+        }
+
         /// Sets the maximum allowed difference in scale used for judging whether to reuse an
         /// existing glyph in the GPU cache.
         ///
@@ -370,6 +454,52 @@ macro_rules! delegate_glyph_brush_builder_fns {
             self
         }
 
+        /// Rasterize glyphs in the texture cache as signed distance fields, cached once at a
+        /// reference scale & reused for all scales.
+        ///
+        /// Defaults to `None`
+        ///
+        /// See docs for `glyph_brush_draw_cache::DrawCacheBuilder::sdf`
+        pub fn draw_cache_sdf<S: Into<Option<$crate::Sdf>>>(mut self, sdf: S) -> Self {
+            self.$inner = self.$inner.draw_cache_sdf(sdf);
+            self
+        }
+
+        /// Sets gamma, contrast & stem darkening corrections applied to rasterized glyphs.
+        ///
+        /// Defaults to `CoverageAdjustment::NONE`
+        ///
+        /// See docs for `glyph_brush_draw_cache::CoverageAdjustment`
+        pub fn draw_cache_coverage_adjustment(
+            mut self,
+            adjustment: $crate::CoverageAdjustment,
+        ) -> Self {
+            self.$inner = self.$inner.draw_cache_coverage_adjustment(adjustment);
+            self
+        }
+
+        /// Sets the pixel format of the texture cache. `TextureFormat::LcdRgb8` rasterizes
+        /// glyphs with LCD subpixel antialiasing.
+        ///
+        /// Defaults to `TextureFormat::A8`
+        ///
+        /// See docs for `glyph_brush_draw_cache::TextureFormat`
+        pub fn draw_cache_texture_format(mut self, texture_format: $crate::TextureFormat) -> Self {
+            self.$inner = self.$inner.draw_cache_texture_format(texture_format);
+            self
+        }
+
+        /// Sets the texture packing strategy of the texture cache. `Packer::Skyline` packs
+        /// mixed text sizes more densely.
+        ///
+        /// Defaults to `Packer::Rows`
+        ///
+        /// See docs for `glyph_brush_draw_cache::Packer`
+        pub fn draw_cache_packer(mut self, packer: $crate::Packer) -> Self {
+            self.$inner = self.$inner.draw_cache_packer(packer);
+            self
+        }
+
         /// Sets whether perform the calculation of glyph positioning according to the layout
         /// every time, or use a cached result if the input `Section` and `GlyphPositioner` are the
         /// same hash as a previous call.
@@ -0,0 +1,39 @@
+use glyph_brush_draw_cache::DrawCacheStats;
+
+/// Layout & draw cache statistics, see [`GlyphBrush::stats`](struct.GlyphBrush.html#method.stats).
+///
+/// Per frame values describe the latest successful `process_queued` call.
+///
+/// # Example
+/// ```
+/// # use glyph_brush::{ab_glyph::*, *};
+/// # let dejavu = FontArc::try_from_slice(include_bytes!("../../../fonts/DejaVuSans.ttf")).unwrap();
+/// # let mut glyph_brush: GlyphBrush<()> = GlyphBrushBuilder::using_font(dejavu).build();
+/// glyph_brush.queue(Section::default().add_text(Text::new("Hello glyph_brush")));
+/// glyph_brush.process_queued(|_, _| {}, |_| ())?;
+///
+/// let stats = glyph_brush.stats();
+/// assert_eq!(stats.sections_queued, 1);
+/// if stats.draw_cache.last_cached_by == Some(CachedBy::Reordering) {
+///     // all glyphs were re-rasterized & uploaded this frame
+/// }
+/// # Ok::<_, BrushError>(())
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[non_exhaustive]
+pub struct GlyphBrushStats {
+    /// Number of section layouts in the layout cache.
+    pub layout_cache_len: usize,
+    /// Queued sections with a cached layout in the latest frame.
+    pub layout_cache_hits: usize,
+    /// Queued sections requiring layout calculation in the latest frame.
+    pub layout_cache_misses: usize,
+    /// Sections, including pre-positioned glyphs, queued in the latest frame.
+    pub sections_queued: usize,
+    /// Vertices generated in the latest frame, `0` if the frame was a `BrushAction::ReDraw`.
+    pub vertices_generated: usize,
+    /// Total frames returning `BrushAction::ReDraw` as nothing had changed.
+    pub redraw_skipped_frames: u64,
+    /// Statistics of the draw cache, including the latest `CachedBy`.
+    pub draw_cache: DrawCacheStats,
+}
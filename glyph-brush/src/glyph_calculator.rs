@@ -59,8 +59,23 @@ pub trait GlyphCruncher<F: Font = FontArc, X: Clone = Extra> {
     /// Returns the available fonts.
     ///
     /// The `FontId` corresponds to the index of the font data.
+    ///
+    /// Font instances created for [`Text::variations`](struct.Text.html#structfield.variations)
+    /// are not included, see [`font`](#method.font).
     fn fonts(&self) -> &[F];
 
+    /// Returns the font for the id of a laid out glyph.
+    ///
+    /// Glyphs of text with [`variations`](struct.Text.html#structfield.variations) have ids
+    /// of font instances following the [`fonts`](#tymethod.fonts). Font instance ids are
+    /// only valid until the next draw, or cache scope, as unused instances are removed.
+    ///
+    /// The default implementation indexes [`fonts`](#tymethod.fonts).
+    #[inline]
+    fn font(&self, font_id: FontId) -> &F {
+        &self.fonts()[font_id.0]
+    }
+
     /// Returns a bounding box for the section glyphs calculated using each glyph's
     /// vertical & horizontal metrics.
     ///
@@ -105,6 +120,75 @@ pub trait GlyphCruncher<F: Font = FontArc, X: Clone = Extra> {
         let layout = section.layout;
         self.glyph_bounds_custom_layout(section, &layout)
     }
+
+    /// Returns the logical bounding box of the section layout, the union of each line's
+    /// advance width, including whitespace, & full line height.
+    ///
+    /// Unlike [`glyph_bounds`](#method.glyph_bounds) trailing spaces & lines without
+    /// visible glyphs, e.g. blank lines, contribute to the bounds. The bounds are not capped
+    /// to the layout bounds, so trailing spaces may extend past a width bound.
+    ///
+    /// Empty text & text ending in a line break, e.g. `"a\n"`, include a final zero width line
+    /// using the font & scale of the last text. Returns `None` if the section has no text.
+    /// Layouts that do not provide lines, see
+    /// [`GlyphPositioner::calculate_glyphs_and_lines`](trait.GlyphPositioner.html#method.calculate_glyphs_and_lines),
+    /// fall back to [`glyph_bounds_custom_layout`](#tymethod.glyph_bounds_custom_layout).
+    ///
+    /// Benefits from caching, see [caching behaviour](#caching-behaviour).
+    ///
+    /// The default implementation returns
+    /// [`glyph_bounds_custom_layout`](#tymethod.glyph_bounds_custom_layout).
+    #[inline]
+    fn layout_bounds_custom_layout<'a, S, L>(
+        &mut self,
+        section: S,
+        custom_layout: &L,
+    ) -> Option<Rect>
+    where
+        X: 'a,
+        L: GlyphPositioner + Hash,
+        S: Into<Cow<'a, Section<'a, X>>>,
+    {
+        self.glyph_bounds_custom_layout(section, custom_layout)
+    }
+
+    /// Returns the logical bounding box of the section layout, the union of each line's
+    /// advance width, including whitespace, & full line height.
+    ///
+    /// Unlike [`glyph_bounds`](#method.glyph_bounds) trailing spaces & lines without
+    /// visible glyphs, e.g. blank lines, contribute to the bounds. The bounds are not capped
+    /// to the layout bounds, so trailing spaces may extend past a width bound.
+    ///
+    /// Empty text & text ending in a line break, e.g. `"a\n"`, include a final zero width line
+    /// using the font & scale of the last text, as a text field would. Returns `None` if the
+    /// section has no text. Custom layouts without lines fall back to glyph bounds.
+    ///
+    /// Benefits from caching, see [caching behaviour](#caching-behaviour).
+    ///
+    /// # Example
+    /// ```
+    /// # use glyph_brush::{ab_glyph::*, *};
+    /// # let font = FontArc::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf")).unwrap();
+    /// # let glyphs = GlyphCalculatorBuilder::using_font(font).build();
+    /// # let mut glyphs = glyphs.cache_scope();
+    /// let with_space = glyphs
+    ///     .layout_bounds(Section::default().add_text(Text::new("Text ")))
+    ///     .unwrap();
+    /// let without = glyphs
+    ///     .layout_bounds(Section::default().add_text(Text::new("Text")))
+    ///     .unwrap();
+    /// assert!(with_space.width() > without.width());
+    /// ```
+    #[inline]
+    fn layout_bounds<'a, S>(&mut self, section: S) -> Option<Rect>
+    where
+        X: 'a,
+        S: Into<Cow<'a, Section<'a, X>>>,
+    {
+        let section = section.into();
+        let layout = section.layout;
+        self.layout_bounds_custom_layout(section, &layout)
+    }
 }
 
 /// Cut down version of a [`GlyphBrush`](struct.GlyphBrush.html) that can calculate pixel bounds,
@@ -147,11 +231,29 @@ pub struct GlyphCalculator<F = FontArc, X = Extra, H = DefaultSectionHasher> {
 
     // cache of section-layout hash -> computed glyphs, this avoid repeated glyph computation
     // for identical layout/sections common to repeated frame rendering
-    calculate_glyph_cache: Mutex<FxHashMap<u64, GlyphedSection<X>>>,
+    calculate_glyph_cache: Mutex<GlyphCalculatorCache<F, X>>,
 
     section_hasher: H,
 }
 
+struct GlyphCalculatorCache<F, X> {
+    glyphs: FxHashMap<u64, GlyphedSection<X>>,
+    // font instances created for text variations, these have ids following `fonts`
+    font_instances: FontInstances<F>,
+    // measured words shared by all section layouts
+    word_cache: WordCache,
+}
+
+impl<F, X> GlyphCalculatorCache<F, X> {
+    fn new(fonts_len: usize) -> Self {
+        Self {
+            glyphs: <_>::default(),
+            font_instances: FontInstances::following(fonts_len),
+            word_cache: <_>::default(),
+        }
+    }
+}
+
 impl<F, X, H> fmt::Debug for GlyphCalculator<F, X, H> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "GlyphCalculator")
@@ -179,12 +281,12 @@ impl<F: Font, X, H: BuildHasher + Clone> GlyphCalculator<F, X, H> {
 /// [`GlyphCalculator`](struct.GlyphCalculator.html) scoped cache lock.
 pub struct GlyphCalculatorGuard<'brush, F: 'brush = FontArc, X = Extra, H = DefaultSectionHasher> {
     fonts: &'brush Vec<F>,
-    glyph_cache: MutexGuard<'brush, FxHashMap<u64, GlyphedSection<X>>>,
+    glyph_cache: MutexGuard<'brush, GlyphCalculatorCache<F, X>>,
     cached: FxHashSet<u64>,
     section_hasher: H,
 }
 
-impl<F: Font, X: Clone + Hash, H: BuildHasher> GlyphCalculatorGuard<'_, F, X, H> {
+impl<F: VariableFontInstance, X: Clone + Hash, H: BuildHasher> GlyphCalculatorGuard<'_, F, X, H> {
     /// Returns the calculate_glyph_cache key for this sections glyphs
     fn cache_glyphs<L>(&mut self, section: &Section<'_, X>, layout: &L) -> u64
     where
@@ -197,14 +299,34 @@ impl<F: Font, X: Clone + Hash, H: BuildHasher> GlyphCalculatorGuard<'_, F, X, H>
             hasher.finish()
         };
 
-        if let Entry::Vacant(entry) = self.glyph_cache.entry(section_hash) {
+        let fonts = self.fonts;
+        let GlyphCalculatorCache {
+            glyphs: glyph_cache,
+            font_instances,
+            word_cache,
+        } = &mut *self.glyph_cache;
+
+        if let Entry::Vacant(entry) = glyph_cache.entry(section_hash) {
             let geometry = SectionGeometry::from(section);
-            let glyphs = layout.calculate_glyphs(self.fonts, &geometry, &section.text);
+            let section_text = font_instances.section_text(fonts, &section.text);
+            let (glyphs, lines) = if section.text.iter().all(|t| t.variations.is_empty()) {
+                layout.calculate_glyphs_and_lines(fonts, &geometry, &section_text, word_cache)
+            } else {
+                layout.calculate_glyphs_and_lines(
+                    &font_instances.all_fonts(fonts),
+                    &geometry,
+                    &section_text,
+                    word_cache,
+                )
+            };
 
             entry.insert(GlyphedSection {
                 bounds: layout.bounds_rect(&geometry),
                 glyphs,
+                lines,
                 extra: section.text.iter().map(|t| t.extra.clone()).collect(),
+                styles: section.synthetic_styles(),
+                transform: section.transform,
             });
         }
 
@@ -218,7 +340,7 @@ impl<F: Font, X, H: BuildHasher> fmt::Debug for GlyphCalculatorGuard<'_, F, X, H
     }
 }
 
-impl<F: Font, X: Clone + Hash, H: BuildHasher> GlyphCruncher<F, X>
+impl<F: VariableFontInstance, X: Clone + Hash, H: BuildHasher> GlyphCruncher<F, X>
     for GlyphCalculatorGuard<'_, F, X, H>
 {
     fn glyphs_custom_layout<'a, 'b, S, L>(
@@ -233,7 +355,7 @@ impl<F: Font, X: Clone + Hash, H: BuildHasher> GlyphCruncher<F, X>
     {
         let section_hash = self.cache_glyphs(&section.into(), custom_layout);
         self.cached.insert(section_hash);
-        self.glyph_cache[&section_hash].glyphs()
+        self.glyph_cache.glyphs[&section_hash].glyphs()
     }
 
     fn glyph_bounds_custom_layout<'a, S, L>(
@@ -252,10 +374,10 @@ impl<F: Font, X: Clone + Hash, H: BuildHasher> GlyphCruncher<F, X>
         let section_hash = self.cache_glyphs(&section, custom_layout);
         self.cached.insert(section_hash);
 
-        self.glyph_cache[&section_hash]
+        self.glyph_cache.glyphs[&section_hash]
             .glyphs()
             .fold(None, |b: Option<Rect>, sg| {
-                let sfont = self.fonts[sg.font_id.0].as_scaled(sg.glyph.scale);
+                let sfont = self.font(sg.font_id).as_scaled(sg.glyph.scale);
                 let pos = sg.glyph.position;
                 let lbound = Rect {
                     min: point(
@@ -290,16 +412,55 @@ impl<F: Font, X: Clone + Hash, H: BuildHasher> GlyphCruncher<F, X>
             })
     }
 
+    fn layout_bounds_custom_layout<'a, S, L>(
+        &mut self,
+        section: S,
+        custom_layout: &L,
+    ) -> Option<Rect>
+    where
+        X: 'a,
+        L: GlyphPositioner + Hash,
+        S: Into<Cow<'a, Section<'a, X>>>,
+    {
+        let section = section.into();
+        let with_final_line = section.with_final_line()?;
+        let section_hash = self.cache_glyphs(&with_final_line, custom_layout);
+        self.cached.insert(section_hash);
+        match self.glyph_cache.glyphs[&section_hash].layout_bounds() {
+            Some(bounds) => Some(bounds),
+            // layout without lines
+            None => self.glyph_bounds_custom_layout(section, custom_layout),
+        }
+    }
+
     #[inline]
     fn fonts(&self) -> &[F] {
         &self.fonts
     }
+
+    #[inline]
+    fn font(&self, font_id: FontId) -> &F {
+        self.glyph_cache.font_instances.font(self.fonts, font_id)
+    }
 }
 
 impl<F, X, H> Drop for GlyphCalculatorGuard<'_, F, X, H> {
     fn drop(&mut self) {
         let cached = mem::take(&mut self.cached);
-        self.glyph_cache.retain(|key, _| cached.contains(key));
+        let GlyphCalculatorCache {
+            glyphs,
+            font_instances,
+            word_cache,
+        } = &mut *self.glyph_cache;
+
+        glyphs.retain(|key, _| cached.contains(key));
+        word_cache.trim();
+
+        let in_use = glyphs.values().flat_map(|g| &g.glyphs).map(|sg| sg.font_id);
+        let removed = font_instances.trim(in_use);
+        if !removed.is_empty() {
+            word_cache.retain_fonts(|id| !removed.contains(&id));
+        }
     }
 }
 
@@ -359,8 +520,8 @@ impl<F: Font, H: BuildHasher> GlyphCalculatorBuilder<F, H> {
     /// Builds a `GlyphCalculator`
     pub fn build<X>(self) -> GlyphCalculator<F, X, H> {
         GlyphCalculator {
+            calculate_glyph_cache: Mutex::new(GlyphCalculatorCache::new(self.font_data.len())),
             fonts: self.font_data,
-            calculate_glyph_cache: Mutex::default(),
             section_hasher: self.section_hasher,
         }
     }
@@ -370,7 +531,13 @@ impl<F: Font, H: BuildHasher> GlyphCalculatorBuilder<F, H> {
 pub(crate) struct GlyphedSection<X = Extra> {
     pub bounds: Rect,
     pub glyphs: Vec<SectionGlyph>,
+    /// Lines of the glyphs, may be empty for custom layouts.
+    pub lines: Vec<SectionLine>,
     pub extra: Vec<X>,
+    /// Synthetic style per section text, may be empty if unstyled.
+    pub styles: Vec<SyntheticStyle>,
+    /// Column-major glyph quad transform.
+    pub transform: Option<[[f32; 4]; 4]>,
 }
 
 impl<X> GlyphedSection<X> {
@@ -378,6 +545,27 @@ impl<X> GlyphedSection<X> {
     pub(crate) fn glyphs(&self) -> SectionGlyphIter<'_> {
         self.glyphs.iter()
     }
+
+    /// Updates font ids after font instances have been moved.
+    pub(crate) fn remap_font_ids(&mut self, remap: &FontIdRemap) {
+        for sg in &mut self.glyphs {
+            sg.font_id = remap.get(sg.font_id);
+        }
+    }
+
+    /// Returns the union of the logical line rects.
+    pub(crate) fn layout_bounds(&self) -> Option<Rect> {
+        self.lines.iter().map(|line| line.rect).reduce(|a, b| Rect {
+            min: point(a.min.x.min(b.min.x), a.min.y.min(b.min.y)),
+            max: point(a.max.x.max(b.max.x), a.max.y.max(b.max.y)),
+        })
+    }
+
+    /// Returns the synthetic style of the given section text.
+    #[inline]
+    pub(crate) fn style(&self, section_index: usize) -> SyntheticStyle {
+        self.styles.get(section_index).copied().unwrap_or_default()
+    }
 }
 
 #[cfg(test)]
@@ -422,6 +610,68 @@ mod test {
         assert_relative_eq!(g_bounds.max.x, g_width * 11.0, epsilon = f32::EPSILON);
     }
 
+    #[test]
+    fn layout_bounds_include_whitespace() {
+        let glyphs = GlyphCalculatorBuilder::using_font(MONO_FONT.clone()).build();
+        let mut glyphs = glyphs.cache_scope();
+
+        let scale = PxScale::from(16.0);
+        let sfont = MONO_FONT.as_scaled(scale);
+        let advance = sfont.h_advance(MONO_FONT.glyph_id(' '));
+        let line_height = sfont.height() + sfont.line_gap();
+
+        // trailing spaces, a blank line & a blank last line
+        let section = Section::default()
+            .add_text(Text::new("Hi  \n\n").with_scale(scale))
+            .with_screen_position((10.0, 20.0));
+
+        let bounds = glyphs.layout_bounds(&section).expect("None bounds");
+        assert_relative_eq!(bounds.min.x, 10.0);
+        assert_relative_eq!(bounds.min.y, 20.0);
+        assert_relative_eq!(bounds.max.x, 10.0 + advance * 4.0, epsilon = 0.001);
+        assert_relative_eq!(bounds.max.y, 20.0 + line_height * 3.0, epsilon = 0.001);
+
+        // the blank lines have no glyphs
+        let g_bounds = glyphs.glyph_bounds(&section).expect("None bounds");
+        assert!(g_bounds.max.y < bounds.max.y);
+
+        // whitespace only
+        let section = Section::default().add_text(Text::new("   ").with_scale(scale));
+        let bounds = glyphs.layout_bounds(&section).expect("None bounds");
+        assert_relative_eq!(bounds.width(), advance * 3.0, epsilon = 0.001);
+
+        assert!(glyphs.layout_bounds(Section::default()).is_none());
+    }
+
+    #[test]
+    fn layout_bounds_of_empty_text_fields() {
+        let glyphs = GlyphCalculatorBuilder::using_font(MONO_FONT.clone()).build();
+        let mut glyphs = glyphs.cache_scope();
+
+        let scale = PxScale::from(20.0);
+        let sfont = MONO_FONT.as_scaled(scale);
+        let line_height = sfont.height() + sfont.line_gap();
+        let field = |text| {
+            Section::default()
+                .add_text(Text::new(text).with_scale(scale))
+                .with_screen_position((10.0, 20.0))
+        };
+
+        // empty text is a single zero width line
+        let bounds = glyphs.layout_bounds(field("")).expect("None bounds");
+        assert_eq!(bounds.min, point(10.0, 20.0));
+        assert_relative_eq!(bounds.width(), 0.0);
+        assert_relative_eq!(bounds.height(), line_height, epsilon = 0.001);
+        assert!(glyphs.glyph_bounds(field("")).is_none());
+
+        // a trailing line break starts a blank last line
+        let bounds = glyphs.layout_bounds(field("a\n")).expect("None bounds");
+        let a_bounds = glyphs.layout_bounds(field("a")).expect("None bounds");
+        assert_relative_eq!(bounds.width(), a_bounds.width());
+        assert_relative_eq!(bounds.height(), line_height * 2.0, epsilon = 0.001);
+        assert_relative_eq!(a_bounds.height(), line_height, epsilon = 0.001);
+    }
+
     #[test]
     fn glyph_bounds_respect_layout_bounds() {
         let glyphs = GlyphCalculatorBuilder::using_font(MONO_FONT.clone()).build();
@@ -468,7 +718,10 @@ mod test {
                 glyph: glyph.clone(),
                 font_id: FontId(0),
             }],
+            lines: vec![],
             extra: vec![Extra { color, z: 0.444 }],
+            styles: vec![],
+            transform: None,
         };
         let mut b = GlyphedSection {
             bounds: Rect {
@@ -481,7 +734,10 @@ mod test {
                 glyph,
                 font_id: FontId(0),
             }],
+            lines: vec![],
             extra: vec![Extra { color, z: 0.444 }],
+            styles: vec![],
+            transform: None,
         };
 
         assert_eq!(a, b);
@@ -564,4 +820,62 @@ mod test {
             assert_relative_eq!(sg.glyph.position.y, bounded_sg.glyph.position.y);
         }
     }
+
+    #[test]
+    fn variations_change_glyph_bounds() {
+        let font = FontArc::try_from_slice(include_bytes!("../../fonts/Cantarell-VF.otf")).unwrap();
+        let calc = GlyphCalculatorBuilder::using_font(font).build();
+        let mut calc = calc.cache_scope();
+
+        let thin = [FontVariation::new(b"wght", 100.0)];
+        let heavy = [FontVariation::new(b"wght", 800.0)];
+        let section = |variations| {
+            Section::default().add_text(Text::new("Hello World").with_variations(variations))
+        };
+
+        let thin_bounds = calc.glyph_bounds(section(&thin)).expect("None bounds");
+        let heavy_bounds = calc.glyph_bounds(section(&heavy)).expect("None bounds");
+        assert!(thin_bounds.width() < heavy_bounds.width());
+
+        assert!(calc
+            .glyphs(section(&heavy))
+            .all(|sg| sg.font_id == FontId(2)));
+        assert_eq!(calc.fonts().len(), 1, "instances are not user fonts");
+
+        // instance ids can be used to look up the instance fonts
+        let sg = calc.glyphs(section(&heavy)).next().unwrap().clone();
+        let h_advance = |font_id| calc.font(font_id).h_advance_unscaled(sg.glyph.id);
+        assert!(h_advance(FontId(1)) < h_advance(FontId(2)));
+    }
+
+    #[test]
+    fn layout_bounds_without_lines() {
+        /// Layout that does not provide lines.
+        #[derive(Hash)]
+        struct NoLines(Layout<BuiltInLineBreaker>);
+
+        impl GlyphPositioner for NoLines {
+            fn calculate_glyphs<F: Font, S: ToSectionText>(
+                &self,
+                fonts: &[F],
+                geometry: &SectionGeometry,
+                sections: &[S],
+            ) -> Vec<SectionGlyph> {
+                self.0.calculate_glyphs(fonts, geometry, sections)
+            }
+
+            fn bounds_rect(&self, geometry: &SectionGeometry) -> Rect {
+                self.0.bounds_rect(geometry)
+            }
+        }
+
+        let calc = GlyphCalculatorBuilder::using_font(MONO_FONT.clone()).build();
+        let mut calc = calc.cache_scope();
+        let section = Section::default().add_text(Text::new("Hello"));
+        let layout = NoLines(Layout::default());
+
+        let bounds = calc.layout_bounds_custom_layout(&section, &layout);
+        assert!(bounds.is_some());
+        assert_eq!(bounds, calc.glyph_bounds_custom_layout(&section, &layout));
+    }
 }
@@ -0,0 +1,473 @@
+//! Golden image regression testing utilities, enabled by the `golden` feature.
+//!
+//! Render sections with a [`CpuRenderer`](../struct.CpuRenderer.html) & compare them
+//! against stored reference images to catch layout & rasterization changes.
+//!
+//! Reference images may be binary PGM (`.pgm`) for `A8` canvases, PAM (`.pam`) for
+//! `Rgba8` canvases or, with the `png` feature, PNG (`.png`) for either.
+//!
+//! # Example
+//! ```no_run
+//! use glyph_brush::{ab_glyph::*, golden, *};
+//! # let font = FontArc::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf")).unwrap();
+//!
+//! let canvas = golden::render_section(
+//!     vec![font],
+//!     Section::default().add_text(Text::new("Hello").with_scale(24.0)),
+//!     CanvasFormat::A8,
+//!     (80, 30),
+//! );
+//! golden::assert_golden(&canvas, "tests/golden/hello.pgm", 8);
+//! ```
+//!
+//! On mismatch the actual & diff images are written next to the reference, e.g.
+//! `hello.actual.pgm` & `hello.diff.pam`. Run with `GLYPH_BRUSH_BLESS=1` to write
+//! the actual images as the new references instead.
+use crate::{Canvas, CanvasFormat, CpuRenderer, GlyphBrushBuilder, Section, VariableFontInstance};
+use std::{
+    borrow::Cow,
+    error, fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Environment variable that, when set, makes [`assert_golden`](fn.assert_golden.html)
+/// write reference images rather than compare them.
+pub const BLESS_ENV: &str = "GLYPH_BRUSH_BLESS";
+
+/// Renders a single `section` with the `fonts` onto a new transparent canvas.
+pub fn render_section<'a, F, S>(
+    fonts: Vec<F>,
+    section: S,
+    format: CanvasFormat,
+    (width, height): (u32, u32),
+) -> Canvas
+where
+    F: VariableFontInstance + Sync,
+    S: Into<Cow<'a, Section<'a>>>,
+{
+    let mut glyph_brush = GlyphBrushBuilder::using_fonts(fonts).build();
+    let mut renderer = CpuRenderer::new(glyph_brush.texture_dimensions());
+    let mut canvas = Canvas::new(format, width, height);
+
+    glyph_brush.queue(section);
+    renderer
+        .render(&mut glyph_brush, &mut canvas)
+        .expect("render section");
+    canvas
+}
+
+/// Pixel differences between two same sized canvases.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageDiff {
+    /// Number of pixels with any channel differing by more than the tolerance.
+    pub differing_pixels: usize,
+    /// Largest channel difference of any pixel.
+    pub max_difference: u8,
+    /// `Rgba8` visualisation, differing pixels are red & others a faint copy of the expected.
+    pub image: Canvas,
+}
+
+/// Golden image comparison error.
+#[derive(Debug)]
+pub enum GoldenError {
+    Io(io::Error),
+    /// Image data could not be decoded or encoded.
+    Format(String),
+    /// Canvases have different dimensions or pixel formats.
+    Incompatible {
+        actual: (u32, u32, CanvasFormat),
+        expected: (u32, u32, CanvasFormat),
+    },
+    /// Pixels differ by more than the tolerance.
+    Mismatch(ImageDiff),
+}
+
+impl fmt::Display for GoldenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "{}", err),
+            Self::Format(msg) => write!(f, "Invalid image: {}", msg),
+            Self::Incompatible { actual, expected } => write!(
+                f,
+                "Image {}x{} {:?} incompatible with expected {}x{} {:?}",
+                actual.0, actual.1, actual.2, expected.0, expected.1, expected.2
+            ),
+            Self::Mismatch(diff) => write!(
+                f,
+                "{} pixels differ, max difference {}",
+                diff.differing_pixels, diff.max_difference
+            ),
+        }
+    }
+}
+
+impl error::Error for GoldenError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for GoldenError {
+    #[inline]
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// Compares `actual` to `expected` allowing each channel to differ by up to `tolerance`.
+pub fn compare(actual: &Canvas, expected: &Canvas, tolerance: u8) -> Result<(), GoldenError> {
+    if (actual.width, actual.height, actual.format)
+        != (expected.width, expected.height, expected.format)
+    {
+        return Err(GoldenError::Incompatible {
+            actual: (actual.width, actual.height, actual.format),
+            expected: (expected.width, expected.height, expected.format),
+        });
+    }
+
+    let bpp = actual.format.bytes_per_pixel();
+    let mut image = Canvas::new(CanvasFormat::Rgba8, actual.width, actual.height);
+    let mut differing_pixels = 0;
+    let mut max_difference = 0;
+
+    let pixels = actual.data.chunks(bpp).zip(expected.data.chunks(bpp));
+    for ((a, e), out) in pixels.zip(image.data.chunks_exact_mut(4)) {
+        let difference = a
+            .iter()
+            .zip(e)
+            .map(|(a, e)| a.abs_diff(*e))
+            .max()
+            .unwrap_or(0);
+        max_difference = max_difference.max(difference);
+
+        if difference > tolerance {
+            differing_pixels += 1;
+            out.copy_from_slice(&[255, 0, 0, 255]);
+        } else {
+            // faint grey copy of the expected coverage on white
+            let shade = 255 - e[bpp - 1] / 4;
+            out.copy_from_slice(&[shade, shade, shade, 255]);
+        }
+    }
+
+    match differing_pixels {
+        0 => Ok(()),
+        _ => Err(GoldenError::Mismatch(ImageDiff {
+            differing_pixels,
+            max_difference,
+            image,
+        })),
+    }
+}
+
+/// Compares `actual` to the reference image at `path`, see [`compare`](fn.compare.html).
+///
+/// On failure writes the actual image & any diff image next to the reference.
+pub fn check_golden<P: AsRef<Path>>(
+    actual: &Canvas,
+    path: P,
+    tolerance: u8,
+) -> Result<(), GoldenError> {
+    let path = path.as_ref();
+    let result = read_image(path).and_then(|expected| compare(actual, &expected, tolerance));
+
+    if result.is_err() {
+        write_image(actual, sibling_path(path, "actual", actual.format))?;
+        if let Err(GoldenError::Mismatch(diff)) = &result {
+            write_image(&diff.image, sibling_path(path, "diff", diff.image.format))?;
+        }
+    }
+    result
+}
+
+/// Asserts `actual` matches the reference image at `path`, see
+/// [`check_golden`](fn.check_golden.html).
+///
+/// If the [`BLESS_ENV`](constant.BLESS_ENV.html) environment variable is set writes
+/// `actual` as the reference image instead.
+///
+/// # Panics
+/// If the images do not match.
+pub fn assert_golden<P: AsRef<Path>>(actual: &Canvas, path: P, tolerance: u8) {
+    let path = path.as_ref();
+    if std::env::var_os(BLESS_ENV).is_some() {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).expect("create golden dir");
+        }
+        write_image(actual, path).expect("write golden image");
+        return;
+    }
+
+    if let Err(err) = check_golden(actual, path, tolerance) {
+        panic!(
+            "{} does not match golden image: {}\n\
+             Set {}=1 to update the reference image.",
+            path.display(),
+            err,
+            BLESS_ENV
+        );
+    }
+}
+
+/// Returns e.g. `hello.diff.pam` for `hello.pgm`, keeping `.png` extensions.
+fn sibling_path(path: &Path, suffix: &str, format: CanvasFormat) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let ext = match (extension(path).as_str(), format) {
+        ("png", _) => "png",
+        (_, CanvasFormat::A8) => "pgm",
+        (_, CanvasFormat::Rgba8) => "pam",
+    };
+    path.with_file_name(format!("{}.{}.{}", stem, suffix, ext))
+}
+
+#[inline]
+fn extension(path: &Path) -> String {
+    path.extension()
+        .map(|ext| ext.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default()
+}
+
+/// Reads an image, the format is chosen by file extension.
+pub fn read_image<P: AsRef<Path>>(path: P) -> Result<Canvas, GoldenError> {
+    let path = path.as_ref();
+    let bytes = fs::read(path)?;
+    match extension(path).as_str() {
+        #[cfg(feature = "png")]
+        "png" => decode_png(&bytes),
+        _ => decode_pnm(&bytes),
+    }
+}
+
+/// Writes an image, the format is chosen by file extension.
+///
+/// `.pgm` & `.pam` files must have `A8` & `Rgba8` canvases respectively.
+pub fn write_image<P: AsRef<Path>>(canvas: &Canvas, path: P) -> Result<(), GoldenError> {
+    let path = path.as_ref();
+    let bytes = match (extension(path).as_str(), canvas.format) {
+        #[cfg(feature = "png")]
+        ("png", _) => encode_png(canvas)?,
+        ("pgm", CanvasFormat::A8) | ("pam", CanvasFormat::Rgba8) => encode_pnm(canvas),
+        (ext, format) => {
+            return Err(GoldenError::Format(format!(
+                "cannot write {:?} canvas as .{}",
+                format, ext
+            )))
+        }
+    };
+    fs::write(path, bytes)?;
+    Ok(())
+}
+
+/// Encodes an `A8` canvas as binary PGM & an `Rgba8` canvas as PAM.
+pub fn encode_pnm(canvas: &Canvas) -> Vec<u8> {
+    let mut out = match canvas.format {
+        CanvasFormat::A8 => format!("P5\n{} {}\n255\n", canvas.width, canvas.height),
+        CanvasFormat::Rgba8 => format!(
+            "P7\nWIDTH {}\nHEIGHT {}\nDEPTH 4\nMAXVAL 255\nTUPLTYPE RGB_ALPHA\nENDHDR\n",
+            canvas.width, canvas.height
+        ),
+    }
+    .into_bytes();
+    out.extend_from_slice(&canvas.data);
+    out
+}
+
+/// Decodes binary PGM into an `A8` canvas & 4 channel PAM into an `Rgba8` canvas.
+pub fn decode_pnm(bytes: &[u8]) -> Result<Canvas, GoldenError> {
+    let mut header = PnmHeader { bytes, pos: 0 };
+    let (width, height, format) = match header.token()? {
+        "P5" => {
+            let width = header.number()?;
+            let height = header.number()?;
+            if header.number()? != 255 {
+                return Err(GoldenError::Format("PGM maxval must be 255".into()));
+            }
+            (width, height, CanvasFormat::A8)
+        }
+        "P7" => {
+            let (mut width, mut height, mut depth) = (None, None, None);
+            loop {
+                match header.token()? {
+                    "WIDTH" => width = Some(header.number()?),
+                    "HEIGHT" => height = Some(header.number()?),
+                    "DEPTH" => depth = Some(header.number()?),
+                    "MAXVAL" if header.number()? == 255 => {}
+                    "TUPLTYPE" => {
+                        header.token()?;
+                    }
+                    "ENDHDR" => break,
+                    token => {
+                        return Err(GoldenError::Format(format!("unexpected PAM {:?}", token)))
+                    }
+                }
+            }
+            match (width, height, depth) {
+                (Some(w), Some(h), Some(4)) => (w, h, CanvasFormat::Rgba8),
+                _ => return Err(GoldenError::Format("PAM must be 4 channel".into())),
+            }
+        }
+        magic => return Err(GoldenError::Format(format!("unsupported {:?}", magic))),
+    };
+
+    // single whitespace byte precedes the data
+    let data = &bytes[(header.pos + 1).min(bytes.len())..];
+    let len = width as usize * height as usize * format.bytes_per_pixel();
+    if data.len() < len {
+        return Err(GoldenError::Format("truncated data".into()));
+    }
+    Ok(Canvas {
+        width,
+        height,
+        format,
+        data: data[..len].to_vec(),
+    })
+}
+
+/// Whitespace separated header token reader, skipping `#` comments.
+struct PnmHeader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> PnmHeader<'a> {
+    fn token(&mut self) -> Result<&'a str, GoldenError> {
+        while let Some(&b) = self.bytes.get(self.pos) {
+            match b {
+                b'#' => {
+                    while self.bytes.get(self.pos).is_some_and(|&b| b != b'\n') {
+                        self.pos += 1;
+                    }
+                }
+                b if b.is_ascii_whitespace() => self.pos += 1,
+                _ => break,
+            }
+        }
+        let start = self.pos;
+        while self
+            .bytes
+            .get(self.pos)
+            .is_some_and(|b| !b.is_ascii_whitespace())
+        {
+            self.pos += 1;
+        }
+        match std::str::from_utf8(&self.bytes[start..self.pos]) {
+            Ok(token) if !token.is_empty() => Ok(token),
+            _ => Err(GoldenError::Format("invalid header".into())),
+        }
+    }
+
+    fn number(&mut self) -> Result<u32, GoldenError> {
+        self.token()?
+            .parse()
+            .map_err(|_| GoldenError::Format("invalid header number".into()))
+    }
+}
+
+/// Encodes a canvas as 8-bit grayscale or RGBA PNG.
+#[cfg(feature = "png")]
+pub fn encode_png(canvas: &Canvas) -> Result<Vec<u8>, GoldenError> {
+    let mut out = vec![];
+    let mut encoder = png::Encoder::new(&mut out, canvas.width, canvas.height);
+    encoder.set_color(match canvas.format {
+        CanvasFormat::A8 => png::ColorType::Grayscale,
+        CanvasFormat::Rgba8 => png::ColorType::Rgba,
+    });
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&canvas.data))
+        .map_err(|err| GoldenError::Format(err.to_string()))?;
+    Ok(out)
+}
+
+/// Decodes 8-bit grayscale PNG into an `A8` canvas & RGBA PNG into an `Rgba8` canvas.
+#[cfg(feature = "png")]
+pub fn decode_png(bytes: &[u8]) -> Result<Canvas, GoldenError> {
+    let format_err = |err: png::DecodingError| GoldenError::Format(err.to_string());
+    let mut reader = png::Decoder::new(bytes).read_info().map_err(format_err)?;
+    let mut data = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut data).map_err(format_err)?;
+    let format = match (info.color_type, info.bit_depth) {
+        (png::ColorType::Grayscale, png::BitDepth::Eight) => CanvasFormat::A8,
+        (png::ColorType::Rgba, png::BitDepth::Eight) => CanvasFormat::Rgba8,
+        other => return Err(GoldenError::Format(format!("unsupported PNG {:?}", other))),
+    };
+    data.truncate(info.buffer_size());
+    Ok(Canvas {
+        width: info.width,
+        height: info.height,
+        format,
+        data,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{ab_glyph::*, Text};
+
+    fn hello(format: CanvasFormat) -> Canvas {
+        let font = FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf")).unwrap();
+        render_section(
+            vec![font],
+            Section::default()
+                .with_screen_position((4.0, 2.0))
+                .add_text(Text::new("Hello").with_scale(24.0)),
+            format,
+            (72, 28),
+        )
+    }
+
+    #[test]
+    fn golden_hello() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/hello.pgm");
+        assert_golden(&hello(CanvasFormat::A8), path, 8);
+    }
+
+    #[test]
+    fn pnm_round_trip() {
+        for format in [CanvasFormat::A8, CanvasFormat::Rgba8] {
+            let canvas = hello(format);
+            assert_eq!(decode_pnm(&encode_pnm(&canvas)).unwrap(), canvas);
+        }
+    }
+
+    #[cfg(feature = "png")]
+    #[test]
+    fn png_round_trip() {
+        for format in [CanvasFormat::A8, CanvasFormat::Rgba8] {
+            let canvas = hello(format);
+            assert_eq!(decode_png(&encode_png(&canvas).unwrap()).unwrap(), canvas);
+        }
+    }
+
+    #[test]
+    fn compare_diff() {
+        let expected = hello(CanvasFormat::A8);
+        assert!(compare(&expected, &expected, 0).is_ok());
+
+        let mut actual = expected.clone();
+        actual.data[0] = 10;
+        actual.data[1] = 200;
+        assert!(compare(&actual, &expected, 10).is_err());
+
+        match compare(&actual, &expected, 0) {
+            Err(GoldenError::Mismatch(diff)) => {
+                assert_eq!(diff.differing_pixels, 2);
+                assert_eq!(diff.max_difference, 200);
+                assert_eq!(diff.image.pixel(1, 0), Some(&[255, 0, 0, 255][..]));
+                assert_eq!(diff.image.pixel(2, 0), Some(&[255, 255, 255, 255][..]));
+            }
+            other => panic!("Unexpected {:?}", other),
+        }
+
+        let smaller = Canvas::new(CanvasFormat::A8, 2, 2);
+        assert!(matches!(
+            compare(&smaller, &expected, 255),
+            Err(GoldenError::Incompatible { .. })
+        ));
+    }
+}
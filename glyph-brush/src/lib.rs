@@ -21,23 +21,34 @@
 //!     Ok(BrushAction::ReDraw) => {
 //!         // Re-draw last frame's vertices unmodified.
 //!     }
-//!     Err(BrushError::TextureTooSmall { suggested }) => {
+//!     Err(BrushError::TextureTooSmall { suggested, .. }) => {
 //!         // Enlarge texture + glyph_brush texture cache and retry.
 //!     }
 //! }
 //! # Ok(())
 //! # }
 //! ```
+mod cpu_renderer;
 mod extra;
 mod glyph_brush;
 mod glyph_calculator;
 mod owned_section;
 mod section;
+mod variation;
 
+#[cfg(feature = "golden")]
+pub mod golden;
 pub mod legacy;
+pub mod markup;
 
-pub use crate::{extra::*, glyph_brush::*, glyph_calculator::*, owned_section::*, section::*};
-pub use glyph_brush_draw_cache::Rectangle;
+pub use crate::{
+    cpu_renderer::*, extra::*, glyph_brush::*, glyph_calculator::*, owned_section::*, section::*,
+    variation::*,
+};
+pub use glyph_brush_draw_cache::{
+    AtlasImage, AtlasOverlay, CachedBy, CoverageAdjustment, DrawCacheStats, Packer, Rectangle, Sdf,
+    SyntheticStyle, TextureFormat,
+};
 pub use glyph_brush_layout::*;
 
 use glyph_brush_layout::ab_glyph::*;
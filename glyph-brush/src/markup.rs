@@ -0,0 +1,459 @@
+//! Inline markup parsing into rich text sections.
+//!
+//! Markup is text with `[tag]` & `[tag=value]` opening tags and `[/tag]` closing tags that
+//! style the enclosed text. Tags may be nested & must be closed in reverse order.
+//! A literal `[` is written `[[`.
+//!
+//! Built-in tags:
+//! * `[b]` bold, using the [`bold_font`](struct.Markup.html#method.bold_font) if set,
+//!   otherwise synthetic bold.
+//! * `[i]` italic, using the [`italic_font`](struct.Markup.html#method.italic_font) if set,
+//!   otherwise synthetic oblique.
+//! * `[color=#rrggbb]` or `[color=#rrggbbaa]` text color.
+//! * `[size=20]` pixel scale.
+//! * `[font=name]` font registered with [`font`](struct.Markup.html#method.font).
+//!
+//! Custom tags can be added with [`tag`](struct.Markup.html#method.tag).
+//!
+//! # Example
+//! ```
+//! use glyph_brush::{markup::Markup, FontId};
+//!
+//! let markup = Markup::new().font("mono", FontId(1));
+//!
+//! // word order can change in translations, styling goes with the words
+//! let section = markup
+//!     .parse("Press [font=mono][b]Enter[/b][/font] to [color=#ff0000]delete[/color]")
+//!     .unwrap();
+//!
+//! assert_eq!(section.text.len(), 4);
+//! assert_eq!(section.text[1].text, "Enter");
+//! assert_eq!(section.text[1].font_id, FontId(1));
+//! assert!(section.text[1].synthetic_style.bold);
+//! assert_eq!(section.text[3].extra.color, [1.0, 0.0, 0.0, 1.0]);
+//! ```
+use super::*;
+use std::{collections::HashMap, error::Error, fmt, sync::Arc};
+
+/// Style of a run of markup text.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextStyle {
+    /// Pixel scale of text. Defaults to 16.
+    pub scale: PxScale,
+    /// Font id of text. Defaults to `FontId(0)`.
+    pub font_id: FontId,
+    /// Synthetic bold & oblique styling. Defaults to none.
+    pub synthetic_style: SyntheticStyle,
+    /// Extra vertex data, e.g. color.
+    pub extra: Extra,
+}
+
+impl Default for TextStyle {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            scale: PxScale::from(16.0),
+            font_id: FontId::default(),
+            synthetic_style: SyntheticStyle::default(),
+            extra: Extra::default(),
+        }
+    }
+}
+
+impl TextStyle {
+    #[inline]
+    fn to_text(self, text: String) -> OwnedText {
+        OwnedText::new(text)
+            .with_scale(self.scale)
+            .with_font_id(self.font_id)
+            .with_synthetic_style(self.synthetic_style)
+            .with_extra(self.extra)
+    }
+}
+
+type TagFn = Arc<dyn Fn(&mut TextStyle) + Send + Sync>;
+
+/// Inline markup parser configuration, mapping tags to text styles.
+///
+/// See [module docs](index.html) for the markup syntax.
+///
+/// # Example
+/// ```
+/// use glyph_brush::{markup::Markup, FontId};
+///
+/// let markup = Markup::new()
+///     .bold_font(FontId(1))
+///     .tag("warning", |style| style.extra.color = [0.9, 0.6, 0.0, 1.0]);
+///
+/// let section = markup.parse("[warning]Low [b]battery[/b][/warning]").unwrap();
+/// assert_eq!(section.text[1].font_id, FontId(1));
+/// ```
+#[derive(Clone, Default)]
+pub struct Markup {
+    style: TextStyle,
+    bold_font: Option<FontId>,
+    italic_font: Option<FontId>,
+    fonts: HashMap<String, FontId>,
+    tags: HashMap<String, TagFn>,
+}
+
+impl fmt::Debug for Markup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Markup")
+            .field("style", &self.style)
+            .field("bold_font", &self.bold_font)
+            .field("italic_font", &self.italic_font)
+            .field("fonts", &self.fonts)
+            .field("tags", &self.tags.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl Markup {
+    /// Returns a new parser with default style & no registered fonts or custom tags.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the style of text outside of any tags.
+    #[inline]
+    pub fn with_style(mut self, style: TextStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Sets the font used by `[b]` tags, instead of synthetic bold.
+    #[inline]
+    pub fn bold_font(mut self, font_id: FontId) -> Self {
+        self.bold_font = Some(font_id);
+        self
+    }
+
+    /// Sets the font used by `[i]` tags, instead of synthetic oblique.
+    #[inline]
+    pub fn italic_font(mut self, font_id: FontId) -> Self {
+        self.italic_font = Some(font_id);
+        self
+    }
+
+    /// Registers a font `name` for use with `[font=name]` tags.
+    #[inline]
+    pub fn font<N: Into<String>>(mut self, name: N, font_id: FontId) -> Self {
+        self.fonts.insert(name.into(), font_id);
+        self
+    }
+
+    /// Registers a custom `[name]` tag that modifies the style of enclosed text.
+    ///
+    /// Custom tags take precedence over built-in tags of the same name.
+    pub fn tag<N, T>(mut self, name: N, tag: T) -> Self
+    where
+        N: Into<String>,
+        T: Fn(&mut TextStyle) + Send + Sync + 'static,
+    {
+        self.tags.insert(name.into(), Arc::new(tag));
+        self
+    }
+
+    /// Parses markup into a section with one text per style run.
+    pub fn parse(&self, markup: &str) -> Result<OwnedSection, MarkupError> {
+        let mut runs = Runs::default();
+        // open tags & the style before each was opened
+        let mut open: Vec<(&str, TextStyle)> = vec![];
+        let mut style = self.style;
+        let mut idx = 0;
+
+        while let Some(start) = markup[idx..].find('[').map(|i| idx + i) {
+            runs.push(&markup[idx..start], style);
+
+            if markup[start + 1..].starts_with('[') {
+                runs.push("[", style);
+                idx = start + 2;
+                continue;
+            }
+
+            let end = markup[start..]
+                .find(']')
+                .map(|i| start + i)
+                .ok_or(MarkupError::UnterminatedTag { position: start })?;
+            let tag = &markup[start + 1..end];
+            idx = end + 1;
+
+            if let Some(name) = tag.strip_prefix('/') {
+                match open.pop() {
+                    Some((open_name, previous)) if open_name == name => style = previous,
+                    _ => {
+                        return Err(MarkupError::UnexpectedClose {
+                            tag: name.into(),
+                            position: start,
+                        })
+                    }
+                }
+            } else {
+                let (name, value) = match tag.find('=') {
+                    Some(eq) => (&tag[..eq], Some(&tag[eq + 1..])),
+                    None => (tag, None),
+                };
+                open.push((name, style));
+                self.apply(name, value, &mut style)
+                    .map_err(|err| err.at(start))?;
+            }
+        }
+        runs.push(&markup[idx..], style);
+
+        if let Some((name, _)) = open.pop() {
+            return Err(MarkupError::Unclosed { tag: name.into() });
+        }
+
+        Ok(OwnedSection {
+            text: runs.into_texts(),
+            ..OwnedSection::default()
+        })
+    }
+
+    /// Applies an opening tag to the style.
+    fn apply(
+        &self,
+        name: &str,
+        value: Option<&str>,
+        style: &mut TextStyle,
+    ) -> Result<(), TagError> {
+        if let Some(tag) = self.tags.get(name) {
+            return match value {
+                None => {
+                    tag(style);
+                    Ok(())
+                }
+                Some(value) => Err(TagError::invalid(name, value)),
+            };
+        }
+
+        match (name, value) {
+            ("b", None) => match self.bold_font {
+                Some(font_id) => style.font_id = font_id,
+                None => style.synthetic_style.bold = true,
+            },
+            ("i", None) => match self.italic_font {
+                Some(font_id) => style.font_id = font_id,
+                None => style.synthetic_style.oblique = true,
+            },
+            ("color", Some(value)) => {
+                style.extra.color =
+                    parse_color(value).ok_or_else(|| TagError::invalid(name, value))?;
+            }
+            ("size", Some(value)) => {
+                let size: f32 = value
+                    .parse()
+                    .ok()
+                    .filter(|size: &f32| *size > 0.0 && size.is_finite())
+                    .ok_or_else(|| TagError::invalid(name, value))?;
+                style.scale = PxScale::from(size);
+            }
+            ("font", Some(value)) => {
+                style.font_id = *self
+                    .fonts
+                    .get(value)
+                    .ok_or_else(|| TagError::invalid(name, value))?;
+            }
+            ("b", Some(value)) | ("i", Some(value)) => return Err(TagError::invalid(name, value)),
+            ("color", None) | ("size", None) | ("font", None) => {
+                return Err(TagError::invalid(name, ""))
+            }
+            _ => return Err(TagError::Unknown(name.into())),
+        }
+        Ok(())
+    }
+}
+
+/// Parses `#rrggbb` or `#rrggbbaa` hex colors.
+fn parse_color(value: &str) -> Option<Color> {
+    let hex = value.strip_prefix('#')?;
+    if !(hex.len() == 6 || hex.len() == 8) || !hex.is_ascii() {
+        return None;
+    }
+
+    let mut color = [1.0; 4];
+    for (n, c) in color.iter_mut().take(hex.len() / 2).enumerate() {
+        let byte = u8::from_str_radix(&hex[n * 2..n * 2 + 2], 16).ok()?;
+        *c = f32::from(byte) / 255.0;
+    }
+    Some(color)
+}
+
+/// Style runs, merging adjacent runs of the same style.
+#[derive(Default)]
+struct Runs(Vec<(String, TextStyle)>);
+
+impl Runs {
+    fn push(&mut self, text: &str, style: TextStyle) {
+        if text.is_empty() {
+            return;
+        }
+        match self.0.last_mut() {
+            Some((last, last_style)) if *last_style == style => last.push_str(text),
+            _ => self.0.push((text.into(), style)),
+        }
+    }
+
+    fn into_texts(self) -> Vec<OwnedText> {
+        self.0
+            .into_iter()
+            .map(|(text, style)| style.to_text(text))
+            .collect()
+    }
+}
+
+/// Tag error without a markup position.
+enum TagError {
+    Unknown(String),
+    InvalidValue { tag: String, value: String },
+}
+
+impl TagError {
+    #[inline]
+    fn invalid(tag: &str, value: &str) -> Self {
+        Self::InvalidValue {
+            tag: tag.into(),
+            value: value.into(),
+        }
+    }
+
+    #[inline]
+    fn at(self, position: usize) -> MarkupError {
+        match self {
+            Self::Unknown(tag) => MarkupError::UnknownTag { tag, position },
+            Self::InvalidValue { tag, value } => MarkupError::InvalidValue {
+                tag,
+                value,
+                position,
+            },
+        }
+    }
+}
+
+/// Markup parsing error. Positions are byte indices of the tag in the markup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MarkupError {
+    /// A `[` without a closing `]`.
+    UnterminatedTag { position: usize },
+    /// A tag that is neither built-in nor registered with [`Markup::tag`].
+    UnknownTag { tag: String, position: usize },
+    /// A tag with a missing, unexpected or unparsable value, e.g. `[size=big]`.
+    InvalidValue {
+        tag: String,
+        value: String,
+        position: usize,
+    },
+    /// A closing tag that does not match the innermost open tag.
+    UnexpectedClose { tag: String, position: usize },
+    /// A tag that is not closed by the end of the markup.
+    Unclosed { tag: String },
+}
+
+impl fmt::Display for MarkupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnterminatedTag { position } => write!(f, "Unterminated tag at {}", position),
+            Self::UnknownTag { tag, position } => {
+                write!(f, "Unknown tag `{}` at {}", tag, position)
+            }
+            Self::InvalidValue {
+                tag,
+                value,
+                position,
+            } => write!(f, "Invalid `{}` value `{}` at {}", tag, value, position),
+            Self::UnexpectedClose { tag, position } => {
+                write!(f, "Unexpected closing tag `{}` at {}", tag, position)
+            }
+            Self::Unclosed { tag } => write!(f, "Unclosed tag `{}`", tag),
+        }
+    }
+}
+
+impl Error for MarkupError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn style_runs() {
+        let markup = Markup::new()
+            .with_style(TextStyle {
+                scale: PxScale::from(20.0),
+                ..TextStyle::default()
+            })
+            .font("mono", FontId(2))
+            .italic_font(FontId(3));
+
+        let section = markup
+            .parse("a [[b] [size=30]c[i]d[/i][/size][color=#00ff0080]e[/color][font=mono]f[/font]")
+            .unwrap();
+        let runs: Vec<_> = section
+            .text
+            .iter()
+            .map(|t| (t.text.as_str(), t.scale.y, t.font_id.0, t.extra.color))
+            .collect();
+
+        let black = [0.0, 0.0, 0.0, 1.0];
+        assert_eq!(
+            runs,
+            vec![
+                ("a [b] ", 20.0, 0, black),
+                ("c", 30.0, 0, black),
+                ("d", 30.0, 3, black),
+                ("e", 20.0, 0, [0.0, 1.0, 0.0, 128.0 / 255.0]),
+                ("f", 20.0, 2, black),
+            ]
+        );
+    }
+
+    #[test]
+    fn nested_styles_merge() {
+        let markup = Markup::new().tag("alert", |s| s.extra.color = [1.0, 0.0, 0.0, 1.0]);
+
+        let section = markup.parse("[alert][b]x[/b][b]y[/b]z[/alert]").unwrap();
+        assert_eq!(section.text.len(), 2);
+        assert_eq!(section.text[0].text, "xy");
+        assert!(section.text[0].synthetic_style.bold);
+        assert_eq!(section.text[1].text, "z");
+        assert!(!section.text[1].synthetic_style.bold);
+        assert_eq!(section.text[1].extra.color, [1.0, 0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn errors() {
+        let markup = Markup::new();
+        assert_eq!(
+            markup.parse("a [b"),
+            Err(MarkupError::UnterminatedTag { position: 2 })
+        );
+        assert_eq!(
+            markup.parse("[u]a[/u]"),
+            Err(MarkupError::UnknownTag {
+                tag: "u".into(),
+                position: 0
+            })
+        );
+        assert_eq!(
+            markup.parse("[size=big]a[/size]"),
+            Err(MarkupError::InvalidValue {
+                tag: "size".into(),
+                value: "big".into(),
+                position: 0
+            })
+        );
+        assert_eq!(
+            markup.parse("[b][i]a[/b][/i]"),
+            Err(MarkupError::UnexpectedClose {
+                tag: "b".into(),
+                position: 7
+            })
+        );
+        assert_eq!(
+            markup.parse("[b]a"),
+            Err(MarkupError::Unclosed { tag: "b".into() })
+        );
+    }
+}
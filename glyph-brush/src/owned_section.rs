@@ -2,6 +2,11 @@ use super::*;
 use std::{borrow::Cow, f32};
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(default, bound(deserialize = "X: serde::Deserialize<'de> + Default"))
+)]
 pub struct OwnedSection<X = Extra> {
     /// Position on screen to render text, in pixels from top-left. Defaults to (0, 0).
     pub screen_position: (f32, f32),
@@ -12,6 +17,11 @@ pub struct OwnedSection<X = Extra> {
     pub layout: Layout<BuiltInLineBreaker>,
     /// Text to render, rendered next to one another according the layout.
     pub text: Vec<OwnedText<X>>,
+    /// Column-major matrix transforming the section's glyph quads from screen pixel coordinates.
+    /// Defaults to `None`.
+    ///
+    /// See [`Section::transform`](struct.Section.html#structfield.transform).
+    pub transform: Option<[[f32; 4]; 4]>,
 }
 
 impl<X: Default> Default for OwnedSection<X> {
@@ -22,6 +32,7 @@ impl<X: Default> Default for OwnedSection<X> {
             bounds: (f32::INFINITY, f32::INFINITY),
             layout: Layout::default(),
             text: vec![],
+            transform: None,
         }
     }
 }
@@ -51,6 +62,13 @@ impl<X> OwnedSection<X> {
         self
     }
 
+    /// Sets the column-major matrix transforming the section's glyph quads.
+    #[inline]
+    pub fn with_transform(mut self, transform: [[f32; 4]; 4]) -> Self {
+        self.transform = Some(transform);
+        self
+    }
+
     #[inline]
     pub fn with_text<X2>(self, text: Vec<OwnedText<X2>>) -> OwnedSection<X2> {
         OwnedSection {
@@ -58,6 +76,7 @@ impl<X> OwnedSection<X> {
             screen_position: self.screen_position,
             bounds: self.bounds,
             layout: self.layout,
+            transform: self.transform,
         }
     }
 }
@@ -69,6 +88,7 @@ impl<X: Clone> OwnedSection<X> {
             bounds: self.bounds,
             layout: self.layout,
             text: self.text.iter().map(|t| t.into()).collect(),
+            transform: self.transform,
         }
     }
 }
@@ -86,10 +106,19 @@ impl<'a> From<&'a OwnedSection> for Cow<'a, Section<'a>> {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(default, bound(deserialize = "X: serde::Deserialize<'de> + Default"))
+)]
 pub struct OwnedText<X = Extra> {
     /// Text to render.
     pub text: String,
     /// Pixel scale of text. Defaults to 16.
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "glyph_brush_layout::serde_remote::PxScaleDef")
+    )]
     pub scale: PxScale,
     /// Font id to use for this section.
     ///
@@ -97,6 +126,10 @@ pub struct OwnedText<X = Extra> {
     /// either `FontId::default()` or the return of
     /// [`add_font`](struct.GlyphBrushBuilder.html#method.add_font).
     pub font_id: FontId,
+    /// Variable font axis settings applied to the font, e.g. `wght=650`. Defaults to none.
+    pub variations: Vec<FontVariation>,
+    /// Synthetic bold & oblique styling applied to glyphs. Defaults to none.
+    pub synthetic_style: SyntheticStyle,
     // Extra stuff for vertex generation.
     pub extra: X,
 }
@@ -120,12 +153,28 @@ impl<X> OwnedText<X> {
         self
     }
 
+    /// Sets variable font axis settings, e.g. `wght=650`.
+    #[inline]
+    pub fn with_variations<V: Into<Vec<FontVariation>>>(mut self, variations: V) -> Self {
+        self.variations = variations.into();
+        self
+    }
+
+    /// Sets synthetic bold & oblique styling.
+    #[inline]
+    pub fn with_synthetic_style(mut self, synthetic_style: SyntheticStyle) -> Self {
+        self.synthetic_style = synthetic_style;
+        self
+    }
+
     #[inline]
     pub fn with_extra<X2>(self, extra: X2) -> OwnedText<X2> {
         OwnedText {
             text: self.text,
             scale: self.scale,
             font_id: self.font_id,
+            variations: self.variations,
+            synthetic_style: self.synthetic_style,
             extra,
         }
     }
@@ -157,6 +206,8 @@ impl<X: Default> Default for OwnedText<X> {
             text: String::new(),
             scale: PxScale::from(16.0),
             font_id: <_>::default(),
+            variations: vec![],
+            synthetic_style: <_>::default(),
             extra: <_>::default(),
         }
     }
@@ -169,6 +220,8 @@ impl<'a, X: Clone> From<&'a OwnedText<X>> for Text<'a, X> {
             text: owned.text.as_str(),
             scale: owned.scale,
             font_id: owned.font_id,
+            variations: &owned.variations,
+            synthetic_style: owned.synthetic_style,
             extra: owned.extra.clone(),
         }
     }
@@ -181,7 +234,54 @@ impl<X: Clone> From<&Text<'_, X>> for OwnedText<X> {
             text: s.text.into(),
             scale: s.scale,
             font_id: s.font_id,
+            variations: s.variations.to_vec(),
+            synthetic_style: s.synthetic_style,
             extra: s.extra.clone(),
         }
     }
 }
+
+#[cfg(all(test, feature = "serde"))]
+mod serde_test {
+    use super::*;
+
+    #[test]
+    fn serde_roundtrip() {
+        let section = OwnedSection::default()
+            .with_screen_position((10.0, 20.5))
+            .with_bounds((300.0, 200.0))
+            .with_layout(
+                Layout::default_single_line()
+                    .h_align(HorizontalAlign::Center)
+                    .line_breaker(BuiltInLineBreaker::AnyCharLineBreaker),
+            )
+            .add_text(
+                OwnedText::new("Hello ")
+                    .with_scale(PxScale { x: 20.0, y: 24.0 })
+                    .with_color([0.5, 0.2, 0.1, 1.0]),
+            )
+            .add_text(OwnedText::new("world").with_font_id(FontId(1)).with_z(0.25));
+
+        let json = serde_json::to_string(&section).unwrap();
+        let deserialized: OwnedSection = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, section);
+    }
+
+    #[test]
+    fn serde_missing_fields_use_defaults() {
+        let section: OwnedSection = serde_json::from_str(
+            r#"{
+                "screen_position": [5.0, 10.0],
+                "text": [{ "text": "Hello", "extra": { "color": [1.0, 0.0, 0.0, 1.0] } }]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            section,
+            OwnedSection::default()
+                .with_screen_position((5.0, 10.0))
+                .add_text(OwnedText::new("Hello").with_color([1.0, 0.0, 0.0, 1.0]))
+        );
+    }
+}
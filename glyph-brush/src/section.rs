@@ -31,6 +31,13 @@ pub struct Section<'a, X = Extra> {
     pub layout: Layout<BuiltInLineBreaker>,
     /// Text to render, rendered next to one another according the layout.
     pub text: Vec<Text<'a, X>>,
+    /// Column-major matrix transforming the section's glyph quads from screen pixel coordinates,
+    /// provided to vertex generation as
+    /// [`GlyphVertex::transform`](struct.GlyphVertex.html#structfield.transform).
+    /// Allows rotating, scaling or placing text in world space. Defaults to `None`.
+    ///
+    /// Changing only the transform reuses the section layout.
+    pub transform: Option<[[f32; 4]; 4]>,
 }
 
 impl<X: Clone> Section<'_, X> {
@@ -38,6 +45,26 @@ impl<X: Clone> Section<'_, X> {
     pub(crate) fn clone_extras(&self) -> Vec<X> {
         self.text.iter().map(|t| &t.extra).cloned().collect()
     }
+
+    /// Returns the synthetic style of each text, or empty if no text is styled.
+    pub(crate) fn synthetic_styles(&self) -> Vec<SyntheticStyle> {
+        if self.text.iter().all(|t| t.synthetic_style.is_none()) {
+            return vec![];
+        }
+        self.text.iter().map(|t| t.synthetic_style).collect()
+    }
+
+    /// Returns this section with a line break appended to the last text, so empty text &
+    /// text ending in a line break lay out a final line. Returns `None` if there is no text.
+    pub(crate) fn with_final_line(&self) -> Option<Self> {
+        let last = self.text.last()?;
+        let mut section = self.clone();
+        section.text.push(Text {
+            text: "\n",
+            ..last.clone()
+        });
+        Some(section)
+    }
 }
 
 impl Default for Section<'static, Extra> {
@@ -55,6 +82,7 @@ impl<'a, X> Section<'a, X> {
             bounds: (f32::INFINITY, f32::INFINITY),
             layout: Layout::default(),
             text: vec![],
+            transform: None,
         }
     }
 }
@@ -84,6 +112,28 @@ impl<'a, X> Section<'a, X> {
         self
     }
 
+    /// Sets the column-major matrix transforming the section's glyph quads.
+    ///
+    /// # Example
+    /// ```
+    /// # use glyph_brush::*;
+    /// // rotate 90° clockwise around the screen position (100, 50)
+    /// let section = Section::default()
+    ///     .add_text(Text::new("Rotated"))
+    ///     .with_screen_position((100.0, 50.0))
+    ///     .with_transform([
+    ///         [0.0, 1.0, 0.0, 0.0],
+    ///         [-1.0, 0.0, 0.0, 0.0],
+    ///         [0.0, 0.0, 1.0, 0.0],
+    ///         [150.0, -50.0, 0.0, 1.0],
+    ///     ]);
+    /// ```
+    #[inline]
+    pub fn with_transform(mut self, transform: [[f32; 4]; 4]) -> Self {
+        self.transform = Some(transform);
+        self
+    }
+
     #[inline]
     pub fn with_text<X2>(self, text: Vec<Text<'_, X2>>) -> Section<'_, X2> {
         Section {
@@ -91,6 +141,7 @@ impl<'a, X> Section<'a, X> {
             screen_position: self.screen_position,
             bounds: self.bounds,
             layout: self.layout,
+            transform: self.transform,
         }
     }
 }
@@ -117,6 +168,7 @@ impl<X: Hash> Hash for Section<'_, X> {
             bounds: (bound_w, bound_h),
             layout,
             ref text,
+            ref transform,
         } = *self;
 
         let ord_floats: &[OrderedFloat<_>] = &[
@@ -131,6 +183,8 @@ impl<X: Hash> Hash for Section<'_, X> {
         hash_section_text(state, text);
 
         ord_floats.hash(state);
+
+        hash_transform(state, transform);
     }
 }
 
@@ -146,6 +200,14 @@ pub struct Text<'a, X = Extra> {
     /// It must be a valid id in the `FontMap` used for layout calls.
     /// The default `FontId(0)` should always be valid.
     pub font_id: FontId,
+    /// Variable font axis settings applied to the font, e.g. `wght=650`. Defaults to none.
+    ///
+    /// See [`VariableFontInstance`](trait.VariableFontInstance.html).
+    pub variations: &'a [FontVariation],
+    /// Synthetic bold & oblique styling applied to glyphs. Defaults to none.
+    ///
+    /// Useful for emphasis with fonts that have no bold or italic faces.
+    pub synthetic_style: SyntheticStyle,
     /// Extra stuff for vertex generation.
     pub extra: X,
 }
@@ -157,6 +219,8 @@ impl<X: Default> Default for Text<'static, X> {
             text: "",
             scale: PxScale::from(16.0),
             font_id: <_>::default(),
+            variations: &[],
+            synthetic_style: <_>::default(),
             extra: <_>::default(),
         }
     }
@@ -164,11 +228,16 @@ impl<X: Default> Default for Text<'static, X> {
 
 impl<'a, X> Text<'a, X> {
     #[inline]
-    pub fn with_text(self, text: &str) -> Text<'_, X> {
+    pub fn with_text<'b>(self, text: &'b str) -> Text<'b, X>
+    where
+        'a: 'b,
+    {
         Text {
             text,
             scale: self.scale,
             font_id: self.font_id,
+            variations: self.variations,
+            synthetic_style: self.synthetic_style,
             extra: self.extra,
         }
     }
@@ -185,12 +254,28 @@ impl<'a, X> Text<'a, X> {
         self
     }
 
+    /// Sets variable font axis settings, e.g. `wght=650`.
+    #[inline]
+    pub fn with_variations(mut self, variations: &'a [FontVariation]) -> Self {
+        self.variations = variations;
+        self
+    }
+
+    /// Sets synthetic bold & oblique styling.
+    #[inline]
+    pub fn with_synthetic_style(mut self, synthetic_style: SyntheticStyle) -> Self {
+        self.synthetic_style = synthetic_style;
+        self
+    }
+
     #[inline]
     pub fn with_extra<X2>(self, extra: X2) -> Text<'a, X2> {
         Text {
             text: self.text,
             scale: self.scale,
             font_id: self.font_id,
+            variations: self.variations,
+            synthetic_style: self.synthetic_style,
             extra,
         }
     }
@@ -222,6 +307,7 @@ impl<X> ToSectionText for Text<'_, X> {
             text: self.text,
             scale: self.scale,
             font_id: self.font_id,
+            synthetic_bold: self.synthetic_style.bold,
         }
     }
 }
@@ -233,12 +319,30 @@ fn hash_section_text<X: Hash, H: Hasher>(state: &mut H, text: &[Text<'_, X>]) {
             text,
             scale,
             font_id,
+            variations,
+            synthetic_style,
             ref extra,
         } = *t;
 
         let ord_floats: [OrderedFloat<_>; 2] = [scale.x.into(), scale.y.into()];
 
-        (text, font_id, extra, ord_floats).hash(state);
+        (
+            text,
+            font_id,
+            variations,
+            synthetic_style,
+            extra,
+            ord_floats,
+        )
+            .hash(state);
+    }
+}
+
+#[inline]
+fn hash_transform<H: Hasher>(state: &mut H, transform: &Option<[[f32; 4]; 4]>) {
+    transform.is_some().hash(state);
+    for &f in transform.iter().flatten().flatten() {
+        OrderedFloat(f).hash(state);
     }
 }
 
@@ -249,6 +353,7 @@ impl<'text, X: Clone> Section<'text, X> {
             bounds: self.bounds,
             layout: self.layout,
             text: self.text.iter().map(OwnedText::from).collect(),
+            transform: self.transform,
         }
     }
 
@@ -259,6 +364,7 @@ impl<'text, X: Clone> Section<'text, X> {
             bounds: (bound_w, bound_h),
             ref text,
             layout: _,
+            ref transform,
         } = *self;
 
         let geometry = [
@@ -268,7 +374,11 @@ impl<'text, X: Clone> Section<'text, X> {
             bound_h.into(),
         ];
 
-        HashableSectionParts { geometry, text }
+        HashableSectionParts {
+            geometry,
+            text,
+            transform,
+        }
     }
 }
 
@@ -285,6 +395,7 @@ impl<X> From<&Section<'_, X>> for SectionGeometry {
 pub(crate) struct HashableSectionParts<'a, X> {
     geometry: [OrderedFloat<f32>; 4],
     text: &'a [Text<'a, X>],
+    transform: &'a Option<[[f32; 4]; 4]>,
 }
 
 impl<X: Hash> HashableSectionParts<'_, X> {
@@ -300,12 +411,14 @@ impl<X: Hash> HashableSectionParts<'_, X> {
                 text,
                 scale,
                 font_id,
+                variations,
+                synthetic_style,
                 ..
             } = *t;
 
             let ord_floats: &[OrderedFloat<_>] = &[scale.x.into(), scale.y.into()];
 
-            (text, font_id, ord_floats).hash(state);
+            (text, font_id, variations, synthetic_style, ord_floats).hash(state);
         }
     }
 
@@ -313,4 +426,9 @@ impl<X: Hash> HashableSectionParts<'_, X> {
     pub fn hash_extra<H: Hasher>(&self, state: &mut H) {
         self.text.iter().for_each(|t| t.extra.hash(state));
     }
+
+    #[inline]
+    pub fn hash_transform<H: Hasher>(&self, state: &mut H) {
+        hash_transform(state, self.transform);
+    }
 }
@@ -0,0 +1,349 @@
+use super::*;
+use ordered_float::OrderedFloat;
+use rustc_hash::FxHashMap;
+use std::hash::{Hash, Hasher};
+
+/// A variable font axis setting, e.g. `wght=650`.
+///
+/// # Example
+/// ```
+/// use glyph_brush::{FontVariation, Text};
+///
+/// let bold_ish = [FontVariation::new(b"wght", 650.0)];
+/// let text = Text::new("Hello").with_variations(&bold_ish);
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FontVariation {
+    /// Axis tag, e.g. `*b"wght"`.
+    pub axis: [u8; 4],
+    /// Axis value, e.g. `650.0`.
+    pub value: f32,
+}
+
+impl FontVariation {
+    #[inline]
+    pub fn new(axis: &[u8; 4], value: f32) -> Self {
+        Self { axis: *axis, value }
+    }
+}
+
+impl Hash for FontVariation {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (self.axis, OrderedFloat::from(self.value)).hash(state)
+    }
+}
+
+impl PartialEq for FontVariation {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.axis == other.axis && OrderedFloat::from(self.value) == OrderedFloat::from(other.value)
+    }
+}
+
+impl Eq for FontVariation {}
+
+/// Font that can create new instances of itself with variable font axis settings applied.
+///
+/// Used to apply [`Text::variations`](struct.Text.html#structfield.variations).
+///
+/// Custom fonts without variation support can use the default implementation,
+/// e.g. `impl VariableFontInstance for MyFont {}`.
+pub trait VariableFontInstance: Font + Sized {
+    /// Returns a new instance of this font with the `variations` applied to both
+    /// metrics & outlines.
+    ///
+    /// Returns `None` if variations are not supported, in which case the
+    /// original font is used.
+    ///
+    /// The default implementation returns `None`.
+    #[inline]
+    fn with_variations(&self, _variations: &[FontVariation]) -> Option<Self> {
+        None
+    }
+}
+
+impl VariableFontInstance for FontRef<'_> {
+    #[inline]
+    fn with_variations(&self, variations: &[FontVariation]) -> Option<Self> {
+        let mut font = self.clone();
+        apply_variations(&mut font, variations);
+        Some(font)
+    }
+}
+
+/// Note: Creating an instance copies the font data, use `FontRef` to share it.
+///
+/// Returns `None` for font collections, e.g. `.ttc` files, holding more than one font as
+/// the collection index of the font is not known.
+impl VariableFontInstance for FontVec {
+    #[inline]
+    fn with_variations(&self, variations: &[FontVariation]) -> Option<Self> {
+        let data = self.font_data();
+        if is_multi_font_collection(data) {
+            return None;
+        }
+        let mut font = FontVec::try_from_vec(data.to_vec()).ok()?;
+        apply_variations(&mut font, variations);
+        Some(font)
+    }
+}
+
+/// Note: Creating an instance copies the font data, use `FontRef` to share it.
+///
+/// Returns `None` for font collections, e.g. `.ttc` files, holding more than one font as
+/// the collection index of the font is not known.
+impl VariableFontInstance for FontArc {
+    #[inline]
+    fn with_variations(&self, variations: &[FontVariation]) -> Option<Self> {
+        let data = self.font_data();
+        if is_multi_font_collection(data) {
+            return None;
+        }
+        let mut font = FontVec::try_from_vec(data.to_vec()).ok()?;
+        apply_variations(&mut font, variations);
+        Some(FontArc::new(font))
+    }
+}
+
+#[inline]
+fn apply_variations<F: VariableFont>(font: &mut F, variations: &[FontVariation]) {
+    for FontVariation { axis, value } in variations {
+        font.set_variation(axis, *value);
+    }
+}
+
+/// Returns true if the font data is a font collection with more than one font.
+#[inline]
+fn is_multi_font_collection(data: &[u8]) -> bool {
+    match data {
+        [b't', b't', b'c', b'f', _, _, _, _, n0, n1, n2, n3, ..] => {
+            u32::from_be_bytes([*n0, *n1, *n2, *n3]) > 1
+        }
+        _ => false,
+    }
+}
+
+/// Font instances created for text variations.
+///
+/// Instances have `FontId`s following the user fonts. Ids are stable while an instance
+/// exists, removed instance ids are only reused after [`trim`](#method.trim) has returned
+/// them so their cached glyphs & words can be evicted.
+///
+/// A `GlyphBrush` stores its user fonts here too, followed by the instances, so
+/// [`fonts`](#method.fonts) can be used by layouts & the draw cache directly.
+pub(crate) struct FontInstances<F> {
+    /// Stored user fonts followed by the instances.
+    fonts: Vec<F>,
+    /// Id of `fonts[0]`, the number of user fonts not stored here.
+    first_id: usize,
+    /// Number of user fonts stored in `fonts`.
+    stored_user_fonts: usize,
+    /// Instance id by base font id & variations.
+    ids: FxHashMap<FontId, FxHashMap<Box<[FontVariation]>, FontId>>,
+    /// Ids of removed instances.
+    free: Vec<FontId>,
+}
+
+impl<F> FontInstances<F> {
+    /// [`trim`](#method.trim) removes unused instances when holding more than this.
+    pub(crate) const MAX_INSTANCES: usize = 16;
+
+    /// Returns new instances storing the user fonts, followed by the instances.
+    #[inline]
+    pub(crate) fn with_user_fonts(fonts: Vec<F>) -> Self {
+        Self {
+            stored_user_fonts: fonts.len(),
+            fonts,
+            first_id: 0,
+            ids: <_>::default(),
+            free: vec![],
+        }
+    }
+
+    /// Returns new instances following `user_fonts_len` user fonts stored elsewhere.
+    #[inline]
+    pub(crate) fn following(user_fonts_len: usize) -> Self {
+        Self {
+            first_id: user_fonts_len,
+            ..Self::with_user_fonts(vec![])
+        }
+    }
+
+    /// Returns the stored fonts, indexable by any `FontId` if storing the user fonts.
+    #[inline]
+    pub(crate) fn fonts(&self) -> &[F] {
+        &self.fonts
+    }
+
+    /// Returns the stored user fonts.
+    #[inline]
+    pub(crate) fn user_fonts(&self) -> &[F] {
+        &self.fonts[..self.stored_user_fonts]
+    }
+
+    /// Returns the user fonts followed by the instances, indexable by any `FontId`.
+    ///
+    /// `user_fonts` are the user fonts not stored here.
+    #[inline]
+    pub(crate) fn all_fonts<'a>(&'a self, user_fonts: &'a [F]) -> Vec<&'a F> {
+        user_fonts.iter().chain(&self.fonts).collect()
+    }
+
+    /// Returns the user font, or font instance, for the id.
+    ///
+    /// `user_fonts` are the user fonts not stored here.
+    #[inline]
+    pub(crate) fn font<'a>(&'a self, user_fonts: &'a [F], font_id: FontId) -> &'a F {
+        match font_id.0.checked_sub(self.first_id) {
+            Some(idx) => &self.fonts[idx],
+            None => &user_fonts[font_id.0],
+        }
+    }
+
+    /// Number of user fonts, i.e. the first instance id.
+    #[inline]
+    fn user_fonts_len(&self) -> usize {
+        self.first_id + self.stored_user_fonts
+    }
+
+    /// Adds a stored user font, moving the instances along one.
+    ///
+    /// Returns the new id of the font & the new ids of the instances, if there are any.
+    pub(crate) fn add_user_font(&mut self, font: F) -> (FontId, Option<FontIdRemap>) {
+        let font_id = FontId(self.user_fonts_len());
+        self.fonts.insert(self.stored_user_fonts, font);
+        self.stored_user_fonts += 1;
+
+        if self.fonts.len() == self.stored_user_fonts {
+            return (font_id, None);
+        }
+        let remap = FontIdRemap {
+            from_len: font_id.0,
+            to_len: font_id.0 + 1,
+        };
+        for id in self.ids.values_mut().flat_map(|ids| ids.values_mut()) {
+            *id = remap.get(*id);
+        }
+        for id in &mut self.free {
+            *id = remap.get(*id);
+        }
+        (font_id, Some(remap))
+    }
+
+    /// If holding more than [`MAX_INSTANCES`](#associatedconstant.MAX_INSTANCES), removes
+    /// instances that are not `in_use`. Should be called regularly, e.g. once per frame.
+    ///
+    /// Returns the ids of removed instances, glyphs & words cached using them should be
+    /// evicted before they are reused.
+    pub(crate) fn trim<I>(&mut self, in_use: I) -> Vec<FontId>
+    where
+        I: IntoIterator<Item = FontId>,
+    {
+        let first_instance = self.user_fonts_len();
+        let instances = self.fonts.len() - self.stored_user_fonts;
+        if instances - self.free.len() <= Self::MAX_INSTANCES {
+            return vec![];
+        }
+
+        let mut used = vec![false; instances];
+        for font_id in in_use {
+            if let Some(idx) = font_id.0.checked_sub(first_instance) {
+                used[idx] = true;
+            }
+        }
+
+        let mut removed = vec![];
+        for ids in self.ids.values_mut() {
+            ids.retain(|_, id| {
+                let keep = used[id.0 - first_instance];
+                if !keep {
+                    removed.push(*id);
+                }
+                keep
+            });
+        }
+        self.ids.retain(|_, ids| !ids.is_empty());
+        self.free.extend(&removed);
+
+        // drop removed instances at the end
+        self.free.sort_unstable_by_key(|id| id.0);
+        while self.free.last().map(|id| id.0 + 1 - self.first_id) == Some(self.fonts.len()) {
+            self.free.pop();
+            self.fonts.pop();
+        }
+        removed
+    }
+}
+
+impl<F: VariableFontInstance> FontInstances<F> {
+    /// Returns the section text with font ids that reference font instances for any
+    /// text variations. Missing instances are created.
+    ///
+    /// `user_fonts` are the user fonts not stored here.
+    pub(crate) fn section_text<'a, X>(
+        &mut self,
+        user_fonts: &[F],
+        text: &'a [Text<'a, X>],
+    ) -> Vec<SectionText<'a>> {
+        text.iter()
+            .map(|t| {
+                let mut section_text = t.to_section_text();
+                if !t.variations.is_empty() {
+                    if let Some(id) = self.instance_id(user_fonts, t.font_id, t.variations) {
+                        section_text.font_id = id;
+                    }
+                }
+                section_text
+            })
+            .collect()
+    }
+
+    /// Returns the id of the font instance, creating it if necessary.
+    fn instance_id(
+        &mut self,
+        user_fonts: &[F],
+        font_id: FontId,
+        variations: &[FontVariation],
+    ) -> Option<FontId> {
+        if let Some(id) = self.ids.get(&font_id).and_then(|ids| ids.get(variations)) {
+            return Some(*id);
+        }
+
+        let instance = self.font(user_fonts, font_id).with_variations(variations)?;
+        let id = match self.free.pop() {
+            Some(id) => {
+                self.fonts[id.0 - self.first_id] = instance;
+                id
+            }
+            None => {
+                self.fonts.push(instance);
+                FontId(self.first_id + self.fonts.len() - 1)
+            }
+        };
+        self.ids
+            .entry(font_id)
+            .or_default()
+            .insert(variations.into(), id);
+        Some(id)
+    }
+}
+
+/// New `FontId`s of font instances after a user font is added.
+#[derive(Debug)]
+pub(crate) struct FontIdRemap {
+    from_len: usize,
+    to_len: usize,
+}
+
+impl FontIdRemap {
+    /// Returns the new id of the font.
+    #[inline]
+    pub(crate) fn get(&self, font_id: FontId) -> FontId {
+        match font_id.0.checked_sub(self.from_len) {
+            None => font_id,
+            Some(idx) => FontId(self.to_len + idx),
+        }
+    }
+}
@@ -1,6 +1,12 @@
-use super::{BuiltInLineBreaker, GlyphPositioner, LineBreaker, SectionGeometry, ToSectionText};
-use crate::{characters::Characters, GlyphChange, SectionGlyph};
+use super::{
+    BuiltInLineBreaker, GlyphPositioner, LineBreaker, ParagraphFormat, SectionGeometry,
+    ToSectionText,
+};
+use crate::{
+    characters::Characters, lines::Line, GlyphChange, SectionGlyph, SectionLine, WordCache,
+};
 use ab_glyph::*;
+use std::mem;
 
 /// Built-in [`GlyphPositioner`](trait.GlyphPositioner.html) implementations.
 ///
@@ -13,6 +19,7 @@ use ab_glyph::*;
 /// let layout = Layout::default().h_align(HorizontalAlign::Right);
 /// ```
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Layout<L: LineBreaker> {
     /// Renders a single line from left-to-right according to the inner alignment.
     /// Hard breaking will end the line, partially hitting the width bound will end the line.
@@ -20,6 +27,14 @@ pub enum Layout<L: LineBreaker> {
         line_breaker: L,
         h_align: HorizontalAlign,
         v_align: VerticalAlign,
+        #[cfg_attr(feature = "serde", serde(default))]
+        white_space: WhiteSpace,
+        #[cfg_attr(feature = "serde", serde(default))]
+        overflow_wrap: OverflowWrap,
+        #[cfg_attr(feature = "serde", serde(default))]
+        anchor: AlignAnchor,
+        #[cfg_attr(feature = "serde", serde(default))]
+        pixel_snap: bool,
     },
     /// Renders multiple lines from left-to-right according to the inner alignment.
     /// Hard breaking characters will cause advancement to another line.
@@ -28,6 +43,16 @@ pub enum Layout<L: LineBreaker> {
         line_breaker: L,
         h_align: HorizontalAlign,
         v_align: VerticalAlign,
+        #[cfg_attr(feature = "serde", serde(default))]
+        white_space: WhiteSpace,
+        #[cfg_attr(feature = "serde", serde(default))]
+        overflow_wrap: OverflowWrap,
+        #[cfg_attr(feature = "serde", serde(default))]
+        paragraph: ParagraphFormat,
+        #[cfg_attr(feature = "serde", serde(default))]
+        anchor: AlignAnchor,
+        #[cfg_attr(feature = "serde", serde(default))]
+        pixel_snap: bool,
     },
 }
 
@@ -45,6 +70,10 @@ impl Layout<BuiltInLineBreaker> {
             line_breaker: BuiltInLineBreaker::default(),
             h_align: HorizontalAlign::Left,
             v_align: VerticalAlign::Top,
+            white_space: WhiteSpace::default(),
+            overflow_wrap: OverflowWrap::default(),
+            anchor: AlignAnchor::default(),
+            pixel_snap: false,
         }
     }
 
@@ -54,124 +83,300 @@ impl Layout<BuiltInLineBreaker> {
             line_breaker: BuiltInLineBreaker::default(),
             h_align: HorizontalAlign::Left,
             v_align: VerticalAlign::Top,
+            white_space: WhiteSpace::default(),
+            overflow_wrap: OverflowWrap::default(),
+            paragraph: ParagraphFormat::default(),
+            anchor: AlignAnchor::default(),
+            pixel_snap: false,
         }
     }
 }
 
 impl<L: LineBreaker> Layout<L> {
     /// Returns an identical `Layout` but with the input `h_align`
-    pub fn h_align(self, h_align: HorizontalAlign) -> Self {
-        use crate::Layout::*;
-        match self {
-            SingleLine {
-                line_breaker,
-                v_align,
-                ..
-            } => SingleLine {
-                line_breaker,
-                v_align,
-                h_align,
-            },
-            Wrap {
-                line_breaker,
-                v_align,
-                ..
-            } => Wrap {
-                line_breaker,
-                v_align,
-                h_align,
-            },
+    pub fn h_align(mut self, h_align: HorizontalAlign) -> Self {
+        match &mut self {
+            Layout::SingleLine { h_align: h, .. } | Layout::Wrap { h_align: h, .. } => *h = h_align,
         }
+        self
     }
 
     /// Returns an identical `Layout` but with the input `v_align`
-    pub fn v_align(self, v_align: VerticalAlign) -> Self {
+    pub fn v_align(mut self, v_align: VerticalAlign) -> Self {
+        match &mut self {
+            Layout::SingleLine { v_align: v, .. } | Layout::Wrap { v_align: v, .. } => *v = v_align,
+        }
+        self
+    }
+
+    /// Returns an identical `Layout` but with the input `white_space` handling.
+    ///
+    /// # Example
+    /// ```
+    /// # use glyph_brush_layout::*;
+    /// // collapse whitespace runs & treat newlines as spaces, like html
+    /// let layout = Layout::default().white_space(WhiteSpace::Normal);
+    /// ```
+    pub fn white_space(mut self, white_space: WhiteSpace) -> Self {
+        match &mut self {
+            Layout::SingleLine { white_space: w, .. } | Layout::Wrap { white_space: w, .. } => {
+                *w = white_space
+            }
+        }
+        self
+    }
+
+    /// Returns an identical `Layout` but with the input `overflow_wrap` handling.
+    ///
+    /// # Example
+    /// ```
+    /// # use glyph_brush_layout::*;
+    /// // break long words, like urls, that would otherwise overflow the width bound
+    /// let layout = Layout::default().overflow_wrap(OverflowWrap::BreakWord);
+    /// ```
+    pub fn overflow_wrap(mut self, overflow_wrap: OverflowWrap) -> Self {
+        match &mut self {
+            Layout::SingleLine {
+                overflow_wrap: o, ..
+            }
+            | Layout::Wrap {
+                overflow_wrap: o, ..
+            } => *o = overflow_wrap,
+        }
+        self
+    }
+
+    /// Returns an identical `Layout` but with the input `paragraph` formatting.
+    ///
+    /// Only applies to [`Layout::Wrap`](#variant.Wrap), has no effect on single line layouts.
+    ///
+    /// # Example
+    /// ```
+    /// # use glyph_brush_layout::*;
+    /// // indent the first line of each paragraph
+    /// let layout = Layout::default().paragraph(ParagraphFormat {
+    ///     first_line_indent: 30.0,
+    ///     ..ParagraphFormat::default()
+    /// });
+    /// ```
+    pub fn paragraph(mut self, paragraph: ParagraphFormat) -> Self {
+        if let Layout::Wrap { paragraph: p, .. } = &mut self {
+            *p = paragraph;
+        }
+        self
+    }
+
+    /// Returns an identical `Layout` but with the input alignment `anchor`.
+    ///
+    /// # Example
+    /// ```
+    /// # use glyph_brush_layout::*;
+    /// // center text inside the bounds box, which has its top-left at the screen position
+    /// let layout = Layout::default()
+    ///     .h_align(HorizontalAlign::Center)
+    ///     .v_align(VerticalAlign::Center)
+    ///     .anchor(AlignAnchor::Bounds);
+    /// ```
+    pub fn anchor(mut self, anchor: AlignAnchor) -> Self {
+        match &mut self {
+            Layout::SingleLine { anchor: a, .. } | Layout::Wrap { anchor: a, .. } => *a = anchor,
+        }
+        self
+    }
+
+    /// Returns an identical `Layout` but with the input `pixel_snap` setting.
+    ///
+    /// Pixel snapped layouts round glyph origins & baselines to whole pixels, and line heights
+    /// to whole pixels, so text looks the same at any screen position. Useful for crisp UI text
+    /// at small sizes, avoiding shimmering when labels move by fractional pixels.
+    ///
+    /// # Example
+    /// ```
+    /// # use glyph_brush_layout::*;
+    /// let layout = Layout::default().pixel_snap(true);
+    /// ```
+    pub fn pixel_snap(mut self, pixel_snap: bool) -> Self {
+        match &mut self {
+            Layout::SingleLine { pixel_snap: p, .. } | Layout::Wrap { pixel_snap: p, .. } => {
+                *p = pixel_snap
+            }
+        }
+        self
+    }
+
+    /// Returns an identical `Layout` but with the input `line_breaker`
+    pub fn line_breaker<L2: LineBreaker>(self, line_breaker: L2) -> Layout<L2> {
         use crate::Layout::*;
         match self {
             SingleLine {
-                line_breaker,
                 h_align,
+                v_align,
+                white_space,
+                overflow_wrap,
+                anchor,
+                pixel_snap,
                 ..
             } => SingleLine {
                 line_breaker,
                 v_align,
                 h_align,
+                white_space,
+                overflow_wrap,
+                anchor,
+                pixel_snap,
             },
             Wrap {
-                line_breaker,
                 h_align,
+                v_align,
+                white_space,
+                overflow_wrap,
+                paragraph,
+                anchor,
+                pixel_snap,
                 ..
             } => Wrap {
                 line_breaker,
                 v_align,
                 h_align,
+                white_space,
+                overflow_wrap,
+                paragraph,
+                anchor,
+                pixel_snap,
             },
         }
     }
+}
 
-    /// Returns an identical `Layout` but with the input `line_breaker`
-    pub fn line_breaker<L2: LineBreaker>(self, line_breaker: L2) -> Layout<L2> {
-        use crate::Layout::*;
-        match self {
-            SingleLine {
-                h_align, v_align, ..
-            } => SingleLine {
-                line_breaker,
-                v_align,
-                h_align,
-            },
+impl<L: LineBreaker> Layout<L> {
+    /// Returns true if glyphs & lines are snapped to whole pixels.
+    #[inline]
+    pub(crate) fn is_pixel_snapped(&self) -> bool {
+        match *self {
+            Layout::SingleLine { pixel_snap, .. } | Layout::Wrap { pixel_snap, .. } => pixel_snap,
+        }
+    }
+
+    /// Returns the distance between the baselines of a line & the next line.
+    #[inline]
+    pub(crate) fn line_advance(&self, line: &Line) -> f32 {
+        match self.is_pixel_snapped() {
+            true => line.line_height().round(),
+            false => line.line_height(),
+        }
+    }
+
+    /// Returns the layout & geometry with the screen position moved to the alignment
+    /// anchor point. Anchored to the bounds, an unbounded axis is aligned `Left`/`Top`.
+    #[inline]
+    pub(crate) fn anchored(&self, geometry: &SectionGeometry) -> (Self, SectionGeometry) {
+        use crate::Layout::{SingleLine, Wrap};
+
+        match *self {
             Wrap {
-                h_align, v_align, ..
-            } => Wrap {
-                line_breaker,
+                h_align,
                 v_align,
+                anchor: AlignAnchor::Bounds,
+                ..
+            }
+            | SingleLine {
                 h_align,
-            },
+                v_align,
+                anchor: AlignAnchor::Bounds,
+                ..
+            } => {
+                let (x, y) = geometry.screen_position;
+                let (bound_w, bound_h) = geometry.bounds;
+                let h_align = match bound_w.is_finite() {
+                    true => h_align,
+                    false => HorizontalAlign::Left,
+                };
+                let v_align = match bound_h.is_finite() {
+                    true => v_align,
+                    false => VerticalAlign::Top,
+                };
+                let geometry = SectionGeometry {
+                    screen_position: (
+                        x + h_align.box_offset(bound_w),
+                        y + v_align.box_offset(bound_h),
+                    ),
+                    ..*geometry
+                };
+                (self.h_align(h_align).v_align(v_align), geometry)
+            }
+            _ => (*self, *geometry),
         }
     }
-}
 
-impl<L: LineBreaker> GlyphPositioner for Layout<L> {
-    fn calculate_glyphs<F, S>(
+    /// Calculates glyphs & lines, optionally using a `WordCache`.
+    fn layout_glyphs<F, S>(
         &self,
         fonts: &[F],
         geometry: &SectionGeometry,
         sections: &[S],
-    ) -> Vec<SectionGlyph>
+        word_cache: Option<&mut WordCache>,
+    ) -> (Vec<SectionGlyph>, Vec<SectionLine>)
     where
         F: Font,
         S: ToSectionText,
     {
         use crate::Layout::{SingleLine, Wrap};
 
+        let (layout, geometry) = self.anchored(geometry);
         let SectionGeometry {
             screen_position,
             bounds: (bound_w, bound_h),
             ..
-        } = *geometry;
+        } = geometry;
 
-        match *self {
+        let (mut glyphs, mut lines) = match layout {
             SingleLine {
                 h_align,
                 v_align,
                 line_breaker,
-            } => Characters::new(
-                fonts,
-                sections.iter().map(|s| s.to_section_text()),
-                line_breaker,
-            )
-            .words()
-            .lines(bound_w)
-            .next()
-            .map(|line| line.aligned_on_screen(screen_position, h_align, v_align))
-            .unwrap_or_default(),
+                white_space,
+                overflow_wrap,
+                ..
+            } => {
+                let line = Characters::new(
+                    fonts,
+                    sections.iter().map(|s| s.to_section_text()),
+                    line_breaker,
+                    white_space,
+                )
+                .words(word_cache)
+                .lines(
+                    white_space.wrap_width(bound_w),
+                    overflow_wrap,
+                    ParagraphFormat::default(),
+                )
+                .next();
+
+                match line {
+                    Some(line) => {
+                        let (glyphs, rect) =
+                            line.aligned_on_screen(screen_position, h_align, v_align);
+                        let line = SectionLine {
+                            glyphs: 0..glyphs.len(),
+                            rect,
+                        };
+                        (glyphs, vec![line])
+                    }
+                    None => <_>::default(),
+                }
+            }
 
             Wrap {
                 h_align,
                 v_align,
                 line_breaker,
+                white_space,
+                overflow_wrap,
+                paragraph,
+                ..
             } => {
                 let mut out = vec![];
+                let mut out_lines = vec![];
                 let mut caret = screen_position;
                 let v_align_top = v_align == VerticalAlign::Top;
 
@@ -179,74 +384,206 @@ impl<L: LineBreaker> GlyphPositioner for Layout<L> {
                     fonts,
                     sections.iter().map(|s| s.to_section_text()),
                     line_breaker,
+                    white_space,
                 )
-                .words()
-                .lines(bound_w);
+                .words(word_cache)
+                .lines(white_space.wrap_width(bound_w), overflow_wrap, paragraph);
 
                 for line in lines {
+                    caret.1 += paragraph
+                        .baseline_snap(caret.1 - screen_position.1 + line.max_v_metrics.ascent);
+
                     // top align can bound check & exit early
                     if v_align_top && caret.1 >= screen_position.1 + bound_h {
                         break;
                     }
 
-                    let line_height = line.line_height();
-                    out.extend(line.aligned_on_screen(caret, h_align, VerticalAlign::Top));
+                    let line_height = self.line_advance(&line);
+                    let line_x = caret.0 + paragraph.align_shift(h_align, line.indent);
+                    let (glyphs, rect) =
+                        line.aligned_on_screen((line_x, caret.1), h_align, VerticalAlign::Top);
+                    out_lines.push(SectionLine {
+                        glyphs: out.len()..out.len() + glyphs.len(),
+                        rect,
+                    });
+                    out.extend(glyphs);
                     caret.1 += line_height;
                 }
 
-                if !out.is_empty() {
-                    match v_align {
-                        // already aligned
-                        VerticalAlign::Top => {}
-                        // convert from top
-                        VerticalAlign::Center | VerticalAlign::Bottom => {
-                            let shift_up = if v_align == VerticalAlign::Center {
-                                (caret.1 - screen_position.1) / 2.0
-                            } else {
-                                caret.1 - screen_position.1
-                            };
-
-                            let (min_x, max_x) = h_align.x_bounds(screen_position.0, bound_w);
-                            let (min_y, max_y) = v_align.y_bounds(screen_position.1, bound_h);
-
-                            out = out
-                                .drain(..)
-                                .filter_map(|mut sg| {
-                                    // shift into position
-                                    sg.glyph.position.y -= shift_up;
-
-                                    // filter away out-of-bounds glyphs
-                                    let sfont = fonts[sg.font_id].as_scaled(sg.glyph.scale);
-                                    let h_advance = sfont.h_advance(sg.glyph.id);
-                                    let h_side_bearing = sfont.h_side_bearing(sg.glyph.id);
-                                    let height = sfont.height();
-
-                                    Some(sg).filter(|sg| {
-                                        sg.glyph.position.x - h_side_bearing <= max_x
-                                            && sg.glyph.position.x + h_advance >= min_x
-                                            && sg.glyph.position.y - height <= max_y
-                                            && sg.glyph.position.y + height >= min_y
+                match v_align {
+                    // already aligned
+                    VerticalAlign::Top => {}
+                    // convert from top
+                    VerticalAlign::Center | VerticalAlign::Bottom => {
+                        let shift_up = if v_align == VerticalAlign::Center {
+                            (caret.1 - screen_position.1) / 2.0
+                        } else {
+                            caret.1 - screen_position.1
+                        };
+
+                        let (min_x, max_x) = h_align.x_bounds(screen_position.0, bound_w);
+                        let (min_y, max_y) = v_align.y_bounds(screen_position.1, bound_h);
+
+                        let in_bounds = |sg: &SectionGlyph| {
+                            let sfont = fonts[sg.font_id].as_scaled(sg.glyph.scale);
+                            let h_advance = sfont.h_advance(sg.glyph.id);
+                            let h_side_bearing = sfont.h_side_bearing(sg.glyph.id);
+                            let height = sfont.height();
+
+                            sg.glyph.position.x - h_side_bearing <= max_x
+                                && sg.glyph.position.x + h_advance >= min_x
+                                && sg.glyph.position.y - height <= max_y
+                                && sg.glyph.position.y + height >= min_y
+                        };
+
+                        let mut glyphs = mem::take(&mut out).into_iter();
+                        for mut line in mem::take(&mut out_lines) {
+                            let line_glyphs = glyphs.by_ref().take(line.glyphs.len());
+                            let start = out.len();
+                            // shift into position & filter away out-of-bounds glyphs
+                            out.extend(
+                                line_glyphs
+                                    .map(|mut sg| {
+                                        sg.glyph.position.y -= shift_up;
+                                        sg
                                     })
-                                })
-                                .collect();
+                                    .filter(in_bounds),
+                            );
+
+                            line.rect.min.y -= shift_up;
+                            line.rect.max.y -= shift_up;
+                            if line.rect.max.y >= min_y && line.rect.min.y <= max_y {
+                                line.glyphs = start..out.len();
+                                out_lines.push(line);
+                            }
                         }
                     }
                 }
 
-                out
+                (out, out_lines)
+            }
+        };
+
+        if self.is_pixel_snapped() {
+            snap_to_pixels(&mut glyphs, &mut lines);
+        }
+        (glyphs, lines)
+    }
+
+    /// Recalculates glyphs & lines, optionally using a `WordCache`.
+    #[allow(clippy::float_cmp, clippy::too_many_arguments)]
+    fn relayout_glyphs<F, S, P>(
+        &self,
+        previous: P,
+        previous_lines: Vec<SectionLine>,
+        change: GlyphChange,
+        fonts: &[F],
+        geometry: &SectionGeometry,
+        sections: &[S],
+        word_cache: Option<&mut WordCache>,
+    ) -> (Vec<SectionGlyph>, Vec<SectionLine>)
+    where
+        F: Font,
+        S: ToSectionText,
+        P: IntoIterator<Item = SectionGlyph>,
+    {
+        let adjustment = match change {
+            GlyphChange::Geometry(old) if old.bounds == geometry.bounds => Some(point(
+                geometry.screen_position.0 - old.screen_position.0,
+                geometry.screen_position.1 - old.screen_position.1,
+            )),
+            _ => None,
+        }
+        // pixel snapped glyphs may only be moved by whole pixels
+        .filter(|a| !self.is_pixel_snapped() || (a.x.fract() == 0.0 && a.y.fract() == 0.0));
+
+        match adjustment {
+            Some(adjustment) => {
+                // position change
+                let mut glyphs: Vec<_> = previous.into_iter().collect();
+                glyphs
+                    .iter_mut()
+                    .for_each(|sg| sg.glyph.position += adjustment);
+
+                let mut lines = previous_lines;
+                for line in &mut lines {
+                    line.rect.min += adjustment;
+                    line.rect.max += adjustment;
+                }
+                (glyphs, lines)
             }
+            None => self.layout_glyphs(fonts, geometry, sections, word_cache),
+        }
+    }
+}
+
+/// Rounds line origins, glyph origins relative to the line & baselines to whole pixels,
+/// so a line looks the same at any fractional position.
+pub(crate) fn snap_to_pixels(glyphs: &mut [SectionGlyph], lines: &mut [SectionLine]) {
+    for line in lines {
+        let origin = line.rect.min;
+        let snapped_x = origin.x.round();
+        // empty lines snap their top
+        let mut shift_y = origin.y.round() - origin.y;
+        for sg in &mut glyphs[line.glyphs.clone()] {
+            let position = &mut sg.glyph.position;
+            shift_y = position.y.round() - position.y;
+            *position = point(
+                snapped_x + (position.x - origin.x).round(),
+                position.y.round(),
+            );
         }
+
+        let Rect { min, max } = &mut line.rect;
+        *max = point(
+            snapped_x + (max.x - origin.x).round(),
+            (max.y + shift_y).round(),
+        );
+        *min = point(snapped_x, (min.y + shift_y).round());
+    }
+}
+
+impl<L: LineBreaker> GlyphPositioner for Layout<L> {
+    #[inline]
+    fn calculate_glyphs<F, S>(
+        &self,
+        fonts: &[F],
+        geometry: &SectionGeometry,
+        sections: &[S],
+    ) -> Vec<SectionGlyph>
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        self.layout_glyphs(fonts, geometry, sections, None).0
+    }
+
+    #[inline]
+    fn calculate_glyphs_cached<F, S>(
+        &self,
+        fonts: &[F],
+        geometry: &SectionGeometry,
+        sections: &[S],
+        word_cache: &mut WordCache,
+    ) -> Vec<SectionGlyph>
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        self.layout_glyphs(fonts, geometry, sections, Some(word_cache))
+            .0
     }
 
     fn bounds_rect(&self, geometry: &SectionGeometry) -> Rect {
         use crate::Layout::{SingleLine, Wrap};
 
+        let (layout, geometry) = self.anchored(geometry);
         let SectionGeometry {
             screen_position: (screen_x, screen_y),
             bounds: (bound_w, bound_h),
-        } = *geometry;
+        } = geometry;
 
-        let (h_align, v_align) = match *self {
+        let (h_align, v_align) = match layout {
             Wrap {
                 h_align, v_align, ..
             }
@@ -264,7 +601,7 @@ impl<L: LineBreaker> GlyphPositioner for Layout<L> {
         }
     }
 
-    #[allow(clippy::float_cmp)]
+    #[inline]
     fn recalculate_glyphs<F, S, P>(
         &self,
         previous: P,
@@ -278,27 +615,83 @@ impl<L: LineBreaker> GlyphPositioner for Layout<L> {
         S: ToSectionText,
         P: IntoIterator<Item = SectionGlyph>,
     {
-        match change {
-            GlyphChange::Geometry(old) if old.bounds == geometry.bounds => {
-                // position change
-                let adjustment = point(
-                    geometry.screen_position.0 - old.screen_position.0,
-                    geometry.screen_position.1 - old.screen_position.1,
-                );
+        self.relayout_glyphs(previous, vec![], change, fonts, geometry, sections, None)
+            .0
+    }
 
-                let mut glyphs: Vec<_> = previous.into_iter().collect();
-                glyphs
-                    .iter_mut()
-                    .for_each(|sg| sg.glyph.position += adjustment);
-                glyphs
-            }
-            _ => self.calculate_glyphs(fonts, geometry, sections),
-        }
+    #[inline]
+    fn recalculate_glyphs_cached<F, S, P>(
+        &self,
+        previous: P,
+        change: GlyphChange,
+        fonts: &[F],
+        geometry: &SectionGeometry,
+        sections: &[S],
+        word_cache: &mut WordCache,
+    ) -> Vec<SectionGlyph>
+    where
+        F: Font,
+        S: ToSectionText,
+        P: IntoIterator<Item = SectionGlyph>,
+    {
+        self.relayout_glyphs(
+            previous,
+            vec![],
+            change,
+            fonts,
+            geometry,
+            sections,
+            Some(word_cache),
+        )
+        .0
+    }
+
+    #[inline]
+    fn calculate_glyphs_and_lines<F, S>(
+        &self,
+        fonts: &[F],
+        geometry: &SectionGeometry,
+        sections: &[S],
+        word_cache: &mut WordCache,
+    ) -> (Vec<SectionGlyph>, Vec<SectionLine>)
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        self.layout_glyphs(fonts, geometry, sections, Some(word_cache))
+    }
+
+    #[inline]
+    fn recalculate_glyphs_and_lines<F, S, P>(
+        &self,
+        previous: P,
+        previous_lines: Vec<SectionLine>,
+        change: GlyphChange,
+        fonts: &[F],
+        geometry: &SectionGeometry,
+        sections: &[S],
+        word_cache: &mut WordCache,
+    ) -> (Vec<SectionGlyph>, Vec<SectionLine>)
+    where
+        F: Font,
+        S: ToSectionText,
+        P: IntoIterator<Item = SectionGlyph>,
+    {
+        self.relayout_glyphs(
+            previous,
+            previous_lines,
+            change,
+            fonts,
+            geometry,
+            sections,
+            Some(word_cache),
+        )
     }
 }
 
 /// Describes horizontal alignment preference for positioning & bounds.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum HorizontalAlign {
     /// Leftmost character is immediately to the right of the render position.<br/>
     /// Bounds start from the render position and advance rightwards.
@@ -322,11 +715,22 @@ impl HorizontalAlign {
 
         (min.floor(), max.ceil())
     }
+
+    /// Returns the x offset of this alignment's anchor from the left of a box of `bound_w`.
+    #[inline]
+    pub(crate) fn box_offset(self, bound_w: f32) -> f32 {
+        match self {
+            HorizontalAlign::Left => 0.0,
+            HorizontalAlign::Center => bound_w / 2.0,
+            HorizontalAlign::Right => bound_w,
+        }
+    }
 }
 
 /// Describes vertical alignment preference for positioning & bounds. Currently a placeholder
 /// for future functionality.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum VerticalAlign {
     /// Characters/bounds start underneath the render position and progress downwards.
     Top,
@@ -347,6 +751,113 @@ impl VerticalAlign {
 
         (min.floor(), max.ceil())
     }
+
+    /// Returns the y offset of this alignment's anchor from the top of a box of `bound_h`.
+    #[inline]
+    pub(crate) fn box_offset(self, bound_h: f32) -> f32 {
+        match self {
+            VerticalAlign::Top => 0.0,
+            VerticalAlign::Center => bound_h / 2.0,
+            VerticalAlign::Bottom => bound_h,
+        }
+    }
+}
+
+/// Describes what [`HorizontalAlign`](enum.HorizontalAlign.html) &
+/// [`VerticalAlign`](enum.VerticalAlign.html) position text relative to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AlignAnchor {
+    /// Text is aligned around the screen position, e.g. `HorizontalAlign::Center` centers
+    /// text on the screen position & the bounds extend equally left & right of it.
+    ScreenPosition,
+    /// The screen position is the top-left corner of the bounds & text is aligned within
+    /// the bounds box, e.g. `HorizontalAlign::Center` centers text in the box, like a CSS box.
+    ///
+    /// An infinite bound aligns as if `Left`/`Top` along that axis, as the box has no
+    /// far edge.
+    Bounds,
+}
+
+impl Default for AlignAnchor {
+    #[inline]
+    fn default() -> Self {
+        AlignAnchor::ScreenPosition
+    }
+}
+
+/// Describes how whitespace & newlines are handled, similarly to the CSS `white-space` property.
+///
+/// Collapsed whitespace is omitted from the output glyphs, other glyphs retain their
+/// `byte_index` into the original text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WhiteSpace {
+    /// Sequences of spaces, tabs & newlines are collapsed into a single space.
+    /// Lines wrap at the width bound.
+    Normal,
+    /// Whitespace is collapsed as with `Normal`, but lines do not wrap at the width bound.
+    NoWrap,
+    /// Whitespace & newlines are preserved. Lines do not wrap at the width bound.
+    Pre,
+    /// Whitespace & newlines are preserved. Lines wrap at the width bound.
+    PreWrap,
+    /// Sequences of spaces & tabs are collapsed into a single space, newlines are preserved.
+    /// Lines wrap at the width bound.
+    PreLine,
+}
+
+impl Default for WhiteSpace {
+    #[inline]
+    fn default() -> Self {
+        WhiteSpace::PreWrap
+    }
+}
+
+impl WhiteSpace {
+    /// Returns true if sequences of spaces & tabs should collapse into a single space.
+    #[inline]
+    pub(crate) fn collapses_spaces(self) -> bool {
+        matches!(
+            self,
+            WhiteSpace::Normal | WhiteSpace::NoWrap | WhiteSpace::PreLine
+        )
+    }
+
+    /// Returns true if newlines should remain hard breaks, rather than collapsing into spaces.
+    #[inline]
+    pub(crate) fn preserves_newlines(self) -> bool {
+        !matches!(self, WhiteSpace::Normal | WhiteSpace::NoWrap)
+    }
+
+    /// Returns the width that lines should wrap at.
+    #[inline]
+    pub(crate) fn wrap_width(self, bound_w: f32) -> f32 {
+        match self {
+            WhiteSpace::NoWrap | WhiteSpace::Pre => f32::INFINITY,
+            _ => bound_w,
+        }
+    }
+}
+
+/// Describes how words wider than the width bound are handled, similarly to the CSS
+/// `overflow-wrap` property.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OverflowWrap {
+    /// Words are only broken at line break opportunities, a word that is too wide for
+    /// a line by itself will overflow the width bound.
+    Normal,
+    /// As `Normal`, but a word that is too wide for a line by itself is broken between
+    /// characters so it fits within the width bound.
+    BreakWord,
+}
+
+impl Default for OverflowWrap {
+    #[inline]
+    fn default() -> Self {
+        OverflowWrap::Normal
+    }
 }
 
 #[cfg(test)]
@@ -372,7 +883,7 @@ mod bounds_test {
 #[cfg(test)]
 mod layout_test {
     use super::*;
-    use crate::{BuiltInLineBreaker::*, FontId, SectionText};
+    use crate::{BuiltInLineBreaker::*, FontId, SectionText, SYNTHETIC_BOLD_STRENGTH};
     use approx::assert_relative_eq;
     use once_cell::sync::Lazy;
     use ordered_float::OrderedFloat;
@@ -899,6 +1410,7 @@ mod layout_test {
                 text: "hello world",
                 scale: PxScale::from(20.0),
                 font_id: FontId(0),
+                ..<_>::default()
             }],
         );
 
@@ -945,6 +1457,7 @@ mod layout_test {
                 text: "提高代碼執行率",
                 scale: PxScale::from(20.0),
                 font_id: FontId(1),
+                ..<_>::default()
             }],
         );
 
@@ -1057,4 +1570,600 @@ mod layout_test {
             no_space_f.glyph.position.x,
         );
     }
+
+    #[test]
+    fn white_space_normal_collapses() {
+        let text = "  Autumn   moon\n\tlight  ";
+        let glyphs = Layout::default()
+            .white_space(WhiteSpace::Normal)
+            .calculate_glyphs(
+                &*FONT_MAP,
+                &SectionGeometry::default(),
+                &[SectionText {
+                    text,
+                    scale: PxScale::from(20.0),
+                    ..SectionText::default()
+                }],
+            );
+
+        assert_glyph_order!(glyphs, "Autumn moon light ");
+
+        // glyphs map back to the original text
+        let byte_indices: Vec<_> = glyphs.iter().map(|sg| sg.byte_index).collect();
+        assert_eq!(
+            byte_indices,
+            vec![2, 3, 4, 5, 6, 7, 8, 11, 12, 13, 14, 15, 17, 18, 19, 20, 21, 22]
+        );
+        assert_eq!(&text[15..16], "\n");
+
+        // newline is just a space
+        let y_ords: HashSet<OrderedFloat<f32>> = glyphs
+            .iter()
+            .map(|sg| OrderedFloat(sg.glyph.position.y))
+            .collect();
+        assert_eq!(y_ords.len(), 1, "expected a single line: {:?}", y_ords);
+
+        // collapsed spaces take up no room
+        let sfont = A_FONT.as_scaled(20.0);
+        assert_relative_eq!(glyphs[0].glyph.position.x, 0.0);
+        assert_relative_eq!(
+            glyphs[7].glyph.position.x,
+            sfont.h_advance(glyphs[0].glyph.id) * 7.0,
+            epsilon = 1e-3
+        );
+    }
+
+    #[test]
+    fn white_space_normal_collapses_across_sections() {
+        let glyphs = Layout::default()
+            .white_space(WhiteSpace::Normal)
+            .calculate_glyphs(
+                &*FONT_MAP,
+                &SectionGeometry::default(),
+                &[
+                    SectionText {
+                        text: "Autumn  ",
+                        ..SectionText::default()
+                    },
+                    SectionText {
+                        text: "  moonlight",
+                        ..SectionText::default()
+                    },
+                ],
+            );
+
+        assert_glyph_order!(glyphs, "Autumn moonlight");
+        assert_eq!(glyphs[6].section_index, 0);
+        assert_eq!(glyphs[6].byte_index, 6);
+        assert_eq!(glyphs[7].section_index, 1);
+        assert_eq!(glyphs[7].byte_index, 2);
+    }
+
+    #[test]
+    fn white_space_pre_line_keeps_newlines() {
+        let glyphs = Layout::default()
+            .white_space(WhiteSpace::PreLine)
+            .calculate_glyphs(
+                &*FONT_MAP,
+                &SectionGeometry::default(),
+                &[SectionText {
+                    text: "Autumn   moon  \n  light",
+                    scale: PxScale::from(20.0),
+                    ..SectionText::default()
+                }],
+            );
+
+        // spaces around the newline are removed
+        assert_glyph_order!(glyphs, "Autumn moonlight");
+        assert_eq!(glyphs[7].byte_index, 9);
+        assert_eq!(glyphs[11].byte_index, 18);
+
+        assert_relative_eq!(glyphs[0].glyph.position.y, glyphs[10].glyph.position.y);
+        assert!(
+            glyphs[11].glyph.position.y > glyphs[10].glyph.position.y,
+            "`light` should be on the second line"
+        );
+        assert_relative_eq!(glyphs[11].glyph.position.x, 0.0);
+    }
+
+    #[test]
+    fn white_space_no_wrap() {
+        let width = A_FONT.as_scaled(20.0).h_advance(A_FONT.glyph_id('A')) * 8.0;
+
+        for (white_space, expected) in &[
+            (WhiteSpace::NoWrap, "Autumn moonlight, a worm"),
+            (WhiteSpace::Pre, "Autumn   moonlight, a worm"),
+        ] {
+            let glyphs = Layout::default()
+                .white_space(*white_space)
+                .calculate_glyphs(
+                    &*FONT_MAP,
+                    &SectionGeometry {
+                        bounds: (width, f32::INFINITY),
+                        ..SectionGeometry::default()
+                    },
+                    &[SectionText {
+                        text: "Autumn   moonlight, a worm",
+                        scale: PxScale::from(20.0),
+                        ..SectionText::default()
+                    }],
+                );
+
+            assert_glyph_order!(glyphs, *expected);
+            assert!(
+                glyphs
+                    .iter()
+                    .all(|sg| sg.glyph.position.y == glyphs[0].glyph.position.y),
+                "{:?} should not wrap",
+                white_space
+            );
+        }
+    }
+
+    #[test]
+    fn paragraph_hanging_indent() {
+        let advance = A_FONT.as_scaled(20.0).h_advance(A_FONT.glyph_id('a'));
+
+        let glyphs = Layout::default()
+            .paragraph(ParagraphFormat {
+                margin_left: advance * 2.0,
+                first_line_indent: advance * -2.0,
+                ..ParagraphFormat::default()
+            })
+            .calculate_glyphs(
+                &*FONT_MAP,
+                &SectionGeometry {
+                    screen_position: (10.0, 0.0),
+                    bounds: (advance * 8.5, f32::INFINITY),
+                },
+                &[SectionText {
+                    text: "- aaa bbb\n- ccc",
+                    scale: PxScale::from(20.0),
+                    ..SectionText::default()
+                }],
+            );
+
+        let glyph_at = |byte_index| {
+            glyphs
+                .iter()
+                .find(|sg| sg.byte_index == byte_index)
+                .unwrap()
+                .glyph
+                .position
+        };
+
+        // first line of a paragraph is outdented to the left bound
+        assert_relative_eq!(glyph_at(0).x, 10.0);
+        // wrapped line is indented by the margin
+        let wrapped = glyph_at(6);
+        assert_relative_eq!(wrapped.x, 10.0 + advance * 2.0);
+        assert!(wrapped.y > glyph_at(0).y);
+        // hard break starts a new paragraph
+        let next_paragraph = glyph_at(10);
+        assert_relative_eq!(next_paragraph.x, 10.0);
+        assert!(next_paragraph.y > wrapped.y);
+    }
+
+    #[test]
+    fn paragraph_margins() {
+        let advance = A_FONT.as_scaled(20.0).h_advance(A_FONT.glyph_id('a'));
+        let paragraph = ParagraphFormat {
+            margin_left: 15.0,
+            margin_right: advance * 4.0,
+            ..ParagraphFormat::default()
+        };
+        let geometry = SectionGeometry {
+            screen_position: (100.0, 0.0),
+            bounds: (15.0 + advance * 10.5, f32::INFINITY),
+        };
+        let text = [SectionText {
+            text: "aaa bbb ccc",
+            scale: PxScale::from(20.0),
+            ..SectionText::default()
+        }];
+
+        // right margin reduces the wrapping width
+        let glyphs = Layout::default()
+            .paragraph(paragraph)
+            .calculate_glyphs(&*FONT_MAP, &geometry, &text);
+        assert_relative_eq!(glyphs[0].glyph.position.x, 115.0);
+        let lines: HashSet<_> = glyphs
+            .iter()
+            .map(|sg| OrderedFloat(sg.glyph.position.y))
+            .collect();
+        assert_eq!(lines.len(), 3);
+
+        // right aligned lines end at the right margin
+        let glyphs = Layout::default()
+            .h_align(HorizontalAlign::Right)
+            .paragraph(paragraph)
+            .calculate_glyphs(&*FONT_MAP, &geometry, &text);
+        let last = glyphs.last().unwrap().glyph.position.x + advance;
+        assert_relative_eq!(last, 100.0 - advance * 4.0, epsilon = 0.001);
+    }
+
+    #[test]
+    fn section_lines() {
+        let scale = PxScale::from(20.0);
+        let sfont = A_FONT.as_scaled(scale);
+        let advance = sfont.h_advance(A_FONT.glyph_id('a'));
+        let line_height = sfont.height() + sfont.line_gap();
+        let text = [SectionText {
+            text: "aa bb \n\ncc",
+            scale,
+            ..SectionText::default()
+        }];
+
+        for &v_align in &[VerticalAlign::Top, VerticalAlign::Center] {
+            let (glyphs, lines) = Layout::default()
+                .h_align(HorizontalAlign::Right)
+                .v_align(v_align)
+                .calculate_glyphs_and_lines(
+                    &*FONT_MAP,
+                    &SectionGeometry {
+                        screen_position: (100.0, 0.0),
+                        bounds: (advance * 4.5, f32::INFINITY),
+                    },
+                    &text,
+                    &mut WordCache::new(),
+                );
+
+            // "aa ", "bb \n", "\n", "cc"
+            let ranges: Vec<_> = lines.iter().map(|l| l.glyphs.clone()).collect();
+            assert_eq!(ranges, vec![0..3, 3..6, 6..6, 6..8], "{:?}", v_align);
+            assert_eq!(glyphs.len(), 8);
+
+            let widths: Vec<_> = lines.iter().map(|l| l.rect.width()).collect();
+            for (width, chars) in widths.iter().zip(&[3.0, 3.0, 0.0, 2.0]) {
+                assert_relative_eq!(*width, advance * chars, epsilon = 0.001);
+            }
+
+            // consecutive full height lines
+            let top = if v_align == VerticalAlign::Top {
+                0.0
+            } else {
+                -line_height * 2.0
+            };
+            for (n, line) in lines.iter().enumerate() {
+                assert_relative_eq!(
+                    line.rect.min.y,
+                    top + line_height * n as f32,
+                    epsilon = 0.001
+                );
+                assert_relative_eq!(line.rect.height(), line_height, epsilon = 0.001);
+            }
+
+            // right aligned on the trailing space exclusive line width
+            assert_relative_eq!(lines[3].rect.max.x, 100.0, epsilon = 0.001);
+            assert_relative_eq!(lines[0].rect.min.x, 100.0 - advance * 2.0, epsilon = 0.001);
+        }
+    }
+
+    #[test]
+    fn pixel_snap() {
+        let scale = PxScale::from(13.3);
+        let sfont = A_FONT.as_scaled(scale);
+        let line_height = (sfont.height() + sfont.line_gap()).round();
+        let text = [SectionText {
+            text: "hello world\nab cd",
+            scale,
+            ..SectionText::default()
+        }];
+        let layout_at = |x: f32, y: f32| {
+            Layout::default()
+                .pixel_snap(true)
+                .calculate_glyphs_and_lines(
+                    &*FONT_MAP,
+                    &SectionGeometry {
+                        screen_position: (x, y),
+                        ..SectionGeometry::default()
+                    },
+                    &text,
+                    &mut WordCache::new(),
+                )
+        };
+
+        let (glyphs, lines) = layout_at(10.3, 20.6);
+        assert_eq!(lines.len(), 2);
+        for sg in &glyphs {
+            let position = sg.glyph.position;
+            assert_eq!(position, point(position.x.round(), position.y.round()));
+        }
+        for line in &lines {
+            let Rect { min, max } = line.rect;
+            assert_eq!(min, point(min.x.round(), min.y.round()));
+            assert_eq!(max, point(max.x.round(), max.y.round()));
+        }
+        let baselines: Vec<_> = lines
+            .iter()
+            .map(|l| glyphs[l.glyphs.start].glyph.position.y)
+            .collect();
+        assert_eq!(baselines[1] - baselines[0], line_height);
+
+        // same whole pixel layout at any fractional offset
+        let relative = |glyphs: &[SectionGlyph]| -> Vec<_> {
+            let origin = glyphs[0].glyph.position;
+            glyphs.iter().map(|sg| sg.glyph.position - origin).collect()
+        };
+        let (moved, _) = layout_at(10.7, 20.2);
+        assert_eq!(relative(&glyphs), relative(&moved));
+    }
+
+    #[test]
+    fn bounds_anchor_aligns_within_box() {
+        let text = [SectionText {
+            text: "hello world",
+            scale: PxScale::from(20.0),
+            ..SectionText::default()
+        }];
+        let geometry = SectionGeometry {
+            screen_position: (100.0, 50.0),
+            bounds: (200.0, 100.0),
+        };
+        let box_rect = Rect {
+            min: point(100.0, 50.0),
+            max: point(300.0, 150.0),
+        };
+
+        for &(h_align, x) in &[
+            (HorizontalAlign::Left, 100.0),
+            (HorizontalAlign::Center, 200.0),
+            (HorizontalAlign::Right, 300.0),
+        ] {
+            for &(v_align, y) in &[
+                (VerticalAlign::Top, 50.0),
+                (VerticalAlign::Center, 100.0),
+                (VerticalAlign::Bottom, 150.0),
+            ] {
+                for layout in &[Layout::default_single_line(), Layout::default_wrap()] {
+                    let layout = layout.h_align(h_align).v_align(v_align);
+                    let boxed = layout.anchor(AlignAnchor::Bounds);
+
+                    // equivalent to anchoring at the alignment point of the box
+                    let glyphs = boxed.calculate_glyphs(&*FONT_MAP, &geometry, &text);
+                    let anchored = SectionGeometry {
+                        screen_position: (x, y),
+                        ..geometry
+                    };
+                    assert_eq!(
+                        glyphs,
+                        layout.calculate_glyphs(&*FONT_MAP, &anchored, &text)
+                    );
+
+                    // bounds are the box whatever the alignment
+                    assert_eq!(boxed.bounds_rect(&geometry), box_rect);
+                }
+            }
+        }
+
+        // infinite bounds align left/top from the screen position
+        let geometry = SectionGeometry {
+            screen_position: (100.0, 50.0),
+            bounds: (f32::INFINITY, f32::INFINITY),
+        };
+        let layout = Layout::default()
+            .h_align(HorizontalAlign::Center)
+            .v_align(VerticalAlign::Center)
+            .anchor(AlignAnchor::Bounds);
+        assert_eq!(
+            layout.calculate_glyphs(&*FONT_MAP, &geometry, &text),
+            Layout::default().calculate_glyphs(&*FONT_MAP, &geometry, &text)
+        );
+        assert_eq!(
+            layout.bounds_rect(&geometry),
+            Layout::default().bounds_rect(&geometry)
+        );
+
+        // only the unbounded axis is left/top aligned
+        let geometry = SectionGeometry {
+            screen_position: (100.0, 50.0),
+            bounds: (200.0, f32::INFINITY),
+        };
+        let anchored = SectionGeometry {
+            screen_position: (200.0, 50.0),
+            ..geometry
+        };
+        assert_eq!(
+            layout.calculate_glyphs(&*FONT_MAP, &geometry, &text),
+            Layout::default()
+                .h_align(HorizontalAlign::Center)
+                .calculate_glyphs(&*FONT_MAP, &anchored, &text)
+        );
+    }
+
+    #[test]
+    fn paragraph_baseline_grid() {
+        let glyphs = Layout::default()
+            .paragraph(ParagraphFormat {
+                baseline_grid: Some(25.0),
+                ..ParagraphFormat::default()
+            })
+            .calculate_glyphs(
+                &*FONT_MAP,
+                &SectionGeometry {
+                    screen_position: (0.0, 10.0),
+                    ..SectionGeometry::default()
+                },
+                &[SectionText {
+                    text: "a\nb\nc",
+                    scale: PxScale::from(20.0),
+                    ..SectionText::default()
+                }],
+            );
+
+        assert_eq!(glyphs.len(), 3);
+        let line_height = A_FONT.as_scaled(20.0).height();
+        let mut last_baseline = f32::NEG_INFINITY;
+        for sg in &glyphs {
+            let baseline = sg.glyph.position.y - 10.0;
+            assert_relative_eq!(baseline % 25.0, 0.0, epsilon = 0.01);
+            assert!(baseline >= last_baseline + line_height);
+            last_baseline = baseline;
+        }
+    }
+
+    #[test]
+    fn synthetic_bold_widens_advances() {
+        let layout = |synthetic_bold| {
+            Layout::default_single_line().calculate_glyphs(
+                &*FONT_MAP,
+                &SectionGeometry::default(),
+                &[SectionText {
+                    text: "hello",
+                    scale: PxScale::from(20.0),
+                    synthetic_bold,
+                    ..SectionText::default()
+                }],
+            )
+        };
+        let regular = layout(false);
+        let bold = layout(true);
+
+        assert_eq!(regular.len(), bold.len());
+        let bold_advance = 20.0 * SYNTHETIC_BOLD_STRENGTH;
+        for (index, (r, b)) in regular.iter().zip(&bold).enumerate() {
+            assert_eq!(r.glyph.id, b.glyph.id);
+            assert_relative_eq!(
+                b.glyph.position.x,
+                r.glyph.position.x + bold_advance * index as f32,
+                epsilon = 0.001
+            );
+        }
+    }
+
+    #[test]
+    fn overflow_wrap_break_word() {
+        let sfont = A_FONT.as_scaled(20.0);
+        let width = sfont.h_advance(A_FONT.glyph_id('a')) * 8.5;
+
+        let layout = Layout::default().overflow_wrap(OverflowWrap::BreakWord);
+        let glyphs = layout.calculate_glyphs(
+            &*FONT_MAP,
+            &SectionGeometry {
+                bounds: (width, f32::INFINITY),
+                ..SectionGeometry::default()
+            },
+            &[SectionText {
+                text: "see abcdefghijklmnopqrst",
+                scale: PxScale::from(20.0),
+                ..SectionText::default()
+            }],
+        );
+
+        let lines: Vec<String> = {
+            let mut lines: Vec<Vec<SectionGlyph>> = vec![];
+            for sg in glyphs {
+                match lines.last_mut() {
+                    Some(line) if line[0].glyph.position.y == sg.glyph.position.y => line.push(sg),
+                    _ => lines.push(vec![sg]),
+                }
+            }
+            lines
+                .iter()
+                .map(|line| glyphs_to_common_string(line, &*A_FONT))
+                .collect()
+        };
+
+        // the long word moves to a new line before being broken
+        assert_eq!(lines, vec!["see ", "abcdefgh", "ijklmnop", "qrst"]);
+
+        // without break-word the long word overflows
+        let glyphs = Layout::default().calculate_glyphs(
+            &*FONT_MAP,
+            &SectionGeometry {
+                bounds: (width, f32::INFINITY),
+                ..SectionGeometry::default()
+            },
+            &[SectionText {
+                text: "see abcdefghijklmnopqrst",
+                scale: PxScale::from(20.0),
+                ..SectionText::default()
+            }],
+        );
+        assert!(glyphs.last().unwrap().glyph.position.x > width);
+    }
+
+    #[test]
+    fn overflow_wrap_break_word_keeps_byte_indices() {
+        let sfont = A_FONT.as_scaled(20.0);
+        let width = sfont.h_advance(A_FONT.glyph_id('a')) * 3.0;
+
+        let glyphs = Layout::default()
+            .overflow_wrap(OverflowWrap::BreakWord)
+            .calculate_glyphs(
+                &*FONT_MAP,
+                &SectionGeometry {
+                    bounds: (width, f32::INFINITY),
+                    ..SectionGeometry::default()
+                },
+                &[SectionText {
+                    text: "abcdefg",
+                    scale: PxScale::from(20.0),
+                    ..SectionText::default()
+                }],
+            );
+
+        assert_glyph_order!(glyphs, "abcdefg");
+        for (idx, sg) in glyphs.iter().enumerate() {
+            assert_eq!(sg.byte_index, idx);
+            let line_x = sg.glyph.position.x - glyphs[idx / 3 * 3].glyph.position.x;
+            assert_relative_eq!(line_x, sfont.h_advance(sg.glyph.id) * (idx % 3) as f32);
+        }
+        assert_relative_eq!(glyphs[3].glyph.position.x, 0.0);
+        assert!(glyphs[3].glyph.position.y > glyphs[2].glyph.position.y);
+        assert!(glyphs[6].glyph.position.y > glyphs[5].glyph.position.y);
+    }
+
+    #[test]
+    fn overflow_wrap_break_word_narrow_bounds() {
+        // at least one character is placed per line, even if it doesn't fit
+        let glyphs = Layout::default()
+            .overflow_wrap(OverflowWrap::BreakWord)
+            .calculate_glyphs(
+                &*FONT_MAP,
+                &SectionGeometry {
+                    bounds: (1.0, f32::INFINITY),
+                    ..SectionGeometry::default()
+                },
+                &[SectionText {
+                    text: "abc",
+                    scale: PxScale::from(20.0),
+                    ..SectionText::default()
+                }],
+            );
+
+        assert_glyph_order!(glyphs, "abc");
+        for sg in &glyphs {
+            assert_relative_eq!(sg.glyph.position.x, 0.0);
+        }
+        assert!(glyphs[0].glyph.position.y < glyphs[1].glyph.position.y);
+        assert!(glyphs[1].glyph.position.y < glyphs[2].glyph.position.y);
+    }
+
+    #[test]
+    fn overflow_wrap_break_word_keeps_graphemes() {
+        // "e" + combining acute, devanagari "ka" + spacing vowel sign "i"
+        let text = "e\u{301}\u{915}\u{93f}";
+        let glyphs = Layout::default()
+            .overflow_wrap(OverflowWrap::BreakWord)
+            .calculate_glyphs(
+                &*FONT_MAP,
+                &SectionGeometry {
+                    bounds: (1.0, f32::INFINITY),
+                    ..SectionGeometry::default()
+                },
+                &[SectionText {
+                    text,
+                    scale: PxScale::from(20.0),
+                    ..SectionText::default()
+                }],
+            );
+
+        assert_eq!(glyphs.len(), 4);
+        assert_relative_eq!(glyphs[0].glyph.position.y, glyphs[1].glyph.position.y);
+        assert_relative_eq!(glyphs[2].glyph.position.y, glyphs[3].glyph.position.y);
+        assert!(glyphs[1].glyph.position.y < glyphs[2].glyph.position.y);
+        assert_relative_eq!(glyphs[2].glyph.position.x, 0.0);
+        assert!(glyphs[3].glyph.position.x > 0.0);
+    }
 }
@@ -1,7 +1,7 @@
 use crate::{
     linebreak::{EolLineBreak, LineBreak, LineBreaker},
     words::Words,
-    FontId, SectionText,
+    FontId, SectionText, WhiteSpace, WordCache, SYNTHETIC_BOLD_STRENGTH,
 };
 use ab_glyph::*;
 use std::{
@@ -11,14 +11,18 @@ use std::{
 
 /// Single character info
 pub(crate) struct Character<'b, F: Font> {
-    pub glyph: Glyph,
+    /// Character to draw, collapsed whitespace may be substituted with a space.
+    pub c: char,
     pub scale_font: PxScaleFont<&'b F>,
     pub font_id: FontId,
+    /// Additional advance width for synthetic emboldening.
+    pub bold_advance: f32,
     /// Line break proceeding this character.
     pub line_break: Option<LineBreak>,
-    /// Equivalent to `char::is_control()`.
+    /// Indicates an invisible character without advance, i.e. `char::is_control()` or
+    /// collapsed whitespace.
     pub control: bool,
-    /// Equivalent to `char::is_whitespace()`.
+    /// Equivalent to `char::is_whitespace()`, true for all collapsed whitespace.
     pub whitespace: bool,
     /// Index of the `SectionText` this character is from.
     pub section_index: usize,
@@ -36,6 +40,10 @@ where
     fonts: &'b [F],
     section_text: Enumerate<S>,
     line_breaker: L,
+    white_space: WhiteSpace,
+    /// Indicates following collapsible whitespace should be collapsed away, ie it
+    /// follows whitespace, a hard break or is at the start of the text.
+    collapse_whitespace: bool,
     part_info: Option<PartInfo<'a>>,
 }
 
@@ -54,19 +62,28 @@ where
     S: Iterator<Item = SectionText<'a>>,
 {
     /// Returns a new `Characters` iterator.
-    pub(crate) fn new(fonts: &'b [F], section_text: S, line_breaker: L) -> Self {
+    pub(crate) fn new(
+        fonts: &'b [F],
+        section_text: S,
+        line_breaker: L,
+        white_space: WhiteSpace,
+    ) -> Self {
         Self {
             fonts,
             section_text: section_text.enumerate(),
             line_breaker,
+            white_space,
+            collapse_whitespace: true,
             part_info: None,
         }
     }
 
-    /// Wraps into a `Words` iterator.
-    pub(crate) fn words(self) -> Words<'a, 'b, L, F, S> {
+    /// Wraps into a `Words` iterator, optionally using a `WordCache`.
+    pub(crate) fn words(self, word_cache: Option<&'b mut WordCache>) -> Words<'a, 'b, L, F, S> {
         Words {
             characters: self.peekable(),
+            word_cache,
+            chars: Vec::new(),
         }
     }
 }
@@ -108,6 +125,7 @@ where
                         scale,
                         font_id,
                         text,
+                        synthetic_bold,
                     },
                 info_chars,
                 line_breaks,
@@ -127,7 +145,7 @@ where
 
                 let scale_font: PxScaleFont<&'b F> = self.fonts[*font_id].as_scaled(*scale);
 
-                let glyph = scale_font.scaled_glyph(c);
+                let mut draw_c = c;
 
                 let c_len = c.len_utf8();
                 let mut line_break = next_break.filter(|b| b.offset() == byte_index + c_len);
@@ -136,13 +154,49 @@ where
                     line_break = line_break.and(c.eol_line_break(&self.line_breaker));
                 }
 
+                let mut control = c.is_control();
+                let mut whitespace = c.is_whitespace();
+
+                if self.white_space.collapses_spaces() {
+                    let preserve_newlines = self.white_space.preserves_newlines();
+                    let newline = c == '\n' || c == '\r';
+
+                    if c == ' ' || c == '\t' || (newline && !preserve_newlines) {
+                        if self.collapse_whitespace
+                            || (preserve_newlines && followed_by_newline(info_chars.as_str()))
+                        {
+                            // collapsed whitespace is invisible, but still breaks lines
+                            control = true;
+                        } else {
+                            draw_c = ' ';
+                            control = false;
+                        }
+                        whitespace = true;
+                        if newline {
+                            line_break = line_break.map(|b| LineBreak::Soft(b.offset()));
+                        }
+                        self.collapse_whitespace = true;
+                    } else {
+                        let hard_break = matches!(line_break, Some(LineBreak::Hard(..)));
+                        self.collapse_whitespace =
+                            hard_break || (control && self.collapse_whitespace);
+                    }
+                }
+
+                let bold_advance = if *synthetic_bold {
+                    scale.x * SYNTHETIC_BOLD_STRENGTH
+                } else {
+                    0.0
+                };
+
                 return Some(Character {
-                    glyph,
+                    c: draw_c,
                     scale_font,
                     font_id: *font_id,
+                    bold_advance,
                     line_break,
-                    control: c.is_control(),
-                    whitespace: c.is_whitespace(),
+                    control,
+                    whitespace,
 
                     section_index: *section_index,
                     byte_index,
@@ -163,6 +217,14 @@ where
 {
 }
 
+/// Returns true if the remaining text starts with a newline after any spaces & tabs.
+#[inline]
+fn followed_by_newline(remaining: &str) -> bool {
+    remaining
+        .trim_start_matches(&[' ', '\t'][..])
+        .starts_with(&['\n', '\r'][..])
+}
+
 #[inline]
 fn valid_section(s: &SectionText<'_>) -> bool {
     let PxScale { x, y } = s.scale;
@@ -2,6 +2,7 @@ use ab_glyph::Font;
 
 /// Id for a font.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FontId(pub usize);
 
 impl<F> std::ops::Index<FontId> for [F]
@@ -0,0 +1,552 @@
+use crate::{
+    GlyphChange, GlyphPositioner, SectionGeometry, SectionGlyph, SectionLine, SectionText,
+    ToSectionText, WordCache, SYNTHETIC_BOLD_STRENGTH,
+};
+use ab_glyph::*;
+use std::{
+    hash::{Hash, Hasher},
+    mem,
+};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Width of a [`GridLayout`](struct.GridLayout.html) cell.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CellWidth {
+    /// The advance width of a reference character using the font & scale of the first
+    /// `SectionText`.
+    Advance(char),
+    /// A fixed width in pixels.
+    Fixed(f32),
+}
+
+impl Default for CellWidth {
+    #[inline]
+    fn default() -> Self {
+        CellWidth::Advance('M')
+    }
+}
+
+impl PartialEq for CellWidth {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (CellWidth::Advance(a), CellWidth::Advance(b)) => a == b,
+            (CellWidth::Fixed(a), CellWidth::Fixed(b)) => a.to_bits() == b.to_bits(),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for CellWidth {}
+
+impl Hash for CellWidth {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        mem::discriminant(self).hash(state);
+        match self {
+            CellWidth::Advance(c) => c.hash(state),
+            CellWidth::Fixed(w) => w.to_bits().hash(state),
+        }
+    }
+}
+
+/// Monospace cell grid [`GlyphPositioner`](trait.GlyphPositioner.html), useful for terminals
+/// & consoles.
+///
+/// Each grapheme cluster is centered in a fixed size cell, or 2 cells for East Asian wide
+/// characters, so columns stay aligned whatever the font advances or kerning.
+/// Rows start at the top-left of the geometry & wrap on reaching the width bound.
+///
+/// Use [`calculate_cells`](#method.calculate_cells) to get the row & column of each glyph.
+///
+/// # Example
+/// ```
+/// # use glyph_brush_layout::*;
+/// // 9px x 18px cells
+/// let layout = GridLayout::default()
+///     .cell_width(CellWidth::Fixed(9.0))
+///     .row_height(18.0);
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GridLayout {
+    /// Width of a single cell. Defaults to `CellWidth::Advance('M')`.
+    pub cell_width: CellWidth,
+    /// Height of each row in pixels. Defaults to `None`, the line height of the font & scale
+    /// of the first `SectionText`.
+    pub row_height: Option<f32>,
+    /// Interval of tab stops in cells. Defaults to `8`.
+    pub tab_width: usize,
+    /// Continue onto the next row on reaching the width bound. Defaults to `true`.
+    pub wrap: bool,
+}
+
+impl Default for GridLayout {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            cell_width: CellWidth::default(),
+            row_height: None,
+            tab_width: 8,
+            wrap: true,
+        }
+    }
+}
+
+impl GridLayout {
+    /// Returns an identical `GridLayout` but with the input `cell_width`.
+    #[inline]
+    pub fn cell_width(mut self, cell_width: CellWidth) -> Self {
+        self.cell_width = cell_width;
+        self
+    }
+
+    /// Returns an identical `GridLayout` but with a fixed `row_height`.
+    #[inline]
+    pub fn row_height(mut self, row_height: f32) -> Self {
+        self.row_height = Some(row_height);
+        self
+    }
+
+    /// Returns an identical `GridLayout` but with the input `tab_width` in cells.
+    #[inline]
+    pub fn tab_width(mut self, tab_width: usize) -> Self {
+        self.tab_width = tab_width;
+        self
+    }
+
+    /// Returns an identical `GridLayout` but with the input `wrap` setting.
+    #[inline]
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Returns the `(width, height)` of a single cell when laying out the input sections.
+    ///
+    /// The cell at `(row, column)` has its top-left at
+    /// `screen_position + (column * width, row * height)`.
+    pub fn cell_size<F, S>(&self, fonts: &[F], sections: &[S]) -> (f32, f32)
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        let metrics = self.metrics(fonts, sections);
+        (metrics.cell_width, metrics.row_height)
+    }
+
+    /// Calculate a sequence of grid positioned glyphs along with their cells.
+    pub fn calculate_cells<F, S>(
+        &self,
+        fonts: &[F],
+        geometry: &SectionGeometry,
+        sections: &[S],
+    ) -> Vec<GridGlyph>
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        self.layout_cells(fonts, geometry, sections).0
+    }
+
+    #[inline]
+    fn bits(&self) -> (CellWidth, Option<u32>, usize, bool) {
+        (
+            self.cell_width,
+            self.row_height.map(f32::to_bits),
+            self.tab_width,
+            self.wrap,
+        )
+    }
+
+    fn metrics<F, S>(&self, fonts: &[F], sections: &[S]) -> GridMetrics
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        let reference = match sections.first() {
+            Some(section) => section.to_section_text(),
+            None => SectionText::default(),
+        };
+        let sfont = fonts[reference.font_id].as_scaled(reference.scale);
+        let line_height = sfont.height() + sfont.line_gap();
+
+        let cell_width = match self.cell_width {
+            CellWidth::Advance(c) => sfont.h_advance(sfont.glyph_id(c)),
+            CellWidth::Fixed(width) => width,
+        };
+        let row_height = self.row_height.unwrap_or(line_height);
+
+        GridMetrics {
+            cell_width,
+            row_height,
+            // center the reference font line vertically in each row
+            baseline: sfont.ascent() + (row_height - line_height) / 2.0,
+        }
+    }
+
+    fn layout_cells<F, S>(
+        &self,
+        fonts: &[F],
+        geometry: &SectionGeometry,
+        sections: &[S],
+    ) -> (Vec<GridGlyph>, Vec<SectionLine>)
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        let GridMetrics {
+            cell_width,
+            row_height,
+            baseline,
+        } = self.metrics(fonts, sections);
+        let SectionGeometry {
+            screen_position: (screen_x, screen_y),
+            bounds: (bound_w, bound_h),
+        } = *geometry;
+
+        let max_columns = if self.wrap && bound_w.is_finite() {
+            ((bound_w / cell_width) as usize).max(1)
+        } else {
+            usize::MAX
+        };
+        let max_rows = if bound_h.is_finite() {
+            (bound_h / row_height).ceil() as usize
+        } else {
+            usize::MAX
+        };
+        let tab_width = self.tab_width.max(1);
+
+        let mut out = <_>::default();
+        let mut lines = <_>::default();
+        if max_rows == 0 {
+            return (out, lines);
+        }
+
+        let mut grid = GridCursor::default();
+
+        'sections: for (section_index, section) in sections.iter().enumerate() {
+            let SectionText {
+                text,
+                scale,
+                font_id,
+                synthetic_bold,
+            } = section.to_section_text();
+            let sfont = fonts[font_id].as_scaled(scale);
+            let bold_advance = if synthetic_bold {
+                scale.x * SYNTHETIC_BOLD_STRENGTH
+            } else {
+                0.0
+            };
+
+            for (byte_index, grapheme) in text.grapheme_indices(true) {
+                if let "\n" | "\r\n" | "\r" = grapheme {
+                    grid.next_row(&mut lines, &out, screen_x, screen_y, cell_width, row_height);
+                    if grid.row >= max_rows {
+                        break 'sections;
+                    }
+                    continue;
+                }
+
+                if grapheme == "\t" {
+                    grid.column = (grid.column / tab_width + 1) * tab_width;
+                    continue;
+                }
+
+                let columns = grapheme.width().min(2);
+                if columns == 0 {
+                    // control characters & the like take no space
+                    continue;
+                }
+
+                if grid.column > 0 && grid.column + columns > max_columns {
+                    grid.next_row(&mut lines, &out, screen_x, screen_y, cell_width, row_height);
+                    if grid.row >= max_rows {
+                        break 'sections;
+                    }
+                }
+
+                let cluster_start = out.len();
+                let mut advance = 0.0;
+                let baseline_y = screen_y + grid.row as f32 * row_height + baseline;
+                for (offset, c) in grapheme.char_indices() {
+                    let id = sfont.glyph_id(c);
+                    out.push(GridGlyph {
+                        glyph: SectionGlyph {
+                            section_index,
+                            byte_index: byte_index + offset,
+                            glyph: id.with_scale_and_position(scale, point(advance, baseline_y)),
+                            font_id,
+                        },
+                        row: grid.row,
+                        column: grid.column,
+                        columns,
+                    });
+                    advance += sfont.h_advance(id) + bold_advance;
+                }
+
+                // center the cluster within its cells
+                let cell_x = screen_x + grid.column as f32 * cell_width;
+                let shift = cell_x + (columns as f32 * cell_width - advance) / 2.0;
+                for cell_glyph in &mut out[cluster_start..] {
+                    cell_glyph.glyph.glyph.position.x += shift;
+                }
+
+                grid.column += columns;
+            }
+        }
+
+        if grid.row < max_rows {
+            grid.next_row(&mut lines, &out, screen_x, screen_y, cell_width, row_height);
+        }
+
+        (out, lines)
+    }
+}
+
+impl PartialEq for GridLayout {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.bits() == other.bits()
+    }
+}
+
+impl Eq for GridLayout {}
+
+impl Hash for GridLayout {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bits().hash(state);
+    }
+}
+
+impl GlyphPositioner for GridLayout {
+    fn calculate_glyphs<F, S>(
+        &self,
+        fonts: &[F],
+        geometry: &SectionGeometry,
+        sections: &[S],
+    ) -> Vec<SectionGlyph>
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        self.calculate_cells(fonts, geometry, sections)
+            .into_iter()
+            .map(|cell_glyph| cell_glyph.glyph)
+            .collect()
+    }
+
+    fn bounds_rect(&self, geometry: &SectionGeometry) -> Rect {
+        let SectionGeometry {
+            screen_position: (screen_x, screen_y),
+            bounds: (bound_w, bound_h),
+        } = *geometry;
+
+        Rect {
+            min: point(screen_x, screen_y),
+            max: point(screen_x + bound_w, screen_y + bound_h),
+        }
+    }
+
+    fn calculate_glyphs_and_lines<F, S>(
+        &self,
+        fonts: &[F],
+        geometry: &SectionGeometry,
+        sections: &[S],
+        _: &mut WordCache,
+    ) -> (Vec<SectionGlyph>, Vec<SectionLine>)
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        let (cells, lines) = self.layout_cells(fonts, geometry, sections);
+        let glyphs = cells
+            .into_iter()
+            .map(|cell_glyph| cell_glyph.glyph)
+            .collect();
+        (glyphs, lines)
+    }
+
+    fn recalculate_glyphs_and_lines<F, S, P>(
+        &self,
+        _: P,
+        _: Vec<SectionLine>,
+        _: GlyphChange,
+        fonts: &[F],
+        geometry: &SectionGeometry,
+        sections: &[S],
+        word_cache: &mut WordCache,
+    ) -> (Vec<SectionGlyph>, Vec<SectionLine>)
+    where
+        F: Font,
+        S: ToSectionText,
+        P: IntoIterator<Item = SectionGlyph>,
+    {
+        self.calculate_glyphs_and_lines(fonts, geometry, sections, word_cache)
+    }
+}
+
+/// A [`GridLayout`](struct.GridLayout.html) positioned glyph with its cell.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GridGlyph {
+    /// The positioned glyph.
+    pub glyph: SectionGlyph,
+    /// Row of the cell, `0` is the top row.
+    pub row: usize,
+    /// Column of the first cell, `0` is the leftmost column.
+    pub column: usize,
+    /// Number of cells occupied, `2` for East Asian wide characters otherwise `1`.
+    /// Glyphs from the same grapheme cluster share the same cells.
+    pub columns: usize,
+}
+
+struct GridMetrics {
+    cell_width: f32,
+    row_height: f32,
+    /// Distance from the top of a row to the baseline.
+    baseline: f32,
+}
+
+#[derive(Default)]
+struct GridCursor {
+    row: usize,
+    column: usize,
+    /// Index of the first glyph of the current row.
+    row_start: usize,
+}
+
+impl GridCursor {
+    /// Ends the current row, adding it to `lines`, & moves to the start of the next.
+    fn next_row(
+        &mut self,
+        lines: &mut Vec<SectionLine>,
+        out: &[GridGlyph],
+        screen_x: f32,
+        screen_y: f32,
+        cell_width: f32,
+        row_height: f32,
+    ) {
+        let top = screen_y + self.row as f32 * row_height;
+        lines.push(SectionLine {
+            glyphs: self.row_start..out.len(),
+            rect: Rect {
+                min: point(screen_x, top),
+                max: point(screen_x + self.column as f32 * cell_width, top + row_height),
+            },
+        });
+        self.row += 1;
+        self.column = 0;
+        self.row_start = out.len();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::FontId;
+    use approx::assert_relative_eq;
+    use once_cell::sync::Lazy;
+
+    static MONO_FONT: Lazy<FontRef<'static>> = Lazy::new(|| {
+        FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSansMono.ttf")).unwrap()
+    });
+    static SANS_FONT: Lazy<FontRef<'static>> = Lazy::new(|| {
+        FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf")).unwrap()
+    });
+    static FONTS: Lazy<[&'static FontRef<'static>; 2]> = Lazy::new(|| [&*MONO_FONT, &*SANS_FONT]);
+
+    fn cells_of(glyphs: &[GridGlyph]) -> Vec<(usize, usize, usize)> {
+        glyphs
+            .iter()
+            .map(|g| (g.row, g.column, g.columns))
+            .collect()
+    }
+
+    #[test]
+    fn proportional_text_on_grid() {
+        let layout = GridLayout::default().cell_width(CellWidth::Fixed(10.0));
+        let sections = [
+            SectionText {
+                text: "Wi",
+                font_id: FontId(1),
+                ..SectionText::default()
+            },
+            SectionText {
+                text: "l\nab",
+                font_id: FontId(1),
+                ..SectionText::default()
+            },
+        ];
+        let geometry = SectionGeometry {
+            screen_position: (5.0, 0.0),
+            ..SectionGeometry::default()
+        };
+
+        let glyphs = layout.calculate_cells(&*FONTS, &geometry, &sections);
+        assert_eq!(
+            cells_of(&glyphs),
+            vec![(0, 0, 1), (0, 1, 1), (0, 2, 1), (1, 0, 1), (1, 1, 1)]
+        );
+        assert_eq!(glyphs[2].glyph.section_index, 1);
+        assert_eq!(glyphs[4].glyph.byte_index, 3);
+
+        let sfont = SANS_FONT.as_scaled(16.0);
+        for cell_glyph in &glyphs {
+            // each glyph is centered in its cell regardless of advance
+            let advance = sfont.h_advance(cell_glyph.glyph.glyph.id);
+            let center = cell_glyph.glyph.glyph.position.x + advance / 2.0;
+            assert_relative_eq!(center, 5.0 + cell_glyph.column as f32 * 10.0 + 5.0);
+        }
+
+        let row_height = sfont.height() + sfont.line_gap();
+        assert_relative_eq!(glyphs[0].glyph.glyph.position.y, sfont.ascent());
+        assert_relative_eq!(
+            glyphs[3].glyph.glyph.position.y,
+            row_height + sfont.ascent(),
+            epsilon = 0.001
+        );
+        assert_eq!(layout.cell_size(&*FONTS, &sections), (10.0, row_height));
+    }
+
+    #[test]
+    fn wide_chars_take_two_cells_and_wrap() {
+        let layout = GridLayout::default().row_height(20.0);
+        let sections = [SectionText {
+            text: "a提b\tc",
+            ..SectionText::default()
+        }];
+        let (cell_width, _) = layout.cell_size(&*FONTS, &sections);
+        assert_relative_eq!(
+            cell_width,
+            MONO_FONT.as_scaled(16.0).h_advance(MONO_FONT.glyph_id('M'))
+        );
+
+        let unbounded = layout.calculate_cells(&*FONTS, &SectionGeometry::default(), &sections);
+        assert_eq!(
+            cells_of(&unbounded),
+            vec![(0, 0, 1), (0, 1, 2), (0, 3, 1), (0, 8, 1)]
+        );
+
+        // 2 columns wide, the wide char doesn't fit after "a" so starts the next row
+        let (glyphs, lines) = layout.calculate_glyphs_and_lines(
+            &*FONTS,
+            &SectionGeometry {
+                bounds: (cell_width * 2.5, 50.0),
+                ..SectionGeometry::default()
+            },
+            &sections,
+            &mut WordCache::new(),
+        );
+        assert_eq!(glyphs.len(), 3);
+        let ranges: Vec<_> = lines.iter().map(|l| l.glyphs.clone()).collect();
+        assert_eq!(ranges, vec![0..1, 1..2, 2..3]);
+        assert_relative_eq!(lines[1].rect.width(), cell_width * 2.0);
+        assert_relative_eq!(lines[2].rect.min.y, 40.0);
+        assert_relative_eq!(lines[2].rect.height(), 20.0);
+    }
+}
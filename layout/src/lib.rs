@@ -25,11 +25,13 @@
 //!             text: "hello ",
 //!             scale: PxScale::from(20.0),
 //!             font_id: FontId(0),
+//!             ..SectionText::default()
 //!         },
 //!         SectionText {
 //!             text: "glyph_brush_layout",
 //!             scale: PxScale::from(25.0),
 //!             font_id: FontId(1),
+//!             ..SectionText::default()
 //!         },
 //!     ],
 //! );
@@ -54,16 +56,24 @@
 mod builtin;
 mod characters;
 mod font;
+mod grid;
 mod linebreak;
 mod lines;
+mod paragraph;
+mod scroll;
 mod section;
+#[cfg(feature = "serde")]
+pub mod serde_remote;
+mod word_cache;
 mod words;
 
 /// Re-exported ab_glyph types.
 pub mod ab_glyph {
     pub use ab_glyph::*;
 }
-pub use self::{builtin::*, font::*, linebreak::*, section::*};
+pub use self::{
+    builtin::*, font::*, grid::*, linebreak::*, paragraph::*, scroll::*, section::*, word_cache::*,
+};
 
 use ::ab_glyph::*;
 use std::hash::Hash;
@@ -88,6 +98,25 @@ pub trait GlyphPositioner: Hash {
     /// appropriate for the glyph layout.
     fn bounds_rect(&self, geometry: &SectionGeometry) -> Rect;
 
+    /// Calculate a sequence of positioned glyphs to render, using & populating a
+    /// [`WordCache`](struct.WordCache.html) of previously measured words.
+    ///
+    /// The default implementation simply calls `calculate_glyphs`.
+    fn calculate_glyphs_cached<F, S>(
+        &self,
+        fonts: &[F],
+        geometry: &SectionGeometry,
+        sections: &[S],
+        word_cache: &mut WordCache,
+    ) -> Vec<SectionGlyph>
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        let _ = word_cache;
+        self.calculate_glyphs(fonts, geometry, sections)
+    }
+
     /// Recalculate a glyph sequence after a change.
     ///
     /// The default implementation simply calls `calculate_glyphs` so must be implemented
@@ -108,6 +137,74 @@ pub trait GlyphPositioner: Hash {
         let _ = (previous, change);
         self.calculate_glyphs(fonts, geometry, sections)
     }
+
+    /// Recalculate a glyph sequence after a change, using & populating a
+    /// [`WordCache`](struct.WordCache.html) of previously measured words.
+    ///
+    /// The default implementation simply calls `recalculate_glyphs`.
+    fn recalculate_glyphs_cached<F, S, P>(
+        &self,
+        previous: P,
+        change: GlyphChange,
+        fonts: &[F],
+        geometry: &SectionGeometry,
+        sections: &[S],
+        word_cache: &mut WordCache,
+    ) -> Vec<SectionGlyph>
+    where
+        F: Font,
+        S: ToSectionText,
+        P: IntoIterator<Item = SectionGlyph>,
+    {
+        let _ = word_cache;
+        self.recalculate_glyphs(previous, change, fonts, geometry, sections)
+    }
+
+    /// Calculate a sequence of positioned glyphs to render along with the
+    /// [`SectionLine`](struct.SectionLine.html)s containing them, using & populating a
+    /// [`WordCache`](struct.WordCache.html) of previously measured words.
+    ///
+    /// The default implementation calls `calculate_glyphs_cached` & returns no lines.
+    fn calculate_glyphs_and_lines<F, S>(
+        &self,
+        fonts: &[F],
+        geometry: &SectionGeometry,
+        sections: &[S],
+        word_cache: &mut WordCache,
+    ) -> (Vec<SectionGlyph>, Vec<SectionLine>)
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        let glyphs = self.calculate_glyphs_cached(fonts, geometry, sections, word_cache);
+        (glyphs, vec![])
+    }
+
+    /// Recalculate a glyph & line sequence after a change, using & populating a
+    /// [`WordCache`](struct.WordCache.html) of previously measured words.
+    ///
+    /// The default implementation calls `recalculate_glyphs_cached` & returns no lines.
+    #[allow(clippy::too_many_arguments)]
+    fn recalculate_glyphs_and_lines<F, S, P>(
+        &self,
+        previous: P,
+        previous_lines: Vec<SectionLine>,
+        change: GlyphChange,
+        fonts: &[F],
+        geometry: &SectionGeometry,
+        sections: &[S],
+        word_cache: &mut WordCache,
+    ) -> (Vec<SectionGlyph>, Vec<SectionLine>)
+    where
+        F: Font,
+        S: ToSectionText,
+        P: IntoIterator<Item = SectionGlyph>,
+    {
+        let _ = previous_lines;
+        let glyphs =
+            self.recalculate_glyphs_cached(previous, change, fonts, geometry, sections, word_cache);
+        (glyphs, vec![])
+    }
 }
 
 #[derive(Debug)]
@@ -33,6 +33,7 @@ pub trait LineBreaker: fmt::Debug + Copy + Hash {
 
 /// Built-in linebreaking logic.
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BuiltInLineBreaker {
     /// LineBreaker that follows Unicode Standard Annex #14. That effectively means it
     /// wraps words in a way that should work for most cases.
@@ -1,7 +1,7 @@
 use super::{HorizontalAlign, SectionGlyph, SectionText, VerticalAlign};
-use crate::{linebreak::LineBreaker, words::*};
+use crate::{linebreak::LineBreaker, words::*, OverflowWrap, ParagraphFormat};
 use ab_glyph::*;
-use std::iter::{FusedIterator, Iterator, Peekable};
+use std::iter::{FusedIterator, Iterator};
 
 /// A line of `Word`s limited to a max width bound.
 #[derive(Default)]
@@ -9,6 +9,12 @@ pub(crate) struct Line {
     pub glyphs: Vec<SectionGlyph>,
     pub max_v_metrics: VMetrics,
     pub rightmost: f32,
+    /// Sum of word advances, including trailing whitespace.
+    pub advance: f32,
+    /// Paragraph indent of this line from the left of the bounds.
+    pub indent: f32,
+    /// Indicates this line is the first line of a paragraph.
+    pub paragraph_start: bool,
 }
 
 impl Line {
@@ -17,17 +23,14 @@ impl Line {
         self.max_v_metrics.ascent - self.max_v_metrics.descent + self.max_v_metrics.line_gap
     }
 
-    /// Returns line glyphs positioned on the screen and aligned.
+    /// Returns line glyphs positioned on the screen and aligned, along with the logical
+    /// line rect.
     pub fn aligned_on_screen(
         mut self,
         screen_position: (f32, f32),
         h_align: HorizontalAlign,
         v_align: VerticalAlign,
-    ) -> Vec<SectionGlyph> {
-        if self.glyphs.is_empty() {
-            return Vec::new();
-        }
-
+    ) -> (Vec<SectionGlyph>, Rect) {
         // implement v-aligns when they're are supported
         let screen_left = match h_align {
             HorizontalAlign::Left => point(screen_position.0, screen_position.1),
@@ -62,7 +65,14 @@ impl Line {
             .iter_mut()
             .for_each(|sg| sg.glyph.position += screen_pos);
 
-        self.glyphs
+        let rect = Rect {
+            min: screen_pos,
+            max: point(
+                screen_pos.x + self.advance,
+                screen_pos.y + self.line_height(),
+            ),
+        };
+        (self.glyphs, rect)
     }
 }
 
@@ -71,15 +81,23 @@ impl Line {
 /// Will iterator through `Word` until the next word would break the `width_bound`.
 ///
 /// Note: Will always have at least one word, if possible, even if the word itself
-/// breaks the `width_bound`.
+/// breaks the `width_bound`. Unless `overflow_wrap` is `BreakWord`, in which case such
+/// words are split across lines.
 pub(crate) struct Lines<'a, 'b, L, F, S>
 where
     L: LineBreaker,
     F: Font,
     S: Iterator<Item = SectionText<'a>>,
 {
-    pub(crate) words: Peekable<Words<'a, 'b, L, F, S>>,
+    pub(crate) words: Words<'a, 'b, L, F, S>,
     pub(crate) width_bound: f32,
+    pub(crate) overflow_wrap: OverflowWrap,
+    pub(crate) paragraph: ParagraphFormat,
+    /// Indicates the next line is the first line of a paragraph.
+    pub(crate) paragraph_start: bool,
+    /// Remainder of a word split by `OverflowWrap::BreakWord`, or a word not fitting the
+    /// previous line. Always from the latest word, so `words.chars` are its characters.
+    pub(crate) pending: Option<Word>,
 }
 
 impl<'a, L, F, S> Iterator for Lines<'a, '_, L, F, S>
@@ -92,11 +110,17 @@ where
 
     fn next(&mut self) -> Option<Self::Item> {
         let mut caret = point(0.0, 0.0);
-        let mut line = Line::default();
+        let mut line = Line {
+            indent: self.paragraph.line_indent(self.paragraph_start),
+            paragraph_start: self.paragraph_start,
+            ..Line::default()
+        };
+        let width_bound = self.width_bound - line.indent - self.paragraph.margin_right;
 
         let mut progressed = false;
+        self.paragraph_start = false;
 
-        while let Some(word) = self.words.peek() {
+        while let Some(mut word) = self.pending.take().or_else(|| self.words.next()) {
             // Drop trailing spaces when bounds-wrapping.
             // However, if the word ends in a hard-break "Foo  \n" keep the trailing space width.
             let word_wrap_width = match word.hard_break {
@@ -104,17 +128,28 @@ where
                 true => word.layout_width,
             };
 
-            let word_right = caret.x + word_wrap_width;
+            let mut word_right = caret.x + word_wrap_width;
             // Reduce float errors by using relative "<= width bound" check
             let word_in_bounds =
-                word_right < self.width_bound || approx::relative_eq!(word_right, self.width_bound);
+                word_right < width_bound || approx::relative_eq!(word_right, width_bound);
 
             // only if `progressed` means the first word is allowed to overlap the bounds
             if !word_in_bounds && progressed {
+                self.pending = Some(word);
                 break;
             }
 
-            let word = self.words.next().unwrap();
+            if !word_in_bounds && self.overflow_wrap == OverflowWrap::BreakWord {
+                word = match word.split_at_width(width_bound - caret.x, &self.words.chars) {
+                    Ok((head, tail)) => {
+                        self.pending = Some(tail);
+                        word_right = caret.x + head.layout_width;
+                        head
+                    }
+                    Err(word) => word,
+                };
+            }
+
             progressed = true;
 
             line.rightmost = word_right;
@@ -141,10 +176,12 @@ where
             caret.x += word.layout_width;
 
             if word.hard_break {
+                self.paragraph_start = true;
                 break;
             }
         }
 
+        line.advance = caret.x;
         Some(line).filter(|_| progressed)
     }
 }
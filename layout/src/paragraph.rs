@@ -0,0 +1,99 @@
+use crate::HorizontalAlign;
+use std::hash::{Hash, Hasher};
+
+/// Paragraph formatting for [`Layout::Wrap`](enum.Layout.html#variant.Wrap).
+///
+/// Paragraphs are delimited by hard line breaks, as reported by the `LineBreaker`.
+/// All values are in pixels.
+///
+/// # Example
+/// ```
+/// # use glyph_brush_layout::*;
+/// // list item style, the first line starts 20px left of the following lines
+/// let layout = Layout::default().paragraph(ParagraphFormat {
+///     margin_left: 20.0,
+///     first_line_indent: -20.0,
+///     ..ParagraphFormat::default()
+/// });
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ParagraphFormat {
+    /// Indent of the first line of each paragraph, relative to `margin_left`.
+    /// May be negative to outdent. Defaults to `0`.
+    pub first_line_indent: f32,
+    /// Indent of each line after the first line of each paragraph, relative to
+    /// `margin_left`. Defaults to `0`.
+    pub hanging_indent: f32,
+    /// Space between the left of the bounds & the lines. Defaults to `0`.
+    pub margin_left: f32,
+    /// Space between the lines & the right of the bounds. Defaults to `0`.
+    pub margin_right: f32,
+    /// Snaps each line baseline down to the next multiple of this step, measured from
+    /// the screen position. Defaults to `None`.
+    pub baseline_grid: Option<f32>,
+}
+
+impl ParagraphFormat {
+    /// Returns the left offset of a line from the left of the bounds.
+    #[inline]
+    pub(crate) fn line_indent(&self, first_line: bool) -> f32 {
+        if first_line {
+            self.margin_left + self.first_line_indent
+        } else {
+            self.margin_left + self.hanging_indent
+        }
+    }
+
+    /// Returns the horizontal shift of an aligned line with the input `indent`.
+    #[inline]
+    pub(crate) fn align_shift(&self, h_align: HorizontalAlign, indent: f32) -> f32 {
+        match h_align {
+            HorizontalAlign::Left => indent,
+            HorizontalAlign::Center => (indent - self.margin_right) / 2.0,
+            HorizontalAlign::Right => -self.margin_right,
+        }
+    }
+
+    /// Returns the distance to move a line down so its baseline, `baseline` distance
+    /// from the screen position, lies on the baseline grid.
+    #[inline]
+    pub(crate) fn baseline_snap(&self, baseline: f32) -> f32 {
+        match self.baseline_grid {
+            Some(step) if step > 0.0 => {
+                // tolerate float error for baselines already on the grid
+                let snapped = (baseline / step - 1e-4).ceil() * step;
+                (snapped - baseline).max(0.0)
+            }
+            _ => 0.0,
+        }
+    }
+
+    #[inline]
+    fn bits(&self) -> (u32, u32, u32, u32, Option<u32>) {
+        (
+            self.first_line_indent.to_bits(),
+            self.hanging_indent.to_bits(),
+            self.margin_left.to_bits(),
+            self.margin_right.to_bits(),
+            self.baseline_grid.map(f32::to_bits),
+        )
+    }
+}
+
+impl PartialEq for ParagraphFormat {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.bits() == other.bits()
+    }
+}
+
+impl Eq for ParagraphFormat {}
+
+impl Hash for ParagraphFormat {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bits().hash(state);
+    }
+}
@@ -0,0 +1,745 @@
+use crate::{
+    builtin::snap_to_pixels, characters::Characters, lines::Line, FontId, GlyphChange,
+    GlyphPositioner, Layout, LineBreaker, ParagraphFormat, SectionGeometry, SectionGlyph,
+    SectionLine, SectionText, ToSectionText, VerticalAlign, WordCache,
+};
+use ab_glyph::*;
+use std::{
+    collections::hash_map::DefaultHasher,
+    f32,
+    hash::{Hash, Hasher},
+};
+
+/// Start of a laid out line from which layout can resume.
+#[derive(Debug, Clone, Copy)]
+pub struct LineStart {
+    /// Index of the `SectionText` containing the first character of the line.
+    pub section_index: usize,
+    /// Byte index of the first character of the line within its `SectionText` text.
+    pub byte_index: usize,
+    /// Distance from the top of the content to the top of the line, in pixels.
+    pub top: f32,
+    /// Indicates the line is the first line of a paragraph.
+    pub paragraph_start: bool,
+}
+
+impl Default for LineStart {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            section_index: 0,
+            byte_index: 0,
+            top: 0.0,
+            paragraph_start: true,
+        }
+    }
+}
+
+impl LineStart {
+    #[inline]
+    fn bits(&self) -> (usize, usize, u32, bool) {
+        (
+            self.section_index,
+            self.byte_index,
+            self.top.to_bits(),
+            self.paragraph_start,
+        )
+    }
+}
+
+impl PartialEq for LineStart {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.bits() == other.bits()
+    }
+}
+
+impl Eq for LineStart {}
+
+impl Hash for LineStart {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bits().hash(state);
+    }
+}
+
+/// [`GlyphPositioner`](trait.GlyphPositioner.html) laying out only the lines of a
+/// [`Layout`](enum.Layout.html) visible in a vertically scrolled viewport.
+///
+/// The geometry `screen_position` is the top-left of the viewport & `bounds` the viewport
+/// size. Layout starts from a known `LineStart` so text before it is never measured, & ends
+/// after the last line visible in the viewport. Vertical alignment is always `Top`.
+///
+/// Obtain from a [`LineIndex`](struct.LineIndex.html), which caches line starts.
+#[derive(Debug, Clone, Copy)]
+pub struct ScrollLayout<L: LineBreaker> {
+    /// Layout of all the content.
+    pub layout: Layout<L>,
+    /// A line start at or above the scroll offset to start layout from.
+    pub start: LineStart,
+    /// Distance from the top of the content to the top of the viewport, in pixels.
+    pub scroll_offset: f32,
+}
+
+impl<L: LineBreaker> ScrollLayout<L> {
+    /// Calculates the glyphs & lines visible in the viewport.
+    fn layout_glyphs<F, S>(
+        &self,
+        fonts: &[F],
+        geometry: &SectionGeometry,
+        sections: &[S],
+        word_cache: Option<&mut WordCache>,
+    ) -> (Vec<SectionGlyph>, Vec<SectionLine>)
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        let (layout, geometry) = self.layout.anchored(geometry);
+        let SectionGeometry {
+            screen_position: (screen_x, screen_y),
+            bounds: (bound_w, bound_h),
+        } = geometry;
+
+        let (h_align, paragraph) = match layout {
+            Layout::SingleLine { h_align, .. } => (h_align, ParagraphFormat::default()),
+            Layout::Wrap {
+                h_align, paragraph, ..
+            } => (h_align, paragraph),
+        };
+        let view_bottom = self.scroll_offset + bound_h;
+        let content_y = screen_y - self.scroll_offset;
+
+        let mut out = vec![];
+        let mut out_lines = vec![];
+
+        lay_out_lines(
+            &self.layout,
+            fonts,
+            sections,
+            self.start,
+            bound_w,
+            word_cache,
+            |line, top| {
+                if top >= view_bottom {
+                    return false;
+                }
+                if top + line.line_height() <= self.scroll_offset {
+                    // above the viewport
+                    return true;
+                }
+
+                let line_x = screen_x + paragraph.align_shift(h_align, line.indent);
+                let (glyphs, rect) =
+                    line.aligned_on_screen((line_x, content_y + top), h_align, VerticalAlign::Top);
+                out_lines.push(SectionLine {
+                    glyphs: out.len()..out.len() + glyphs.len(),
+                    rect,
+                });
+                out.extend(glyphs);
+                true
+            },
+        );
+
+        if self.layout.is_pixel_snapped() {
+            snap_to_pixels(&mut out, &mut out_lines);
+        }
+        (out, out_lines)
+    }
+
+    #[inline]
+    fn bits(&self) -> (&Layout<L>, &LineStart, u32) {
+        (&self.layout, &self.start, self.scroll_offset.to_bits())
+    }
+}
+
+impl<L: LineBreaker + PartialEq> PartialEq for ScrollLayout<L> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.bits() == other.bits()
+    }
+}
+
+impl<L: LineBreaker + Eq> Eq for ScrollLayout<L> {}
+
+impl<L: LineBreaker> Hash for ScrollLayout<L> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bits().hash(state);
+    }
+}
+
+impl<L: LineBreaker> GlyphPositioner for ScrollLayout<L> {
+    #[inline]
+    fn calculate_glyphs<F, S>(
+        &self,
+        fonts: &[F],
+        geometry: &SectionGeometry,
+        sections: &[S],
+    ) -> Vec<SectionGlyph>
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        self.layout_glyphs(fonts, geometry, sections, None).0
+    }
+
+    #[inline]
+    fn bounds_rect(&self, geometry: &SectionGeometry) -> Rect {
+        self.layout.bounds_rect(geometry)
+    }
+
+    #[inline]
+    fn calculate_glyphs_cached<F, S>(
+        &self,
+        fonts: &[F],
+        geometry: &SectionGeometry,
+        sections: &[S],
+        word_cache: &mut WordCache,
+    ) -> Vec<SectionGlyph>
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        self.layout_glyphs(fonts, geometry, sections, Some(word_cache))
+            .0
+    }
+
+    #[inline]
+    fn calculate_glyphs_and_lines<F, S>(
+        &self,
+        fonts: &[F],
+        geometry: &SectionGeometry,
+        sections: &[S],
+        word_cache: &mut WordCache,
+    ) -> (Vec<SectionGlyph>, Vec<SectionLine>)
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        self.layout_glyphs(fonts, geometry, sections, Some(word_cache))
+    }
+
+    #[inline]
+    fn recalculate_glyphs_and_lines<F, S, P>(
+        &self,
+        _: P,
+        _: Vec<SectionLine>,
+        _: GlyphChange,
+        fonts: &[F],
+        geometry: &SectionGeometry,
+        sections: &[S],
+        word_cache: &mut WordCache,
+    ) -> (Vec<SectionGlyph>, Vec<SectionLine>)
+    where
+        F: Font,
+        S: ToSectionText,
+        P: IntoIterator<Item = SectionGlyph>,
+    {
+        self.layout_glyphs(fonts, geometry, sections, Some(word_cache))
+    }
+}
+
+/// Cache of line starts of a [`Layout`](enum.Layout.html), allowing very long text to be
+/// scrolled through without laying out the lines before the viewport.
+///
+/// Lines are indexed lazily, as far as the furthest scroll offset requested, or to the end
+/// of the text when the total content height is requested. The index is rebuilt when the
+/// layout or width bound change. Lines before the first changed section length, section
+/// count, scale, font or synthetic bold are kept, so appending text only re-indexes the
+/// last lines. Text edited without changing section lengths, or edited before the end of
+/// a section, must be reported with [`invalidate_from`](#method.invalidate_from).
+/// Call [`clear`](#method.clear) if the fonts change.
+///
+/// # Example
+/// ```
+/// # use glyph_brush_layout::{ab_glyph::*, *};
+/// # let font = FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf")).unwrap();
+/// # let fonts = &[font];
+/// # let log = "a very long log\n".repeat(1000);
+/// let sections = [SectionText { text: &log, ..<_>::default() }];
+/// let viewport = SectionGeometry {
+///     screen_position: (10.0, 10.0),
+///     bounds: (400.0, 300.0),
+/// };
+///
+/// let mut index = LineIndex::new();
+/// let scrollbar_max = index.content_height(Layout::default(), fonts, &viewport, &sections) - 300.0;
+///
+/// // glyphs for the lines visible 500px down the log
+/// let layout = index.scroll_layout(Layout::default(), fonts, &viewport, &sections, 500.0);
+/// let glyphs = layout.calculate_glyphs(fonts, &viewport, &sections);
+/// # assert!(scrollbar_max > 500.0);
+/// # assert!(!glyphs.is_empty());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct LineIndex {
+    key: Option<u64>,
+    /// Sections seen by the last indexing.
+    seen: Vec<SeenSection>,
+    /// Resumable line starts in order, always beginning with the start of the content.
+    starts: Vec<LineStart>,
+    /// Total content height, when all lines have been indexed.
+    content_height: Option<f32>,
+}
+
+impl LineIndex {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a `ScrollLayout` to lay out the lines of `layout` visible in the viewport
+    /// `geometry` when the content is scrolled down by `scroll_offset` pixels.
+    pub fn scroll_layout<L, F, S>(
+        &mut self,
+        layout: Layout<L>,
+        fonts: &[F],
+        geometry: &SectionGeometry,
+        sections: &[S],
+        scroll_offset: f32,
+    ) -> ScrollLayout<L>
+    where
+        L: LineBreaker,
+        F: Font,
+        S: ToSectionText,
+    {
+        let layout = layout.v_align(VerticalAlign::Top);
+        self.index_until(&layout, fonts, geometry.bounds.0, sections, scroll_offset);
+
+        let after = self.starts.partition_point(|s| s.top <= scroll_offset);
+        ScrollLayout {
+            layout,
+            start: self.starts[after.saturating_sub(1)],
+            scroll_offset,
+        }
+    }
+
+    /// Returns the total height of all lines of `layout`, indexing any lines not already
+    /// indexed.
+    pub fn content_height<L, F, S>(
+        &mut self,
+        layout: Layout<L>,
+        fonts: &[F],
+        geometry: &SectionGeometry,
+        sections: &[S],
+    ) -> f32
+    where
+        L: LineBreaker,
+        F: Font,
+        S: ToSectionText,
+    {
+        let layout = layout.v_align(VerticalAlign::Top);
+        self.index_until(&layout, fonts, geometry.bounds.0, sections, f32::INFINITY);
+        self.content_height.unwrap_or_default()
+    }
+
+    /// Removes all indexed lines.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.key = None;
+        self.seen.clear();
+        self.starts.clear();
+        self.content_height = None;
+    }
+
+    /// Removes indexed lines that may be affected by a text change at `byte_index` of the
+    /// `SectionText` at `section_index`. Lines before the change are kept.
+    ///
+    /// # Example
+    /// ```
+    /// # use glyph_brush_layout::{ab_glyph::*, *};
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf")).unwrap();
+    /// # let fonts = &[font];
+    /// # let viewport = SectionGeometry { bounds: (400.0, 300.0), ..<_>::default() };
+    /// # let mut index = LineIndex::new();
+    /// let mut log = "a very long log\n".repeat(1000);
+    /// # index.content_height(Layout::default(), fonts, &viewport, &[SectionText { text: &log, ..<_>::default() }]);
+    /// // replace "a" at the start of line 500, keeping the text length
+    /// log.replace_range(8000..8001, "A");
+    /// index.invalidate_from(0, 8000);
+    /// ```
+    pub fn invalidate_from(&mut self, section_index: usize, byte_index: usize) {
+        let changed = (section_index, byte_index);
+        let before = self
+            .starts
+            .partition_point(|s| (s.section_index, s.byte_index) < changed);
+        // the line before the changed line may wrap differently too
+        self.starts.truncate(before.saturating_sub(1).max(1));
+        self.content_height = None;
+    }
+
+    /// Indexes lines until a line start below `target` or the end of the content.
+    fn index_until<L, F, S>(
+        &mut self,
+        layout: &Layout<L>,
+        fonts: &[F],
+        bound_w: f32,
+        sections: &[S],
+        target: f32,
+    ) where
+        L: LineBreaker,
+        F: Font,
+        S: ToSectionText,
+    {
+        let key = index_key(layout, bound_w);
+        if self.key != Some(key) {
+            self.clear();
+            self.key = Some(key);
+            self.starts.push(LineStart::default());
+        }
+        self.invalidate_changed(sections);
+
+        let last = *self.starts.last().unwrap();
+        if self.content_height.is_some() || last.top > target {
+            return;
+        }
+
+        let starts = &mut self.starts;
+        let mut bottom = last.top;
+        let mut resumed_line = true;
+        let complete = lay_out_lines(layout, fonts, sections, last, bound_w, None, |line, top| {
+            bottom = top + line.line_height();
+            if resumed_line {
+                // already indexed
+                resumed_line = false;
+                return true;
+            }
+            if let Some(sg) = line.glyphs.first() {
+                starts.push(LineStart {
+                    section_index: sg.section_index,
+                    byte_index: sg.byte_index,
+                    top,
+                    paragraph_start: line.paragraph_start,
+                });
+                if top > target {
+                    return false;
+                }
+            }
+            true
+        });
+
+        if complete {
+            self.content_height = Some(bottom);
+        }
+    }
+
+    /// Invalidates lines from the first section differing from the sections last seen,
+    /// assuming text length changes are appends or truncations.
+    fn invalidate_changed<S: ToSectionText>(&mut self, sections: &[S]) {
+        let mut changed = None;
+        for (index, section) in sections.iter().enumerate() {
+            let section = SeenSection::new(&section.to_section_text());
+            match self.seen.get(index) {
+                Some(seen) if *seen == section => {}
+                Some(seen) => {
+                    if changed.is_none() {
+                        let byte_index = if seen.style == section.style {
+                            seen.len.min(section.len)
+                        } else {
+                            0
+                        };
+                        changed = Some((index, byte_index));
+                    }
+                    self.seen[index] = section;
+                }
+                None => {
+                    changed = changed.or(Some((index, 0)));
+                    self.seen.push(section);
+                }
+            }
+        }
+        if self.seen.len() > sections.len() {
+            changed = changed.or(Some((sections.len(), 0)));
+            self.seen.truncate(sections.len());
+        }
+
+        if let Some((section_index, byte_index)) = changed {
+            self.invalidate_from(section_index, byte_index);
+        }
+    }
+}
+
+/// Length & style of a section, as seen by a `LineIndex`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SeenSection {
+    len: usize,
+    style: (u32, u32, FontId, bool),
+}
+
+impl SeenSection {
+    #[inline]
+    fn new(section: &SectionText<'_>) -> Self {
+        Self {
+            len: section.text.len(),
+            style: (
+                section.scale.x.to_bits(),
+                section.scale.y.to_bits(),
+                section.font_id,
+                section.synthetic_bold,
+            ),
+        }
+    }
+}
+
+/// Lays out lines from `start`, calling `f` with each line, glyphs positioned relative to
+/// the line origin, & the line top relative to the content top. Stops when `f` returns
+/// `false`.
+///
+/// Returns `true` if all lines were laid out.
+fn lay_out_lines<L, F, S>(
+    layout: &Layout<L>,
+    fonts: &[F],
+    sections: &[S],
+    start: LineStart,
+    bound_w: f32,
+    word_cache: Option<&mut WordCache>,
+    mut f: impl FnMut(Line, f32) -> bool,
+) -> bool
+where
+    L: LineBreaker,
+    F: Font,
+    S: ToSectionText,
+{
+    let (line_breaker, white_space, overflow_wrap, paragraph, single_line) = match *layout {
+        Layout::SingleLine {
+            line_breaker,
+            white_space,
+            overflow_wrap,
+            ..
+        } => (
+            line_breaker,
+            white_space,
+            overflow_wrap,
+            ParagraphFormat::default(),
+            true,
+        ),
+        Layout::Wrap {
+            line_breaker,
+            white_space,
+            overflow_wrap,
+            paragraph,
+            ..
+        } => (line_breaker, white_space, overflow_wrap, paragraph, false),
+    };
+
+    // resume from the start character, glyph indices are relative to it
+    let sections = sections
+        .get(start.section_index..)
+        .unwrap_or_default()
+        .iter()
+        .enumerate()
+        .map(|(index, s)| {
+            let mut section = s.to_section_text();
+            if index == 0 {
+                section.text = section.text.get(start.byte_index..).unwrap_or_default();
+            }
+            section
+        });
+
+    let mut lines = Characters::new(fonts, sections, line_breaker, white_space)
+        .words(word_cache)
+        .lines(white_space.wrap_width(bound_w), overflow_wrap, paragraph);
+    lines.paragraph_start = start.paragraph_start;
+
+    let mut caret_y = start.top;
+    for mut line in lines {
+        for sg in &mut line.glyphs {
+            if sg.section_index == 0 {
+                sg.byte_index += start.byte_index;
+            }
+            sg.section_index += start.section_index;
+        }
+
+        caret_y += paragraph.baseline_snap(caret_y + line.max_v_metrics.ascent);
+        let line_height = layout.line_advance(&line);
+        if !f(line, caret_y) {
+            return false;
+        }
+        caret_y += line_height;
+
+        if single_line {
+            break;
+        }
+    }
+    true
+}
+
+/// Returns a hash of everything affecting line starts, except the sections & fonts.
+fn index_key<L: LineBreaker>(layout: &Layout<L>, bound_w: f32) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    layout.hash(&mut hasher);
+    bound_w.to_bits().hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use approx::assert_relative_eq;
+    use once_cell::sync::Lazy;
+
+    static FONTS: Lazy<[FontRef<'static>; 1]> = Lazy::new(|| {
+        [FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf")).unwrap()]
+    });
+
+    #[test]
+    fn scrolled_glyphs_match_full_layout() {
+        let paragraph = "Lorem ipsum dolor sit amet, consectetur adipiscing elit.\n".repeat(30);
+        let sections = [
+            SectionText {
+                text: &paragraph,
+                scale: PxScale::from(18.0),
+                ..SectionText::default()
+            },
+            SectionText {
+                text: &paragraph,
+                scale: PxScale::from(22.0),
+                font_id: FontId(0),
+                ..SectionText::default()
+            },
+        ];
+        let layout = Layout::default().paragraph(ParagraphFormat {
+            first_line_indent: 30.0,
+            ..ParagraphFormat::default()
+        });
+        let viewport = SectionGeometry {
+            screen_position: (10.0, 20.0),
+            bounds: (200.0, 150.0),
+        };
+
+        let (all_glyphs, all_lines) = layout.calculate_glyphs_and_lines(
+            &*FONTS,
+            &SectionGeometry {
+                bounds: (200.0, f32::INFINITY),
+                ..viewport
+            },
+            &sections,
+            &mut WordCache::new(),
+        );
+
+        let mut index = LineIndex::new();
+        assert_relative_eq!(
+            index.content_height(layout, &*FONTS, &viewport, &sections),
+            all_lines.last().unwrap().rect.max.y - 20.0,
+            epsilon = 0.01
+        );
+
+        // scroll into the middle of the wrapped paragraphs of the second section
+        for &scroll_offset in &[0.0, 1234.5, 3000.0] {
+            let scroll_layout =
+                index.scroll_layout(layout, &*FONTS, &viewport, &sections, scroll_offset);
+            let glyphs = scroll_layout.calculate_glyphs(&*FONTS, &viewport, &sections);
+
+            let expected: Vec<_> = all_lines
+                .iter()
+                .filter(|line| {
+                    line.rect.max.y - 20.0 > scroll_offset
+                        && line.rect.min.y - 20.0 < scroll_offset + 150.0
+                })
+                .flat_map(|line| all_glyphs[line.glyphs.clone()].iter())
+                .collect();
+
+            assert!(!glyphs.is_empty());
+            assert_eq!(glyphs.len(), expected.len(), "offset {}", scroll_offset);
+            for (glyph, expected) in glyphs.iter().zip(expected) {
+                assert_eq!(glyph.section_index, expected.section_index);
+                assert_eq!(glyph.byte_index, expected.byte_index);
+                assert_relative_eq!(glyph.glyph.position.x, expected.glyph.position.x);
+                assert_relative_eq!(
+                    glyph.glyph.position.y,
+                    expected.glyph.position.y - scroll_offset,
+                    epsilon = 0.01
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn index_rebuilt_on_text_change() {
+        let layout = Layout::default();
+        let geometry = SectionGeometry {
+            bounds: (500.0, 100.0),
+            ..SectionGeometry::default()
+        };
+        let text = |text| {
+            [SectionText {
+                text,
+                ..SectionText::default()
+            }]
+        };
+
+        let mut index = LineIndex::new();
+        let one_line = index.content_height(layout, &*FONTS, &geometry, &text("hello"));
+        let two_lines = index.content_height(layout, &*FONTS, &geometry, &text("hello\nworld"));
+        assert_relative_eq!(two_lines, one_line * 2.0);
+
+        let start = index
+            .scroll_layout(layout, &*FONTS, &geometry, &text("hello\nworld"), one_line)
+            .start;
+        assert_eq!(start.byte_index, 6);
+        assert_relative_eq!(start.top, one_line);
+        assert!(start.paragraph_start);
+    }
+
+    #[test]
+    fn appended_text_keeps_earlier_lines() {
+        let layout = Layout::default();
+        let geometry = SectionGeometry {
+            bounds: (300.0, 100.0),
+            ..SectionGeometry::default()
+        };
+        let mut log = "Lorem ipsum dolor sit amet, consectetur adipiscing elit.\n".repeat(20);
+        fn text(text: &str) -> [SectionText<'_>; 1] {
+            [SectionText {
+                text,
+                ..SectionText::default()
+            }]
+        }
+
+        let mut index = LineIndex::new();
+        index.content_height(layout, &*FONTS, &geometry, &text(&log));
+        let indexed = index.starts.clone();
+        assert!(indexed.len() > 20);
+
+        log.push_str("sed do eiusmod tempor");
+        let height = index.content_height(layout, &*FONTS, &geometry, &text(&log));
+
+        // only the last line is re-indexed
+        assert_eq!(
+            index.starts[..indexed.len() - 1],
+            indexed[..indexed.len() - 1]
+        );
+        let mut fresh = LineIndex::new();
+        assert_relative_eq!(
+            height,
+            fresh.content_height(layout, &*FONTS, &geometry, &text(&log))
+        );
+        assert_eq!(index.starts, fresh.starts);
+    }
+
+    #[test]
+    fn invalidate_from_reindexes_edited_lines() {
+        let layout = Layout::default();
+        let geometry = SectionGeometry {
+            bounds: (500.0, 100.0),
+            ..SectionGeometry::default()
+        };
+        let text = |text| {
+            [SectionText {
+                text,
+                ..SectionText::default()
+            }]
+        };
+
+        let mut index = LineIndex::new();
+        let five_lines = index.content_height(layout, &*FONTS, &geometry, &text("a\nb\nc\nd\ne"));
+        assert_eq!(index.starts.len(), 5);
+
+        // same length edit joining the last two lines keeps the lines before "c"
+        index.invalidate_from(0, 7);
+        assert_eq!(index.starts.len(), 3);
+        let four_lines = index.content_height(layout, &*FONTS, &geometry, &text("a\nb\nc\nd e"));
+        assert_relative_eq!(four_lines, five_lines * 4.0 / 5.0);
+        assert_eq!(index.starts.len(), 4);
+    }
+}
@@ -1,8 +1,10 @@
 use crate::FontId;
 use ab_glyph::*;
-use std::f32;
+use std::{f32, ops::Range};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct SectionGeometry {
     /// Position on screen to render text, in pixels from top-left. Defaults to (0, 0).
     pub screen_position: (f32, f32),
@@ -20,6 +22,11 @@ impl Default for SectionGeometry {
     }
 }
 
+/// Extra advance of synthetically emboldened glyphs as a fraction of the x-scale.
+///
+/// Matches `glyph_brush_draw_cache::SyntheticStyle::BOLD_STRENGTH`, the amount glyph outlines are widened.
+pub const SYNTHETIC_BOLD_STRENGTH: f32 = 1.0 / 24.0;
+
 /// Text to layout together using a font & scale.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct SectionText<'a> {
@@ -32,6 +39,9 @@ pub struct SectionText<'a> {
     /// It must be a valid id in the `FontMap` used for layout calls.
     /// The default `FontId(0)` should always be valid.
     pub font_id: FontId,
+    /// Widens glyph advances by [`SYNTHETIC_BOLD_STRENGTH`](constant.SYNTHETIC_BOLD_STRENGTH.html)
+    /// of the x-scale to fit synthetically emboldened glyphs. Defaults to `false`.
+    pub synthetic_bold: bool,
 }
 
 impl Default for SectionText<'static> {
@@ -41,6 +51,7 @@ impl Default for SectionText<'static> {
             text: "",
             scale: PxScale::from(16.0),
             font_id: FontId::default(),
+            synthetic_bold: false,
         }
     }
 }
@@ -65,13 +76,27 @@ impl ToSectionText for &SectionText<'_> {
 
 /// A positioned glyph with info relating to the `SectionText` from which it was derived.
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SectionGlyph {
     /// The `SectionText` index.
     pub section_index: usize,
     /// The character byte index from the `SectionText` text.
     pub byte_index: usize,
     /// A positioned glyph.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_remote::GlyphDef"))]
     pub glyph: Glyph,
     /// Font id.
     pub font_id: FontId,
 }
+
+/// A laid out line with its logical extent.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SectionLine {
+    /// Index range of the line's glyphs in the layout output.
+    pub glyphs: Range<usize>,
+    /// Logical rect of the line, spanning the advance widths of all characters, including
+    /// whitespace, & the full line height. Lines without visible glyphs have a rect too.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_remote::RectDef"))]
+    pub rect: Rect,
+}
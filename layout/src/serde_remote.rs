@@ -0,0 +1,118 @@
+//! Serde remote definitions for ab_glyph types.
+use ab_glyph::{Glyph, GlyphId, Point, PxScale, Rect};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "GlyphId")]
+struct GlyphIdDef(u16);
+
+/// Serde remote definition of [`PxScale`](../ab_glyph/struct.PxScale.html).
+///
+/// # Example
+/// ```
+/// # use glyph_brush_layout::ab_glyph::PxScale;
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Text {
+///     #[serde(with = "glyph_brush_layout::serde_remote::PxScaleDef")]
+///     scale: PxScale,
+/// }
+/// ```
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "PxScale")]
+pub struct PxScaleDef {
+    x: f32,
+    y: f32,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Point")]
+struct PointDef {
+    x: f32,
+    y: f32,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Rect")]
+pub(crate) struct RectDef {
+    #[serde(with = "PointDef")]
+    min: Point,
+    #[serde(with = "PointDef")]
+    max: Point,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Glyph")]
+pub(crate) struct GlyphDef {
+    #[serde(with = "GlyphIdDef")]
+    id: GlyphId,
+    #[serde(with = "PxScaleDef")]
+    scale: PxScale,
+    #[serde(with = "PointDef")]
+    position: Point,
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{ab_glyph::*, *};
+
+    #[test]
+    fn layout_roundtrip() {
+        let layout = Layout::default_wrap()
+            .h_align(HorizontalAlign::Right)
+            .v_align(VerticalAlign::Center)
+            .white_space(WhiteSpace::PreLine)
+            .overflow_wrap(OverflowWrap::BreakWord)
+            .line_breaker(BuiltInLineBreaker::AnyCharLineBreaker);
+
+        let json = serde_json::to_string(&layout).unwrap();
+        let deserialized: Layout<BuiltInLineBreaker> = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, layout);
+    }
+
+    #[test]
+    fn layout_field_defaults() {
+        let json = r#"{ "Wrap": {
+            "line_breaker": "UnicodeLineBreaker",
+            "h_align": "Center",
+            "v_align": "Top"
+        } }"#;
+        let layout: Layout<BuiltInLineBreaker> = serde_json::from_str(json).unwrap();
+        assert_eq!(layout, Layout::default().h_align(HorizontalAlign::Center));
+    }
+
+    #[test]
+    fn section_glyphs_roundtrip() {
+        let font =
+            FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSansMono.ttf")).unwrap();
+        let glyphs = Layout::default().calculate_glyphs(
+            &[font],
+            &SectionGeometry {
+                screen_position: (12.5, 40.0),
+                bounds: (50.0, 100.0),
+            },
+            &[SectionText {
+                text: "Hello serde",
+                scale: PxScale { x: 18.0, y: 20.0 },
+                font_id: FontId(0),
+                ..<_>::default()
+            }],
+        );
+
+        let json = serde_json::to_string(&glyphs).unwrap();
+        let deserialized: Vec<SectionGlyph> = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, glyphs);
+    }
+
+    #[test]
+    fn section_geometry_defaults() {
+        let geometry: SectionGeometry =
+            serde_json::from_str(r#"{ "screen_position": [1.0, 2.0] }"#).unwrap();
+        assert_eq!(
+            geometry,
+            SectionGeometry {
+                screen_position: (1.0, 2.0),
+                ..SectionGeometry::default()
+            }
+        );
+    }
+}
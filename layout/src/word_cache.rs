@@ -0,0 +1,476 @@
+use crate::{
+    characters::Character,
+    words::{VMetrics, Word},
+    FontId, SectionGlyph,
+};
+use ab_glyph::*;
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap, HashSet},
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+
+/// Cache of measured words, i.e. glyph ids, relative positions & advance widths, keyed by
+/// word text, font & scale.
+///
+/// Allows layouts to skip glyph lookup, kerning & advance computation for words that have
+/// been seen before, e.g. identical words across many sections or when re-laying out a
+/// section with new bounds.
+///
+/// A cache must only be used with a single set of fonts as words are keyed by
+/// [`FontId`](struct.FontId.html).
+///
+/// # Example
+/// ```
+/// # use glyph_brush_layout::{ab_glyph::*, *};
+/// # let font = FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf")).unwrap();
+/// let mut word_cache = WordCache::new();
+///
+/// let text = [SectionText {
+///     text: "hello world",
+///     ..SectionText::default()
+/// }];
+/// let glyphs = Layout::default().calculate_glyphs_cached(
+///     &[&font],
+///     &SectionGeometry::default(),
+///     &text,
+///     &mut word_cache,
+/// );
+/// assert_eq!(word_cache.len(), 2);
+///
+/// // remove words that haven't been used recently
+/// word_cache.trim();
+/// ```
+///
+/// # Parallel layouts
+/// A cache can be [`fork`](#method.fork)ed for each thread, sharing the cached words
+/// read-only, & the new words [`merge`](#method.merge)d back afterwards.
+/// ```
+/// # use glyph_brush_layout::{ab_glyph::*, *};
+/// # let font = FontRef::try_from_slice(include_bytes!("../../fonts/DejaVuSans.ttf")).unwrap();
+/// # let text = [SectionText { text: "hello world", ..SectionText::default() }];
+/// let mut word_cache = WordCache::new();
+///
+/// let mut forked = word_cache.fork();
+/// Layout::default().calculate_glyphs_cached(
+///     &[&font],
+///     &SectionGeometry::default(),
+///     &text,
+///     &mut forked,
+/// );
+/// word_cache.merge(forked);
+/// assert_eq!(word_cache.len(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct WordCache {
+    words: Arc<HashMap<u64, CachedWord>>,
+    /// Read-only words of the cache this was forked from.
+    shared: Option<Arc<HashMap<u64, CachedWord>>>,
+    /// Shared words used by this fork.
+    shared_used: HashSet<u64>,
+    max_words: usize,
+}
+
+impl Default for WordCache {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WordCache {
+    /// Default [`max_words`](#method.with_max_words).
+    pub const DEFAULT_MAX_WORDS: usize = 8192;
+
+    /// Returns a new empty cache.
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_max_words(Self::DEFAULT_MAX_WORDS)
+    }
+
+    /// Returns a new empty cache that [`trim`](#method.trim) will reduce when holding
+    /// more than `max_words`.
+    #[inline]
+    pub fn with_max_words(max_words: usize) -> Self {
+        Self {
+            words: <_>::default(),
+            shared: None,
+            shared_used: <_>::default(),
+            max_words,
+        }
+    }
+
+    /// Returns a new cache that reads the words of this cache, without copying them, &
+    /// caches new words itself. New words can be added back with [`merge`](#method.merge).
+    ///
+    /// Modifying this cache while forks exist will copy the words.
+    #[inline]
+    pub fn fork(&self) -> Self {
+        Self {
+            shared: Some(Arc::clone(&self.words)),
+            ..Self::with_max_words(self.max_words)
+        }
+    }
+
+    /// Adds the words cached by a [`fork`](#method.fork) of this cache, marking shared
+    /// words it used as used.
+    pub fn merge(&mut self, fork: WordCache) {
+        let WordCache {
+            words: fork_words,
+            shared,
+            shared_used,
+            ..
+        } = fork;
+        // release the fork's reference so the words are not copied
+        drop(shared);
+
+        let words = Arc::make_mut(&mut self.words);
+        for hash in shared_used {
+            if let Some(word) = words.get_mut(&hash) {
+                word.used = true;
+            }
+        }
+        match Arc::try_unwrap(fork_words) {
+            Ok(fork_words) => words.extend(fork_words),
+            Err(fork_words) => words.extend(fork_words.iter().map(|(k, v)| (*k, v.clone()))),
+        }
+    }
+
+    /// Returns the number of cached words, not including words shared by the cache
+    /// this was forked from.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Returns true if no words are cached.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Removes all cached words.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.words = <_>::default();
+        self.shared = None;
+        self.shared_used.clear();
+    }
+
+    /// Removes cached words of fonts for which `keep` returns `false`, e.g. fonts that
+    /// will no longer be used, so their `FontId`s can be reused.
+    pub fn retain_fonts<P: FnMut(FontId) -> bool>(&mut self, mut keep: P) {
+        Arc::make_mut(&mut self.words).retain(|_, word| keep(word.key.font_id));
+    }
+
+    /// If holding more than `max_words`, removes words that have not been used since the
+    /// previous call to `trim`.
+    ///
+    /// Should be called regularly, e.g. once per frame.
+    pub fn trim(&mut self) {
+        let words = Arc::make_mut(&mut self.words);
+        if words.len() > self.max_words {
+            words.retain(|_, word| word.used);
+        }
+        words.values_mut().for_each(|word| word.used = false);
+    }
+
+    /// Returns the word of the input characters, using a cached measurement if available.
+    pub(crate) fn word<F: Font>(&mut self, chars: &[Character<'_, F>], hard_break: bool) -> Word {
+        let key = match WordKey::new(chars) {
+            Some(key) => key,
+            // mixed font/scale words are not cached
+            None => return Word::measure(chars, hard_break),
+        };
+        let hash = key.hash_with(chars);
+
+        let words = Arc::make_mut(&mut self.words);
+        if let Some(cached) = words.get_mut(&hash) {
+            if cached.key == key && cached.matches(chars) {
+                cached.used = true;
+                return cached.to_word(chars, hard_break);
+            }
+        }
+        if let Some(cached) = self.shared.as_ref().and_then(|shared| shared.get(&hash)) {
+            if cached.key == key && cached.matches(chars) {
+                self.shared_used.insert(hash);
+                return cached.to_word(chars, hard_break);
+            }
+        }
+
+        let word = Word::measure(chars, hard_break);
+        words.insert(hash, CachedWord::new(key, chars, &word));
+        word
+    }
+}
+
+/// Font & scale shared by all characters of a cachable word.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct WordKey {
+    font_id: FontId,
+    scale: PxScale,
+    bold_advance: f32,
+}
+
+impl WordKey {
+    /// Returns the shared key of the characters, or `None` if they use
+    /// different fonts or scales.
+    #[inline]
+    fn new<F: Font>(chars: &[Character<'_, F>]) -> Option<Self> {
+        let first = chars.first()?;
+        let key = Self {
+            font_id: first.font_id,
+            scale: first.scale_font.scale,
+            bold_advance: first.bold_advance,
+        };
+        let shared = chars.iter().all(|c| {
+            c.font_id == key.font_id
+                && c.scale_font.scale == key.scale
+                && c.bold_advance.to_bits() == key.bold_advance.to_bits()
+        });
+        Some(key).filter(|_| shared)
+    }
+
+    #[inline]
+    fn hash_with<F: Font>(&self, chars: &[Character<'_, F>]) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        (
+            self.font_id,
+            self.scale.x.to_bits(),
+            self.scale.y.to_bits(),
+            self.bold_advance.to_bits(),
+        )
+            .hash(&mut hasher);
+        for c in chars {
+            (c.c, c.control, c.whitespace).hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CachedWord {
+    key: WordKey,
+    /// `(char, control, whitespace)` of each character.
+    chars: Box<[(char, bool, bool)]>,
+    /// Glyph id & relative x position of each non-control character.
+    glyphs: Box<[(GlyphId, f32)]>,
+    layout_width: f32,
+    layout_width_no_trail: f32,
+    max_v_metrics: VMetrics,
+    /// Indicates the word has been used since the last trim.
+    used: bool,
+}
+
+impl CachedWord {
+    #[inline]
+    fn new<F: Font>(key: WordKey, chars: &[Character<'_, F>], word: &Word) -> Self {
+        Self {
+            key,
+            chars: chars
+                .iter()
+                .map(|c| (c.c, c.control, c.whitespace))
+                .collect(),
+            glyphs: word
+                .glyphs
+                .iter()
+                .map(|sg| (sg.glyph.id, sg.glyph.position.x))
+                .collect(),
+            layout_width: word.layout_width,
+            layout_width_no_trail: word.layout_width_no_trail,
+            max_v_metrics: word.max_v_metrics.clone(),
+            used: true,
+        }
+    }
+
+    /// Returns true if this was cached from equivalent characters, guarding against
+    /// hash collisions.
+    #[inline]
+    fn matches<F: Font>(&self, chars: &[Character<'_, F>]) -> bool {
+        self.chars.len() == chars.len()
+            && self
+                .chars
+                .iter()
+                .zip(chars)
+                .all(|(cached, c)| *cached == (c.c, c.control, c.whitespace))
+    }
+
+    #[inline]
+    fn to_word<F: Font>(&self, chars: &[Character<'_, F>], hard_break: bool) -> Word {
+        let glyphs = chars
+            .iter()
+            .filter(|c| !c.control)
+            .zip(self.glyphs.iter())
+            .map(|(c, &(id, x))| SectionGlyph {
+                section_index: c.section_index,
+                byte_index: c.byte_index,
+                glyph: Glyph {
+                    id,
+                    scale: self.key.scale,
+                    position: point(x, 0.0),
+                },
+                font_id: c.font_id,
+            })
+            .collect();
+
+        Word {
+            glyphs,
+            layout_width: self.layout_width,
+            layout_width_no_trail: self.layout_width_no_trail,
+            max_v_metrics: self.max_v_metrics.clone(),
+            hard_break,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::*;
+    use once_cell::sync::Lazy;
+
+    static FONT: Lazy<FontRef<'static>> = Lazy::new(|| {
+        FontRef::try_from_slice(include_bytes!("../../fonts/OpenSans-Light.ttf")).unwrap()
+    });
+
+    const TEXT: &str = "Lorem ipsum dolor sit amet, lorem ipsum AV  Wa.\n\nLorem  ipsum";
+
+    #[test]
+    fn cached_layout_matches_uncached() {
+        let mut word_cache = WordCache::new();
+        let text = [
+            SectionText {
+                text: TEXT,
+                scale: PxScale::from(20.0),
+                ..SectionText::default()
+            },
+            // word spanning sections
+            SectionText {
+                text: "dolor",
+                scale: PxScale::from(20.0),
+                ..SectionText::default()
+            },
+            SectionText {
+                text: "sit amet",
+                scale: PxScale::from(24.0),
+                synthetic_bold: true,
+                ..SectionText::default()
+            },
+        ];
+
+        let mut cached_words = vec![];
+        for width in &[f32::INFINITY, 300.0, 120.0, 300.0] {
+            for layout in &[
+                Layout::default(),
+                Layout::default().white_space(WhiteSpace::Normal),
+                Layout::default().overflow_wrap(OverflowWrap::BreakWord),
+            ] {
+                let geometry = SectionGeometry {
+                    bounds: (*width, f32::INFINITY),
+                    ..SectionGeometry::default()
+                };
+                let uncached = layout.calculate_glyphs(&[&*FONT], &geometry, &text);
+                let cached =
+                    layout.calculate_glyphs_cached(&[&*FONT], &geometry, &text, &mut word_cache);
+                assert_eq!(cached, uncached, "{:?} {}", layout, width);
+            }
+            cached_words.push(word_cache.len());
+        }
+
+        // new bounds re-use the cached words
+        assert!(cached_words[0] > 0);
+        assert!(cached_words.iter().all(|n| *n == cached_words[0]));
+    }
+
+    #[test]
+    fn mixed_scale_words_are_not_cached() {
+        let mut word_cache = WordCache::new();
+        let text = [
+            SectionText {
+                text: "mix",
+                scale: PxScale::from(20.0),
+                ..SectionText::default()
+            },
+            SectionText {
+                text: "ed",
+                scale: PxScale::from(30.0),
+                ..SectionText::default()
+            },
+        ];
+
+        Layout::default().calculate_glyphs_cached(
+            &[&*FONT],
+            &SectionGeometry::default(),
+            &text,
+            &mut word_cache,
+        );
+        assert!(word_cache.is_empty());
+    }
+
+    #[test]
+    fn trim_unused_words() {
+        let mut word_cache = WordCache::with_max_words(2);
+        let layout = |text, word_cache: &mut WordCache| {
+            Layout::default().calculate_glyphs_cached(
+                &[&*FONT],
+                &SectionGeometry::default(),
+                &[SectionText {
+                    text,
+                    ..SectionText::default()
+                }],
+                word_cache,
+            );
+        };
+
+        layout("one two", &mut word_cache);
+        word_cache.trim();
+        assert_eq!(word_cache.len(), 2, "within max_words nothing is trimmed");
+
+        layout("three", &mut word_cache);
+        word_cache.trim();
+        assert_eq!(
+            word_cache.len(),
+            1,
+            "only words used since the last trim remain"
+        );
+
+        word_cache.trim();
+        assert_eq!(word_cache.len(), 1);
+    }
+
+    #[test]
+    fn fork_shares_words_and_merges_new() {
+        let layout = |text, word_cache: &mut WordCache| {
+            Layout::default().calculate_glyphs_cached(
+                &[&*FONT],
+                &SectionGeometry::default(),
+                &[SectionText {
+                    text,
+                    ..SectionText::default()
+                }],
+                word_cache,
+            )
+        };
+
+        let mut word_cache = WordCache::with_max_words(2);
+        layout("one two", &mut word_cache);
+        word_cache.trim();
+
+        let mut fork = word_cache.fork();
+        assert_eq!(
+            layout("one three", &mut fork),
+            layout("one three", &mut WordCache::new())
+        );
+        assert_eq!(fork.len(), 1, "shared words are not re-cached");
+
+        word_cache.merge(fork);
+        assert_eq!(word_cache.len(), 3);
+
+        // words used by the fork are kept
+        word_cache.trim();
+        assert_eq!(word_cache.len(), 2);
+        let mut fork = word_cache.fork();
+        layout("one three", &mut fork);
+        assert!(fork.is_empty());
+    }
+}
@@ -2,10 +2,11 @@ use crate::{
     characters::{Character, Characters},
     linebreak::{LineBreak, LineBreaker},
     lines::Lines,
-    SectionGlyph, SectionText,
+    OverflowWrap, ParagraphFormat, SectionGlyph, SectionText, WordCache,
 };
 use ab_glyph::*;
 use std::iter::{FusedIterator, Iterator, Peekable};
+use unicode_segmentation::UnicodeSegmentation;
 
 #[derive(Clone, Debug, Default)]
 pub(crate) struct VMetrics {
@@ -55,6 +56,76 @@ pub(crate) struct Word {
     pub hard_break: bool,
 }
 
+impl Word {
+    /// Splits the word into a leading part no wider than `width` & the remainder,
+    /// which is repositioned to start from 0. The leading part will contain at least
+    /// one glyph even if it is wider than `width`.
+    ///
+    /// Splits only happen at grapheme cluster boundaries, so combining marks & other
+    /// cluster continuations stay with their base character. `chars` are the characters
+    /// this word, or the word it was split from, was measured from.
+    ///
+    /// Returns `Err(self)` if the word cannot be split.
+    pub(crate) fn split_at_width<F: Font>(
+        mut self,
+        width: f32,
+        chars: &[Character<'_, F>],
+    ) -> Result<(Word, Word), Word> {
+        let glyph_x = |glyphs: &[SectionGlyph], idx: usize| {
+            glyphs
+                .get(idx)
+                .map(|sg| sg.glyph.position.x)
+                .unwrap_or(self.layout_width)
+        };
+
+        let grapheme_starts = grapheme_starts(chars);
+        debug_assert!(grapheme_starts.len() >= self.glyphs.len());
+        // this word's glyphs are the last of the measured word's glyphs
+        let grapheme_starts = &grapheme_starts[grapheme_starts.len() - self.glyphs.len()..];
+
+        let mut split = None;
+        for (idx, _) in grapheme_starts
+            .iter()
+            .enumerate()
+            .skip(1)
+            .filter(|(_, s)| **s)
+        {
+            let x = glyph_x(&self.glyphs, idx);
+            if split.is_some() && x > width && !approx::relative_eq!(x, width) {
+                break;
+            }
+            split = Some(idx);
+        }
+
+        let split = match split {
+            Some(idx) => idx,
+            None => return Err(self),
+        };
+        let split_x = glyph_x(&self.glyphs, split);
+
+        let mut tail_glyphs = self.glyphs.split_off(split);
+        for sg in &mut tail_glyphs {
+            sg.glyph.position.x -= split_x;
+        }
+
+        let tail = Word {
+            glyphs: tail_glyphs,
+            layout_width: self.layout_width - split_x,
+            layout_width_no_trail: (self.layout_width_no_trail - split_x).max(0.0),
+            max_v_metrics: self.max_v_metrics.clone(),
+            hard_break: self.hard_break,
+        };
+        let head = Word {
+            glyphs: self.glyphs,
+            layout_width: split_x,
+            layout_width_no_trail: split_x,
+            max_v_metrics: self.max_v_metrics,
+            hard_break: false,
+        };
+        Ok((head, tail))
+    }
+}
+
 /// `Word` iterator.
 pub(crate) struct Words<'a, 'b, L, F, S>
 where
@@ -63,6 +134,9 @@ where
     S: Iterator<Item = SectionText<'a>>,
 {
     pub(crate) characters: Peekable<Characters<'a, 'b, L, F, S>>,
+    pub(crate) word_cache: Option<&'b mut WordCache>,
+    /// Reusable buffer of the current word characters.
+    pub(crate) chars: Vec<Character<'b, F>>,
 }
 
 impl<'a, 'b, L, F, S> Words<'a, 'b, L, F, S>
@@ -71,10 +145,19 @@ where
     F: Font,
     S: Iterator<Item = SectionText<'a>>,
 {
-    pub(crate) fn lines(self, width_bound: f32) -> Lines<'a, 'b, L, F, S> {
+    pub(crate) fn lines(
+        self,
+        width_bound: f32,
+        overflow_wrap: OverflowWrap,
+        paragraph: ParagraphFormat,
+    ) -> Lines<'a, 'b, L, F, S> {
         Lines {
-            words: self.peekable(),
+            words: self,
             width_bound,
+            overflow_wrap,
+            paragraph,
+            paragraph_start: true,
+            pending: None,
         }
     }
 }
@@ -89,28 +172,65 @@ where
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
+        self.chars.clear();
+        let mut hard_break = false;
+
+        for character in &mut self.characters {
+            let line_break = character.line_break;
+            self.chars.push(character);
+
+            if line_break.is_some() {
+                if let Some(LineBreak::Hard(..)) = line_break {
+                    hard_break = true;
+                } else if self.characters.peek().is_none() {
+                    // simulate hard-break at end of all sections
+                    hard_break = true;
+                }
+                break;
+            }
+        }
+
+        if self.chars.is_empty() {
+            return None;
+        }
+
+        Some(match self.word_cache.as_mut() {
+            Some(word_cache) => word_cache.word(&self.chars, hard_break),
+            None => Word::measure(&self.chars, hard_break),
+        })
+    }
+}
+
+impl Word {
+    /// Returns the word of the input characters, positioning glyphs using font
+    /// advances & kerning.
+    pub(crate) fn measure<F: Font>(chars: &[Character<'_, F>], hard_break: bool) -> Word {
         let mut glyphs = Vec::new();
         let mut caret = 0.0;
         let mut caret_no_trail = caret;
         let mut last_glyph_id = None;
         let mut max_v_metrics = VMetrics::default();
-        let mut hard_break = false;
-        let mut progress = false;
 
         for Character {
-            mut glyph,
+            c,
             scale_font,
             font_id,
-            line_break,
+            bold_advance,
             control,
             whitespace,
             section_index,
             byte_index,
-        } in &mut self.characters
+            ..
+        } in chars
         {
-            progress = true;
+            max_v_metrics = max_v_metrics.max((*scale_font).into());
+
+            if *control && *whitespace {
+                // collapsed whitespace & whitespace controls are not kerned
+                continue;
+            }
 
-            max_v_metrics = max_v_metrics.max(scale_font.into());
+            let mut glyph = scale_font.scaled_glyph(*c);
 
             if let Some(id) = last_glyph_id.take() {
                 caret += scale_font.kern(id, glyph.id);
@@ -118,14 +238,14 @@ where
             last_glyph_id = Some(glyph.id);
 
             if !control {
-                let advance_width = scale_font.h_advance(glyph.id);
+                let advance_width = scale_font.h_advance(glyph.id) + bold_advance;
 
                 glyph.position = point(caret, 0.0);
                 glyphs.push(SectionGlyph {
-                    section_index,
-                    byte_index,
+                    section_index: *section_index,
+                    byte_index: *byte_index,
                     glyph,
-                    font_id,
+                    font_id: *font_id,
                 });
                 caret += advance_width;
 
@@ -134,32 +254,38 @@ where
                     caret_no_trail = caret;
                 }
             }
-
-            if line_break.is_some() {
-                if let Some(LineBreak::Hard(..)) = line_break {
-                    hard_break = true;
-                } else if self.characters.peek().is_none() {
-                    // simulate hard-break at end of all sections
-                    hard_break = true;
-                }
-                break;
-            }
         }
 
-        if progress {
-            return Some(Word {
-                glyphs,
-                layout_width: caret,
-                layout_width_no_trail: caret_no_trail,
-                hard_break,
-                max_v_metrics,
-            });
+        Word {
+            glyphs,
+            layout_width: caret,
+            layout_width_no_trail: caret_no_trail,
+            hard_break,
+            max_v_metrics,
         }
-
-        None
     }
 }
 
+/// Returns whether each non-control character, i.e. each glyph of the measured word,
+/// starts a grapheme cluster.
+fn grapheme_starts<F: Font>(chars: &[Character<'_, F>]) -> Vec<bool> {
+    let text: String = chars.iter().map(|c| c.c).collect();
+    let mut starts = text.grapheme_indices(true).map(|(idx, _)| idx).peekable();
+
+    let mut byte_index = 0;
+    chars
+        .iter()
+        .filter_map(|c| {
+            let start = starts.peek() == Some(&byte_index);
+            if start {
+                starts.next();
+            }
+            byte_index += c.c.len_utf8();
+            Some(start).filter(|_| !c.control)
+        })
+        .collect()
+}
+
 impl<'a, L, F, S> FusedIterator for Words<'a, '_, L, F, S>
 where
     L: LineBreaker,